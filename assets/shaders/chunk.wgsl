@@ -20,6 +20,7 @@
 
 @group(2) @binding(100) var my_array_texture: texture_2d_array<f32>;
 @group(2) @binding(101) var my_array_texture_sampler: sampler;
+@group(2) @binding(102) var<uniform> sky_light: f32;
 
 struct Vertex {
 	@builtin(instance_index) instance_index: u32,
@@ -64,6 +65,8 @@ fn fragment(
 	var pbr_input = pbr_input_from_standard_material(in, is_front);
 	// get color from array texture
 	pbr_input.material.base_color = textureSample(my_array_texture, my_array_texture_sampler, in.uv, i32(block_id));
+	// darken the blocks while the sun is down
+	pbr_input.material.base_color = vec4<f32>(pbr_input.material.base_color.rgb * sky_light, pbr_input.material.base_color.a);
 
 #ifdef PREPASS_PIPELINE
 	// in deferred mode we can't modify anything after that, as lighting is run in a separate fullscreen shader.
@@ -33,6 +33,11 @@ impl Axis {
 	pub fn all() -> AxisIter {
 		AxisIter { index: 0 }
 	}
+
+	/// the index of the axis in a vector
+	pub const fn index(self) -> usize {
+		axis_to_index(self)
+	}
 }
 
 pub struct AxisIter {
@@ -0,0 +1,41 @@
+//! hosts a world without opening a window, so that it can run on a machine without a GPU.<br>
+//! players join it with the "Join Server" button, and commands like `/stop` are typed into the terminal.
+//!
+//! ```text
+//! cargo run --bin server -- [--port N] [--preset NAME] [--seed N]
+//! ```
+//!
+//! the world is saved in the same place as the world of the game.
+//! `--preset` and `--seed` are only used if there is no saved world yet.
+
+use bevy::app::AppExit;
+use std::{env, error::Error};
+use voxel_game::headless::{self, ServerOptions};
+
+fn main() -> AppExit {
+	match parse_args() {
+		Ok(options) => headless::run(options),
+		Err(e) => {
+			eprintln!("{}", e);
+			eprintln!("usage: server [--port N] [--preset NAME] [--seed N]");
+			AppExit::error()
+		}
+	}
+}
+
+fn parse_args() -> Result<ServerOptions, Box<dyn Error>> {
+	let mut options = ServerOptions::default();
+	let mut iter = env::args().skip(1);
+	while let Some(flag) = iter.next() {
+		let value = iter
+			.next()
+			.ok_or_else(|| format!("missing value for {}", flag))?;
+		match flag.as_str() {
+			"--port" => options.port = value.parse()?,
+			"--preset" => options.preset = value,
+			"--seed" => options.seed = value.parse()?,
+			_ => return Err(format!("unknown argument: {}", flag).into()),
+		}
+	}
+	Ok(options)
+}
@@ -0,0 +1,200 @@
+//! renders top down images of a world, using the same world generation as the game.<br>
+//! this does not open a window, so it can also be used without a GPU.
+//!
+//! ```text
+//! cargo run --bin worldgen_preview -- [--seed N] [--preset NAME] [--x X] [--z Z] [--size N] [--slice-y Y] [--out FOLDER]
+//! ```
+//!
+//! creates these images in the output folder, where each pixel is one block column:
+//! - `heights.png`: the height of the terrain, from dark (low) to bright (high)
+//! - `surface.png`: the color of the highest block
+//! - `caves.png`: the blocks at the height given by `--slice-y`, with air being black
+//! - `trees.png`: the height of the terrain, with every column that contains a log in red
+
+use image::{Rgb, RgbImage};
+use std::{collections::HashSet, env, error::Error, fs, path::PathBuf, process::ExitCode};
+use voxel_game::{
+	block::prelude::*,
+	game_world::{
+		chunk::{IsLoaded, CHUNK_LENGTH},
+		fully_generate_chunk, get_height_at_with_seed, GameWorld, WorldGenPreset,
+	},
+	pos::{BlockPos, ChunkPos},
+};
+
+/// how many blocks above the terrain are searched for the highest block, so that trees are included
+const ABOVE_TERRAIN: i32 = 12;
+
+struct Args {
+	seed: u32,
+	preset: String,
+	center_x: i32,
+	center_z: i32,
+	size: u32,
+	slice_y: i32,
+	out: PathBuf,
+}
+
+fn main() -> ExitCode {
+	let args = match parse_args() {
+		Ok(args) => args,
+		Err(e) => {
+			eprintln!("{}", e);
+			eprintln!(
+				"usage: worldgen_preview [--seed N] [--preset NAME] [--x X] [--z Z] [--size N] [--slice-y Y] [--out FOLDER]"
+			);
+			return ExitCode::FAILURE;
+		}
+	};
+	match render(&args) {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(e) => {
+			eprintln!("failed to render the preview: {}", e);
+			ExitCode::FAILURE
+		}
+	}
+}
+
+fn parse_args() -> Result<Args, Box<dyn Error>> {
+	let mut args = Args {
+		seed: 0,
+		preset: "Default".to_owned(),
+		center_x: 0,
+		center_z: 0,
+		size: 256,
+		slice_y: -20,
+		out: PathBuf::from("worldgen_preview"),
+	};
+	let mut iter = env::args().skip(1);
+	while let Some(flag) = iter.next() {
+		let value = iter
+			.next()
+			.ok_or_else(|| format!("missing value for {}", flag))?;
+		match flag.as_str() {
+			"--seed" => args.seed = value.parse()?,
+			"--preset" => args.preset = value,
+			"--x" => args.center_x = value.parse()?,
+			"--z" => args.center_z = value.parse()?,
+			"--size" => args.size = value.parse()?,
+			"--slice-y" => args.slice_y = value.parse()?,
+			"--out" => args.out = PathBuf::from(value),
+			_ => return Err(format!("unknown argument: {}", flag).into()),
+		}
+	}
+	Ok(args)
+}
+
+fn render(args: &Args) -> Result<(), Box<dyn Error>> {
+	let preset = WorldGenPreset::all()
+		.into_iter()
+		.find(|p| p.to_string().eq_ignore_ascii_case(&args.preset))
+		.ok_or_else(|| format!("unknown preset: {}", args.preset))?;
+	let mut world = GameWorld {
+		seed: args.seed,
+		worldgen_settings: preset.to_settings()?,
+		..Default::default()
+	};
+
+	let size = args.size as i32;
+	let min_x = args.center_x - size / 2;
+	let min_z = args.center_z - size / 2;
+	let columns = || (0..size).flat_map(move |z| (0..size).map(move |x| (x, z)));
+
+	let heights = columns()
+		.map(|(x, z)| {
+			let settings = &world.worldgen_settings;
+			get_height_at_with_seed(min_x + x, min_z + z, world.seed, settings)
+		})
+		.collect::<Vec<_>>();
+	let min_height = heights.iter().copied().min().unwrap_or(0);
+	let max_height = heights.iter().copied().max().unwrap_or(0);
+
+	// generate every chunk that is needed for any of the images
+	let chunk_length = CHUNK_LENGTH as i32;
+	let mut chunk_ys = (min_height.div_euclid(chunk_length)
+		..=(max_height + ABOVE_TERRAIN).div_euclid(chunk_length))
+		.collect::<HashSet<_>>();
+	chunk_ys.insert(args.slice_y.div_euclid(chunk_length));
+	for cx in min_x.div_euclid(chunk_length)..=(min_x + size - 1).div_euclid(chunk_length) {
+		for cz in min_z.div_euclid(chunk_length)..=(min_z + size - 1).div_euclid(chunk_length) {
+			for &cy in &chunk_ys {
+				fully_generate_chunk(&mut world, ChunkPos::new(cx, cy, cz), IsLoaded::NOT_LOADED);
+			}
+		}
+	}
+
+	let mut height_image = RgbImage::new(args.size, args.size);
+	let mut surface_image = RgbImage::new(args.size, args.size);
+	let mut cave_image = RgbImage::new(args.size, args.size);
+	let mut tree_image = RgbImage::new(args.size, args.size);
+	for ((x, z), height) in columns().zip(&heights) {
+		let [block_x, block_z] = [min_x + x, min_z + z];
+		let pixel = (x as u32, z as u32);
+
+		let range = (max_height - min_height).max(1) as f32;
+		let brightness = ((height - min_height) as f32 / range * 255.0) as u8;
+		height_image.put_pixel(pixel.0, pixel.1, Rgb([brightness; 3]));
+
+		let top = max_height + ABOVE_TERRAIN;
+		let column = (min_height - 1..=top)
+			.rev()
+			.map(|y| get_block(&world, BlockPos::new(block_x, y, block_z)));
+		let mut has_log = false;
+		let mut surface = None;
+		for block in column {
+			has_log |= block == Log::BLOCK;
+			if surface.is_none() && block != Air::BLOCK {
+				surface = Some(block);
+			}
+		}
+		let surface_color = surface.map(block_color).unwrap_or([0; 3]);
+		surface_image.put_pixel(pixel.0, pixel.1, Rgb(surface_color));
+
+		let slice = get_block(&world, BlockPos::new(block_x, args.slice_y, block_z));
+		cave_image.put_pixel(pixel.0, pixel.1, Rgb(block_color(slice)));
+
+		let tree_color = if has_log {
+			[255, 0, 0]
+		} else {
+			[brightness / 2; 3]
+		};
+		tree_image.put_pixel(pixel.0, pixel.1, Rgb(tree_color));
+	}
+
+	fs::create_dir_all(&args.out)?;
+	height_image.save(args.out.join("heights.png"))?;
+	surface_image.save(args.out.join("surface.png"))?;
+	cave_image.save(args.out.join("caves.png"))?;
+	tree_image.save(args.out.join("trees.png"))?;
+	println!(
+		"rendered {}x{} blocks of preset {} with seed {} to {}",
+		args.size,
+		args.size,
+		preset,
+		args.seed,
+		args.out.display()
+	);
+	Ok(())
+}
+
+fn get_block(world: &GameWorld, pos: BlockPos) -> Block {
+	world.get_block_at(pos).copied().unwrap_or(Air::BLOCK)
+}
+
+/// a color that roughly matches the texture of the block
+fn block_color(block: Block) -> [u8; 3] {
+	match block.id {
+		Air::BLOCK_ID => [0, 0, 0],
+		Stone::BLOCK_ID => [125, 125, 125],
+		Cobblestone::BLOCK_ID => [100, 100, 100],
+		Dirt::BLOCK_ID => [134, 96, 67],
+		GrassBlock::BLOCK_ID => [95, 159, 53],
+		Log::BLOCK_ID => [102, 81, 51],
+		Planks::BLOCK_ID => [162, 130, 78],
+		Leaves::BLOCK_ID => [55, 105, 35],
+		Water::BLOCK_ID => [50, 90, 220],
+		Lava::BLOCK_ID => [230, 100, 20],
+		CraftingTable::BLOCK_ID => [120, 80, 45],
+		_ => [255, 0, 255],
+	}
+}
@@ -1,5 +1,12 @@
-use super::{Block, BlockData, BlockId};
-use crate::cuboid::Cuboid;
+use super::{
+	fluid::{FluidKind, FluidState},
+	Block, BlockData, BlockId,
+};
+use crate::{
+	cuboid::Cuboid,
+	item::{Item, ItemStack},
+};
+use rand::Rng;
 use std::fmt::Debug;
 
 /// The Trait for a Block that may contain BlockData
@@ -14,9 +21,25 @@ pub trait BlockTrait: Debug + Sized {
 	/// the given block, but calling this function assumes that it is valid.
 	unsafe fn from_data(data: BlockData) -> Self;
 
+	/// Turns the Block back into the data that [`BlockTrait::from_data`] takes
+	fn to_data(&self) -> BlockData {
+		BlockData(0)
+	}
+
+	fn to_block(&self) -> Block {
+		Block {
+			id: Self::BLOCK_ID,
+			data: self.to_data(),
+		}
+	}
+
 	/// whether you can place a block inside of this one
 	fn is_replacable(&self) -> bool;
 
+	/// how many seconds it takes to break the block without a tool.<br>
+	/// a hardness of `0.0` means that it breaks instantly
+	fn get_hardness(&self) -> f32;
+
 	/// gets the Volume where you can collide with the block
 	fn get_collision(&self) -> Vec<Cuboid>;
 
@@ -25,6 +48,17 @@ pub trait BlockTrait: Debug + Sized {
 		// TODO in the future more blocks will have other outlines
 		self.get_collision()
 	}
+
+	/// gets the fluid that fills this block, if there is any
+	fn get_fluid(&self) -> Option<(FluidKind, FluidState)> {
+		None
+	}
+
+	/// gets the items that are dropped when the block is broken.<br>
+	/// by default this is the block itself.
+	fn get_drops(&self, _rng: &mut impl Rng) -> Vec<ItemStack> {
+		vec![ItemStack::new(Item::Block(Self::BLOCK_ID), 1)]
+	}
 }
 
 /// The Trait for a Block that is guarenteed to never contain BlockData
@@ -36,9 +70,10 @@ pub trait BlockTrait: Debug + Sized {
 pub unsafe trait BlockWithoutData: BlockTrait {
 	const BLOCK: Block = Block {
 		id: Self::BLOCK_ID,
-		// data: BlockData(0),
+		data: BlockData(0),
 	};
 
+	#[allow(dead_code)]
 	fn new() -> Self {
 		// SAFETY: BlockWithoutData already assumes that this contains no data
 		unsafe { Self::from_data(BlockData(0)) }
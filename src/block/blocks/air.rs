@@ -4,7 +4,9 @@ use crate::{
 		BlockData, BlockId,
 	},
 	cuboid::Cuboid,
+	item::ItemStack,
 };
+use rand::Rng;
 use std::fmt::Debug;
 
 pub struct Air;
@@ -20,9 +22,17 @@ impl BlockTrait for Air {
 		true
 	}
 
+	fn get_hardness(&self) -> f32 {
+		0.0
+	}
+
 	fn get_collision(&self) -> Vec<Cuboid> {
 		Vec::new()
 	}
+
+	fn get_drops(&self, _rng: &mut impl Rng) -> Vec<ItemStack> {
+		Vec::new()
+	}
 }
 
 // SAFETY: Air is a Unit Type
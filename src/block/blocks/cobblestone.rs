@@ -21,6 +21,10 @@ impl BlockTrait for Cobblestone {
 		false
 	}
 
+	fn get_hardness(&self) -> f32 {
+		2.0
+	}
+
 	fn get_collision(&self) -> Vec<Cuboid> {
 		vec![Cuboid {
 			min: Vec3::ZERO,
@@ -0,0 +1,43 @@
+use crate::{
+	block::{
+		block_trait::{BlockTrait, BlockWithoutData},
+		BlockData, BlockId,
+	},
+	cuboid::Cuboid,
+};
+use bevy::math::Vec3;
+use std::fmt::Debug;
+
+pub struct CraftingTable;
+
+impl BlockTrait for CraftingTable {
+	const BLOCK_ID: BlockId = BlockId(10);
+
+	unsafe fn from_data(_data: BlockData) -> Self {
+		Self
+	}
+
+	fn is_replacable(&self) -> bool {
+		false
+	}
+
+	fn get_hardness(&self) -> f32 {
+		2.5
+	}
+
+	fn get_collision(&self) -> Vec<Cuboid> {
+		vec![Cuboid {
+			min: Vec3::ZERO,
+			max: Vec3::ONE,
+		}]
+	}
+}
+
+// SAFETY: CraftingTable is a Unit Type
+unsafe impl BlockWithoutData for CraftingTable {}
+
+impl Debug for CraftingTable {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, stringify!(CraftingTable))
+	}
+}
@@ -1,6 +1,7 @@
 use crate::{
 	block::{
-		block_trait::{BlockTrait, BlockWithoutData}, BlockData, BlockId
+		block_trait::{BlockTrait, BlockWithoutData},
+		BlockData, BlockId,
 	},
 	cuboid::Cuboid,
 };
@@ -20,6 +21,10 @@ impl BlockTrait for DebugBlock {
 		false
 	}
 
+	fn get_hardness(&self) -> f32 {
+		0.0
+	}
+
 	fn get_collision(&self) -> Vec<Cuboid> {
 		vec![Cuboid {
 			min: Vec3::ZERO,
@@ -21,6 +21,10 @@ impl BlockTrait for Dirt {
 		false
 	}
 
+	fn get_hardness(&self) -> f32 {
+		0.5
+	}
+
 	fn get_collision(&self) -> Vec<Cuboid> {
 		vec![Cuboid {
 			min: Vec3::ZERO,
@@ -1,11 +1,14 @@
+use super::dirt::Dirt;
 use crate::{
 	block::{
 		block_trait::{BlockTrait, BlockWithoutData},
 		BlockData, BlockId,
 	},
 	cuboid::Cuboid,
+	item::{Item, ItemStack},
 };
 use bevy::math::Vec3;
+use rand::Rng;
 use std::fmt::Debug;
 
 pub struct GrassBlock;
@@ -21,12 +24,20 @@ impl BlockTrait for GrassBlock {
 		false
 	}
 
+	fn get_hardness(&self) -> f32 {
+		0.6
+	}
+
 	fn get_collision(&self) -> Vec<Cuboid> {
 		vec![Cuboid {
 			min: Vec3::ZERO,
 			max: Vec3::ONE,
 		}]
 	}
+
+	fn get_drops(&self, _rng: &mut impl Rng) -> Vec<ItemStack> {
+		vec![ItemStack::new(Item::Block(Dirt::BLOCK_ID), 1)]
+	}
 }
 
 // SAFETY: GrassBlock is a Unit Type
@@ -0,0 +1,57 @@
+use crate::{
+	block::{
+		block_trait::BlockTrait,
+		fluid::{FluidKind, FluidState},
+		BlockData, BlockId,
+	},
+	cuboid::Cuboid,
+	item::ItemStack,
+};
+use rand::Rng;
+use std::fmt::Debug;
+
+pub struct Lava {
+	pub state: FluidState,
+}
+
+impl BlockTrait for Lava {
+	const BLOCK_ID: BlockId = BlockId(9);
+
+	unsafe fn from_data(data: BlockData) -> Self {
+		Self {
+			state: FluidState::from_data(data),
+		}
+	}
+
+	fn to_data(&self) -> BlockData {
+		self.state.to_data()
+	}
+
+	fn is_replacable(&self) -> bool {
+		true
+	}
+
+	fn get_hardness(&self) -> f32 {
+		f32::INFINITY
+	}
+
+	fn get_collision(&self) -> Vec<Cuboid> {
+		Vec::new()
+	}
+
+	fn get_fluid(&self) -> Option<(FluidKind, FluidState)> {
+		Some((FluidKind::Lava, self.state))
+	}
+
+	fn get_drops(&self, _rng: &mut impl Rng) -> Vec<ItemStack> {
+		Vec::new()
+	}
+}
+
+impl Debug for Lava {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct(stringify!(Lava))
+			.field("state", &self.state)
+			.finish()
+	}
+}
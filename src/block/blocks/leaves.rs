@@ -4,8 +4,10 @@ use crate::{
 		BlockData, BlockId,
 	},
 	cuboid::Cuboid,
+	item::{Item, ItemStack},
 };
 use bevy::math::Vec3;
+use rand::Rng;
 use std::fmt::Debug;
 
 pub struct Leaves;
@@ -21,12 +23,25 @@ impl BlockTrait for Leaves {
 		false
 	}
 
+	fn get_hardness(&self) -> f32 {
+		0.2
+	}
+
 	fn get_collision(&self) -> Vec<Cuboid> {
 		vec![Cuboid {
 			min: Vec3::ZERO,
 			max: Vec3::ONE,
 		}]
 	}
+
+	fn get_drops(&self, rng: &mut impl Rng) -> Vec<ItemStack> {
+		// most of the time leaves just disappear
+		if rng.gen_bool(0.2) {
+			vec![ItemStack::new(Item::Block(Self::BLOCK_ID), 1)]
+		} else {
+			Vec::new()
+		}
+	}
 }
 
 // SAFETY: Leaves is a Unit Type
@@ -1,10 +1,13 @@
 pub mod air;
 pub mod cobblestone;
+pub mod crafting_table;
 pub mod debug_block;
 pub mod debug_slab;
 pub mod dirt;
 pub mod grass_block;
+pub mod lava;
 pub mod leaves;
 pub mod log;
 pub mod planks;
 pub mod stone;
+pub mod water;
@@ -1,11 +1,14 @@
+use super::cobblestone::Cobblestone;
 use crate::{
 	block::{
 		block_trait::{BlockTrait, BlockWithoutData},
 		BlockData, BlockId,
 	},
 	cuboid::Cuboid,
+	item::{Item, ItemStack},
 };
 use bevy::math::Vec3;
+use rand::Rng;
 use std::fmt::Debug;
 
 pub struct Stone;
@@ -21,12 +24,20 @@ impl BlockTrait for Stone {
 		false
 	}
 
+	fn get_hardness(&self) -> f32 {
+		1.5
+	}
+
 	fn get_collision(&self) -> Vec<Cuboid> {
 		vec![Cuboid {
 			min: Vec3::ZERO,
 			max: Vec3::ONE,
 		}]
 	}
+
+	fn get_drops(&self, _rng: &mut impl Rng) -> Vec<ItemStack> {
+		vec![ItemStack::new(Item::Block(Cobblestone::BLOCK_ID), 1)]
+	}
 }
 
 // SAFETY: Stone is a Unit Type
@@ -0,0 +1,57 @@
+use crate::{
+	block::{
+		block_trait::BlockTrait,
+		fluid::{FluidKind, FluidState},
+		BlockData, BlockId,
+	},
+	cuboid::Cuboid,
+	item::ItemStack,
+};
+use rand::Rng;
+use std::fmt::Debug;
+
+pub struct Water {
+	pub state: FluidState,
+}
+
+impl BlockTrait for Water {
+	const BLOCK_ID: BlockId = BlockId(8);
+
+	unsafe fn from_data(data: BlockData) -> Self {
+		Self {
+			state: FluidState::from_data(data),
+		}
+	}
+
+	fn to_data(&self) -> BlockData {
+		self.state.to_data()
+	}
+
+	fn is_replacable(&self) -> bool {
+		true
+	}
+
+	fn get_hardness(&self) -> f32 {
+		f32::INFINITY
+	}
+
+	fn get_collision(&self) -> Vec<Cuboid> {
+		Vec::new()
+	}
+
+	fn get_fluid(&self) -> Option<(FluidKind, FluidState)> {
+		Some((FluidKind::Water, self.state))
+	}
+
+	fn get_drops(&self, _rng: &mut impl Rng) -> Vec<ItemStack> {
+		Vec::new()
+	}
+}
+
+impl Debug for Water {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct(stringify!(Water))
+			.field("state", &self.state)
+			.finish()
+	}
+}
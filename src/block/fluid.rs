@@ -0,0 +1,110 @@
+//! the properties of blocks that are filled with a fluid, like water or lava
+
+use super::{prelude::*, BlockData};
+use bevy::color::Color;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FluidKind {
+	Water,
+	Lava,
+}
+
+/// how far a fluid block is away from its source, and whether it is falling down
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FluidState {
+	/// how many blocks horizontally this is away from a source or falling fluid.<br>
+	/// a distance of 0 is the source itself
+	pub distance: u8,
+	/// whether the fluid is falling down from above,
+	/// which makes it behave like a source when spreading
+	pub falling: bool,
+}
+
+impl FluidKind {
+	pub fn block(self, state: FluidState) -> Block {
+		match self {
+			Self::Water => Water { state }.to_block(),
+			Self::Lava => Lava { state }.to_block(),
+		}
+	}
+
+	pub fn source_block(self) -> Block {
+		self.block(FluidState::SOURCE)
+	}
+
+	/// how many blocks the fluid can flow horizontally away from its source
+	pub fn max_distance(self) -> u8 {
+		match self {
+			Self::Water => 7,
+			Self::Lava => 3,
+		}
+	}
+
+	/// how many block ticks it takes for the fluid to flow one block further
+	pub fn tick_delay(self) -> u64 {
+		match self {
+			Self::Water => 5,
+			Self::Lava => 30,
+		}
+	}
+
+	/// the color of the surface of the fluid, including its transparency
+	pub fn color(self) -> Color {
+		match self {
+			Self::Water => Color::srgba(0.2, 0.35, 0.85, 0.6),
+			Self::Lava => Color::srgba(0.9, 0.35, 0.05, 0.95),
+		}
+	}
+
+	/// how strongly the fluid slows down entities moving through it
+	pub fn viscosity(self) -> f32 {
+		match self {
+			Self::Water => 1.0,
+			Self::Lava => 3.0,
+		}
+	}
+}
+
+impl FluidState {
+	pub const SOURCE: Self = Self {
+		distance: 0,
+		falling: false,
+	};
+
+	pub const FALLING: Self = Self {
+		distance: 0,
+		falling: true,
+	};
+
+	pub fn flowing(distance: u8) -> Self {
+		Self {
+			distance,
+			falling: false,
+		}
+	}
+
+	pub fn is_source(self) -> bool {
+		self == Self::SOURCE
+	}
+
+	/// how high the surface of the fluid is inside of the block, from 0 to 1
+	pub fn height(self) -> f32 {
+		if self.falling {
+			return 1.0;
+		}
+		// a source is not completely full, so that the surface is visible
+		(8 - self.distance.min(7)) as f32 / 9.0
+	}
+
+	/// the first 3 bits are the distance, the 4th bit is whether it is falling
+	pub(super) fn from_data(data: BlockData) -> Self {
+		Self {
+			distance: data.0 & 0b111,
+			falling: data.0 & 0b1000 != 0,
+		}
+	}
+
+	pub(super) fn to_data(self) -> BlockData {
+		BlockData(self.distance.min(7) | (self.falling as u8) << 3)
+	}
+}
@@ -1,15 +1,15 @@
-/// Turns a [`BlockId`](super::BlockId) into the struct implementing
+/// Turns a [`Block`](super::Block) into the struct implementing
 /// [`BlockTrait`](super::block_trait::BlockTrait), that has
 /// that [`BlockTrait:BLOCK_ID`](super::block_trait::BlockTrait::BLOCK_ID),
 /// and lets you use it in some expression.
 ///
 /// # Examples
 ///
-/// ```
-/// # use crate::block::BlockId;
-/// let id = BlockId::from_debug_name("Stone").unwrap();
-/// let is_replacable = match_block_id!(id, (block: Type) => {
-///     format!("{} | {}", Type::BlockId.0, block.is_replacable())
+/// ```ignore
+/// # use crate::block::{Block, BlockId};
+/// let block = Block::from_id(BlockId::from_debug_name("Stone").unwrap());
+/// let is_replacable = match_block_id!(block, (block: Type) => {
+///     format!("{} | {}", Type::BLOCK_ID.0, block.is_replacable())
 /// });
 /// assert_eq!(is_replacable, "1 | false");
 /// ```
@@ -25,9 +25,12 @@ macro_rules! match_block_id {
 			grass_block::GrassBlock,
 			log::Log,
 			planks::Planks,
+			crafting_table::CraftingTable,
 			leaves::Leaves,
 			debug_block::DebugBlock,
 			debug_slab::DebugSlab,
+			water::Water,
+			lava::Lava,
 		}
 	}};
 }
@@ -35,12 +38,13 @@ pub(crate) use match_block_id;
 
 macro_rules! big_match {
 	($block:expr, $block_var:ident, $block_type:ident, $expr:expr; $($t:ty),* $(,)?) => {
-		use $crate::block::block_trait::{BlockTrait, BlockWithoutData};
+		use $crate::block::block_trait::BlockTrait;
 		match $block {
 			$(
-				Block { id: <$t>::BLOCK_ID } => {
+				Block { id: <$t>::BLOCK_ID, data } => {
 					type $block_type = $t;
-					let $block_var = <$t>::new();
+					// SAFETY: the data of a `Block` is always valid for its id
+					let $block_var = unsafe { <$t>::from_data(data) };
 					$expr
 				}
 			)*
@@ -4,18 +4,25 @@
 
 mod block_trait;
 mod blocks;
+pub mod fluid;
 mod macros;
 pub mod prelude;
 
-use self::{macros::match_block_id, prelude::*};
-use crate::cuboid::Cuboid;
+use self::{
+	fluid::{FluidKind, FluidState},
+	macros::match_block_id,
+	prelude::*,
+};
+use crate::{cuboid::Cuboid, item::ItemStack};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Block {
 	pub id: BlockId,
-	// TODO block data
-	// pub data: BlockData,
+	/// extra information about the block, that is interpreted differently for every block.<br>
+	/// must always be valid for the block with the given id
+	pub data: BlockData,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -25,23 +32,84 @@ pub struct BlockId(u8);
 pub struct BlockData(u8);
 
 impl Block {
+	/// creates the block with the given id and the default data for that block
+	pub fn from_id(id: BlockId) -> Self {
+		Self {
+			id,
+			data: BlockData(0),
+		}
+	}
+
+	/// creates a block from data that was saved with [`Block::raw_data`].<br>
+	/// returns `None` if the id is unknown, or the data is not valid for the block with that id.
+	pub fn from_raw_data(id: BlockId, data: u8) -> Option<Self> {
+		if !BlockId::ALL.contains(&id) {
+			return None;
+		}
+		let block = Self {
+			id,
+			data: BlockData(data),
+		};
+		// every block turns all the data it understands back into the same data,
+		// so invalid data is found by turning it into the block and back
+		let valid_block = match_block_id!(block, (block: _Type) => block.to_block());
+		(valid_block == block).then_some(block)
+	}
+
+	/// the data of the block as a number, for saving it in other formats than the world
+	pub fn raw_data(self) -> u8 {
+		self.data.0
+	}
+
 	/// whether you can place a block inside of this one
 	pub fn is_replacable(self) -> bool {
 		match_block_id!(self, (block: _Type) => block.is_replacable())
 	}
 
+	/// how many seconds it takes to break the block without a tool
+	pub fn get_hardness(self) -> f32 {
+		match_block_id!(self, (block: _Type) => block.get_hardness())
+	}
+
 	/// gets the Volume where you can collide with the block
 	pub fn get_collision(&self) -> Vec<Cuboid> {
-		match_block_id!(self, (block: _Type) => block.get_collision())
+		match_block_id!(*self, (block: _Type) => block.get_collision())
 	}
 
 	/// gets the Volume where the block can be highighted by looking at it
 	pub fn get_outline(&self) -> Vec<Cuboid> {
-		match_block_id!(self, (block: _Type) => block.get_outline())
+		match_block_id!(*self, (block: _Type) => block.get_outline())
+	}
+
+	/// gets the fluid that fills this block, if there is any
+	pub fn get_fluid(self) -> Option<(FluidKind, FluidState)> {
+		match_block_id!(self, (block: _Type) => block.get_fluid())
+	}
+
+	/// gets the items that are dropped when the block is broken
+	pub(crate) fn get_drops(self, rng: &mut impl Rng) -> Vec<ItemStack> {
+		match_block_id!(self, (block: _Type) => block.get_drops(rng))
 	}
 }
 
 impl BlockId {
+	/// the ids of all blocks that exist
+	pub const ALL: [Self; 13] = [
+		Air::BLOCK_ID,
+		Stone::BLOCK_ID,
+		Dirt::BLOCK_ID,
+		GrassBlock::BLOCK_ID,
+		Cobblestone::BLOCK_ID,
+		DebugBlock::BLOCK_ID,
+		DebugSlab::BLOCK_ID,
+		Log::BLOCK_ID,
+		Planks::BLOCK_ID,
+		Leaves::BLOCK_ID,
+		Water::BLOCK_ID,
+		Lava::BLOCK_ID,
+		CraftingTable::BLOCK_ID,
+	];
+
 	pub fn from_debug_name(s: &str) -> Option<Self> {
 		match s {
 			"Air" => Some(Air::BLOCK_ID),
@@ -54,7 +122,29 @@ impl BlockId {
 			"Log" => Some(Log::BLOCK_ID),
 			"Planks" => Some(Planks::BLOCK_ID),
 			"Leaves" => Some(Leaves::BLOCK_ID),
+			"Water" => Some(Water::BLOCK_ID),
+			"Lava" => Some(Lava::BLOCK_ID),
+			"CraftingTable" => Some(CraftingTable::BLOCK_ID),
 			_ => None,
 		}
 	}
+
+	pub fn to_debug_name(self) -> &'static str {
+		match self {
+			Air::BLOCK_ID => "Air",
+			Stone::BLOCK_ID => "Stone",
+			Dirt::BLOCK_ID => "Dirt",
+			GrassBlock::BLOCK_ID => "GrassBlock",
+			Cobblestone::BLOCK_ID => "Cobblestone",
+			DebugBlock::BLOCK_ID => "DebugBlock",
+			DebugSlab::BLOCK_ID => "DebugSlab",
+			Log::BLOCK_ID => "Log",
+			Planks::BLOCK_ID => "Planks",
+			Leaves::BLOCK_ID => "Leaves",
+			Water::BLOCK_ID => "Water",
+			Lava::BLOCK_ID => "Lava",
+			CraftingTable::BLOCK_ID => "CraftingTable",
+			id => panic!("No name for block id: {:?}", id),
+		}
+	}
 }
@@ -1,9 +1,9 @@
 pub use super::{
 	block_trait::{BlockTrait, BlockWithoutData},
 	blocks::{
-		air::Air, cobblestone::Cobblestone, debug_block::DebugBlock, debug_slab::DebugSlab,
-		dirt::Dirt, grass_block::GrassBlock, leaves::Leaves, log::Log, planks::Planks,
-		stone::Stone,
+		air::Air, cobblestone::Cobblestone, crafting_table::CraftingTable, debug_block::DebugBlock,
+		debug_slab::DebugSlab, dirt::Dirt, grass_block::GrassBlock, lava::Lava, leaves::Leaves,
+		log::Log, planks::Planks, stone::Stone, water::Water,
 	},
 	Block,
 };
@@ -32,6 +32,9 @@ pub struct ChunkMaterial {
 	#[texture(100, dimension = "2d_array")]
 	#[sampler(101)]
 	pub texture: Handle<Image>,
+	/// how bright the sky currently is, from 0 to 1. the color of every block is multiplied by this
+	#[uniform(102)]
+	pub sky_light: f32,
 }
 
 impl MaterialExtension for ChunkMaterial {
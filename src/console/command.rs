@@ -0,0 +1,334 @@
+//! the [`Command`] trait, and parsing the arguments of a command
+
+use crate::{block::BlockId, entity::player::GameMode, item::Item};
+use bevy::prelude::*;
+use std::{error::Error, sync::Arc};
+
+/// something that can be typed into the console, like `/tp 0 10 0`.<br>
+/// plugins can add their own commands with [`AddCommand::add_command`].
+pub trait Command: Send + Sync + 'static {
+	/// the name that is typed after the `/`
+	fn name(&self) -> &'static str;
+
+	/// a short explanation of what the command does, which is shown by `/help`
+	fn description(&self) -> &'static str;
+
+	/// the different ways the arguments can be given.
+	/// the first signature that fits the typed arguments is used.
+	fn signatures(&self) -> &'static [&'static [Param]];
+
+	/// runs the command with arguments that fit one of the [`Command::signatures`].<br>
+	/// returns a message that is shown in the console.
+	fn run(&self, args: &Args, world: &mut World) -> Result<String, Box<dyn Error>>;
+}
+
+/// a single argument of a command
+#[derive(Debug, Clone, Copy)]
+pub struct Param {
+	/// shown in the usage of the command
+	pub name: &'static str,
+	pub kind: ParamKind,
+}
+
+/// what kind of value an argument has to be
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+	Int,
+	/// a coordinate, which can be relative to the player by starting with `~`
+	Coord,
+	Block,
+	Item,
+	GameMode,
+	/// any single word
+	Text,
+	/// exactly one of the given words
+	OneOf(&'static [&'static str]),
+}
+
+/// the value of a single parsed argument
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgValue {
+	Int(i64),
+	Coord { value: f32, relative: bool },
+	Block(BlockId),
+	Item(Item),
+	GameMode(GameMode),
+	Text(String),
+	Word(&'static str),
+}
+
+/// the parsed arguments of a command, in the order of the signature that was used
+#[derive(Debug, Clone, PartialEq)]
+pub struct Args(Vec<ArgValue>);
+
+/// all commands that can be typed into the console
+#[derive(Resource, Default)]
+pub struct CommandRegistry {
+	commands: Vec<Arc<dyn Command>>,
+}
+
+pub trait AddCommand {
+	/// makes the command available in the console
+	fn add_command(&mut self, command: impl Command) -> &mut Self;
+}
+
+impl AddCommand for App {
+	fn add_command(&mut self, command: impl Command) -> &mut Self {
+		self.init_resource::<CommandRegistry>();
+		let mut registry = self.world_mut().resource_mut::<CommandRegistry>();
+		registry.commands.push(Arc::new(command));
+		self
+	}
+}
+
+impl Param {
+	pub const fn new(name: &'static str, kind: ParamKind) -> Self {
+		Self { name, kind }
+	}
+
+	/// a param that only accepts the given word
+	pub const fn literal(word: &'static [&'static str; 1]) -> Self {
+		Self::new(word[0], ParamKind::OneOf(word))
+	}
+
+	fn parse(self, s: &str) -> Option<ArgValue> {
+		match self.kind {
+			ParamKind::Int => s.parse().ok().map(ArgValue::Int),
+			ParamKind::Coord => match s.strip_prefix('~') {
+				Some("") => Some(ArgValue::Coord {
+					value: 0.0,
+					relative: true,
+				}),
+				Some(offset) => offset.parse().ok().map(|value| ArgValue::Coord {
+					value,
+					relative: true,
+				}),
+				None => s.parse().ok().map(|value| ArgValue::Coord {
+					value,
+					relative: false,
+				}),
+			},
+			ParamKind::Block => BlockId::from_debug_name(s).map(ArgValue::Block),
+			ParamKind::Item => Item::from_name(s).map(ArgValue::Item),
+			ParamKind::GameMode => GameMode::from_name(s).map(ArgValue::GameMode),
+			ParamKind::Text => Some(ArgValue::Text(s.to_owned())),
+			ParamKind::OneOf(words) => words.iter().find(|&&w| w == s).map(|w| ArgValue::Word(w)),
+		}
+	}
+
+	/// all values that can be suggested when pressing tab
+	fn completions(self) -> Vec<&'static str> {
+		match self.kind {
+			ParamKind::Int | ParamKind::Text => Vec::new(),
+			ParamKind::Coord => vec!["~"],
+			ParamKind::Block => BlockId::ALL.iter().map(|id| id.to_debug_name()).collect(),
+			ParamKind::Item => Item::all().map(Item::name).collect(),
+			ParamKind::GameMode => GameMode::ALL.iter().map(|mode| mode.name()).collect(),
+			ParamKind::OneOf(words) => words.to_vec(),
+		}
+	}
+
+	fn usage(self) -> String {
+		match self.kind {
+			ParamKind::OneOf([word]) => word.to_string(),
+			_ => format!("<{}>", self.name),
+		}
+	}
+}
+
+impl Args {
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	fn get(&self, index: usize) -> &ArgValue {
+		&self.0[index]
+	}
+
+	// the signature decides the kind of each argument,
+	// so getting a different kind is a mistake in the command
+
+	pub fn int(&self, index: usize) -> i64 {
+		match self.get(index) {
+			&ArgValue::Int(value) => value,
+			value => panic!("argument {index} is not an int: {value:?}"),
+		}
+	}
+
+	/// gets a coordinate, where relative coordinates are added to `origin`
+	pub fn coord(&self, index: usize, origin: f32) -> f32 {
+		match self.get(index) {
+			&ArgValue::Coord {
+				value,
+				relative: true,
+			} => origin + value,
+			&ArgValue::Coord {
+				value,
+				relative: false,
+			} => value,
+			value => panic!("argument {index} is not a coordinate: {value:?}"),
+		}
+	}
+
+	/// gets the three coordinates starting at `index`
+	pub fn position(&self, index: usize, origin: Vec3) -> Vec3 {
+		Vec3::new(
+			self.coord(index, origin.x),
+			self.coord(index + 1, origin.y),
+			self.coord(index + 2, origin.z),
+		)
+	}
+
+	pub fn block(&self, index: usize) -> BlockId {
+		match self.get(index) {
+			&ArgValue::Block(value) => value,
+			value => panic!("argument {index} is not a block: {value:?}"),
+		}
+	}
+
+	pub fn item(&self, index: usize) -> Item {
+		match self.get(index) {
+			&ArgValue::Item(value) => value,
+			value => panic!("argument {index} is not an item: {value:?}"),
+		}
+	}
+
+	pub fn game_mode(&self, index: usize) -> GameMode {
+		match self.get(index) {
+			&ArgValue::GameMode(value) => value,
+			value => panic!("argument {index} is not a game mode: {value:?}"),
+		}
+	}
+
+	pub fn text(&self, index: usize) -> &str {
+		match self.get(index) {
+			ArgValue::Text(value) => value,
+			value => panic!("argument {index} is not text: {value:?}"),
+		}
+	}
+
+	pub fn word(&self, index: usize) -> &'static str {
+		match self.get(index) {
+			&ArgValue::Word(value) => value,
+			value => panic!("argument {index} is not a word: {value:?}"),
+		}
+	}
+
+	/// gets the word, if the argument is one.
+	/// this is useful when signatures have different kinds at the same index
+	pub fn get_word(&self, index: usize) -> Option<&'static str> {
+		match self.get(index) {
+			&ArgValue::Word(value) => Some(value),
+			_ => None,
+		}
+	}
+}
+
+impl CommandRegistry {
+	pub fn commands(&self) -> impl Iterator<Item = &dyn Command> {
+		self.commands.iter().map(|command| command.as_ref())
+	}
+
+	fn get(&self, name: &str) -> Option<&Arc<dyn Command>> {
+		self.commands.iter().find(|command| command.name() == name)
+	}
+
+	/// all words that could be typed instead of the last word in the line.<br>
+	/// if the line ends with a space, a new word is completed instead.
+	pub fn completions(&self, line: &str) -> Vec<&'static str> {
+		let Some(line) = line.strip_prefix('/') else {
+			return Vec::new();
+		};
+		let mut words = line.split_whitespace().collect::<Vec<_>>();
+		if line.is_empty() || line.ends_with(char::is_whitespace) {
+			words.push("");
+		}
+		let (&current, previous) = words.split_last().expect("there is always a word");
+
+		let candidates = match previous.split_first() {
+			None => self.commands().map(|command| command.name()).collect(),
+			Some((name, args)) => {
+				let Some(command) = self.get(name) else {
+					return Vec::new();
+				};
+				let mut candidates = Vec::new();
+				for signature in command.signatures() {
+					let Some(param) = signature.get(args.len()) else {
+						continue;
+					};
+					let fits = signature
+						.iter()
+						.zip(args)
+						.all(|(param, word)| param.parse(word).is_some());
+					if fits {
+						candidates.extend(param.completions());
+					}
+				}
+				candidates
+			}
+		};
+
+		let mut completions = candidates
+			.into_iter()
+			.filter(|candidate| {
+				let candidate = candidate.to_ascii_lowercase();
+				candidate.starts_with(&current.to_ascii_lowercase())
+			})
+			.collect::<Vec<_>>();
+		completions.sort_unstable();
+		completions.dedup();
+		completions
+	}
+}
+
+/// parses a line like `/tp 0 10 0` and runs the command with the [`CommandRegistry`] of the world.<br>
+/// returns the message of the command, or why it failed.
+pub fn execute(line: &str, world: &mut World) -> Result<String, Box<dyn Error>> {
+	let line = line.strip_prefix('/').unwrap_or(line);
+	let mut words = line.split_whitespace();
+	let Some(name) = words.next() else {
+		return Err("no command was given".into());
+	};
+	let registry = world.resource::<CommandRegistry>();
+	let Some(command) = registry.get(name).cloned() else {
+		return Err(format!("unknown command: /{name}").into());
+	};
+	let words = words.collect::<Vec<_>>();
+
+	let args = command.signatures().iter().find_map(|signature| {
+		if signature.len() != words.len() {
+			return None;
+		}
+		let values = signature
+			.iter()
+			.zip(&words)
+			.map(|(param, word)| param.parse(word))
+			.collect::<Option<Vec<_>>>()?;
+		Some(Args(values))
+	});
+	match args {
+		// the command is cloned out of the registry, so that it can use the whole world
+		Some(args) => command.run(&args, world),
+		None => Err(format!("usage: {}", usage(command.as_ref()).join("\n       ")).into()),
+	}
+}
+
+/// how every signature of the command can be typed, like `/give <item> <count>`
+pub fn usage(command: &dyn Command) -> Vec<String> {
+	command
+		.signatures()
+		.iter()
+		.map(|signature| {
+			let mut usage = format!("/{}", command.name());
+			for param in *signature {
+				usage.push(' ');
+				usage.push_str(&param.usage());
+			}
+			usage
+		})
+		.collect()
+}
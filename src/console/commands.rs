@@ -0,0 +1,316 @@
+//! the commands that are always available in the console.<br>
+//! other commands are added by the plugins they belong to.
+
+use super::command::{usage, AddCommand, Args, Command, CommandRegistry, Param, ParamKind};
+use crate::{
+	block::Block,
+	entity::{movement::Velocity, player::Player},
+	game_world::GameWorld,
+	global_config::Config,
+	item::{Inventory, ItemStack},
+	pos::{BlockRegion, Vec3Utils},
+	savedata,
+};
+use bevy::prelude::*;
+use std::error::Error;
+
+/// the most blocks that `/fill` can change at once
+const MAX_FILL_VOLUME: u64 = 32 * 32 * 32;
+
+pub(super) fn add_commands(app: &mut App) {
+	app.add_command(HelpCommand)
+		.add_command(TpCommand)
+		.add_command(GiveCommand)
+		.add_command(SetBlockCommand)
+		.add_command(FillCommand)
+		.add_command(SeedCommand)
+		.add_command(SaveCommand)
+		.add_command(RenderDistanceCommand);
+}
+
+/// the position of the player, which relative coordinates start from
+fn player_pos(world: &mut World) -> Result<Vec3, Box<dyn Error>> {
+	let mut player = world.query_filtered::<&Transform, With<Player>>();
+	let trans = player.get_single(world).map_err(|_| "there is no player")?;
+	Ok(trans.translation)
+}
+
+struct HelpCommand;
+
+impl Command for HelpCommand {
+	fn name(&self) -> &'static str {
+		"help"
+	}
+
+	fn description(&self) -> &'static str {
+		"lists all commands, or shows how to use one"
+	}
+
+	fn signatures(&self) -> &'static [&'static [Param]] {
+		const SIGNATURES: &[&[Param]] = &[&[], &[Param::new("command", ParamKind::Text)]];
+		SIGNATURES
+	}
+
+	fn run(&self, args: &Args, world: &mut World) -> Result<String, Box<dyn Error>> {
+		let registry = world.resource::<CommandRegistry>();
+		if args.is_empty() {
+			let mut names = registry
+				.commands()
+				.map(|command| format!("/{} - {}", command.name(), command.description()))
+				.collect::<Vec<_>>();
+			names.sort();
+			return Ok(names.join("\n"));
+		}
+		let name = args.text(0);
+		let command = registry
+			.commands()
+			.find(|command| command.name() == name)
+			.ok_or_else(|| format!("unknown command: /{name}"))?;
+		Ok(format!(
+			"{}\n{}",
+			command.description(),
+			usage(command).join("\n")
+		))
+	}
+}
+
+struct TpCommand;
+
+impl Command for TpCommand {
+	fn name(&self) -> &'static str {
+		"tp"
+	}
+
+	fn description(&self) -> &'static str {
+		"teleports the player"
+	}
+
+	fn signatures(&self) -> &'static [&'static [Param]] {
+		const SIGNATURES: &[&[Param]] = &[&[
+			Param::new("x", ParamKind::Coord),
+			Param::new("y", ParamKind::Coord),
+			Param::new("z", ParamKind::Coord),
+		]];
+		SIGNATURES
+	}
+
+	fn run(&self, args: &Args, world: &mut World) -> Result<String, Box<dyn Error>> {
+		let mut player = world.query_filtered::<(&mut Transform, &mut Velocity), With<Player>>();
+		let (mut trans, mut vel) = player
+			.get_single_mut(world)
+			.map_err(|_| "there is no player")?;
+		trans.translation = args.position(0, trans.translation);
+		*vel = Velocity::default();
+		Ok(format!("teleported to {:.2}", trans.translation))
+	}
+}
+
+struct GiveCommand;
+
+impl Command for GiveCommand {
+	fn name(&self) -> &'static str {
+		"give"
+	}
+
+	fn description(&self) -> &'static str {
+		"puts items into the inventory of the player"
+	}
+
+	fn signatures(&self) -> &'static [&'static [Param]] {
+		const SIGNATURES: &[&[Param]] = &[
+			&[Param::new("item", ParamKind::Item)],
+			&[
+				Param::new("item", ParamKind::Item),
+				Param::new("count", ParamKind::Int),
+			],
+		];
+		SIGNATURES
+	}
+
+	fn run(&self, args: &Args, world: &mut World) -> Result<String, Box<dyn Error>> {
+		let item = args.item(0);
+		let count = if args.len() > 1 { args.int(1) } else { 1 };
+		let count = u32::try_from(count)
+			.ok()
+			.filter(|&count| count > 0)
+			.ok_or("the count has to be positive")?;
+
+		let mut player = world.query_filtered::<&mut Inventory, With<Player>>();
+		let mut inventory = player
+			.get_single_mut(world)
+			.map_err(|_| "there is no player")?;
+		let remaining = inventory.add(ItemStack::new(item, count));
+		if remaining == count {
+			return Err("the inventory is full".into());
+		}
+		Ok(format!("gave {} {}", count - remaining, item.name()))
+	}
+}
+
+struct SetBlockCommand;
+
+impl Command for SetBlockCommand {
+	fn name(&self) -> &'static str {
+		"setblock"
+	}
+
+	fn description(&self) -> &'static str {
+		"places a block"
+	}
+
+	fn signatures(&self) -> &'static [&'static [Param]] {
+		const SIGNATURES: &[&[Param]] = &[&[
+			Param::new("x", ParamKind::Coord),
+			Param::new("y", ParamKind::Coord),
+			Param::new("z", ParamKind::Coord),
+			Param::new("block", ParamKind::Block),
+		]];
+		SIGNATURES
+	}
+
+	fn run(&self, args: &Args, world: &mut World) -> Result<String, Box<dyn Error>> {
+		let pos = args.position(0, player_pos(world)?).to_block_pos();
+		let block = args.block(3);
+		let mut game_world = world.resource_mut::<GameWorld>();
+		if game_world.set_blocks([(pos, Block::from_id(block))]) == 0 {
+			return Err("that position is not loaded".into());
+		}
+		Ok(format!("placed {} at {:?}", block.to_debug_name(), pos))
+	}
+}
+
+struct FillCommand;
+
+impl Command for FillCommand {
+	fn name(&self) -> &'static str {
+		"fill"
+	}
+
+	fn description(&self) -> &'static str {
+		"places blocks in the box between two corners"
+	}
+
+	fn signatures(&self) -> &'static [&'static [Param]] {
+		const SIGNATURES: &[&[Param]] = &[&[
+			Param::new("x1", ParamKind::Coord),
+			Param::new("y1", ParamKind::Coord),
+			Param::new("z1", ParamKind::Coord),
+			Param::new("x2", ParamKind::Coord),
+			Param::new("y2", ParamKind::Coord),
+			Param::new("z2", ParamKind::Coord),
+			Param::new("block", ParamKind::Block),
+		]];
+		SIGNATURES
+	}
+
+	fn run(&self, args: &Args, world: &mut World) -> Result<String, Box<dyn Error>> {
+		let origin = player_pos(world)?;
+		let region = BlockRegion::from_corners(
+			args.position(0, origin).to_block_pos(),
+			args.position(3, origin).to_block_pos(),
+		);
+		if region.volume() > MAX_FILL_VOLUME {
+			return Err(format!(
+				"{} blocks are too many, the limit is {MAX_FILL_VOLUME}",
+				region.volume()
+			)
+			.into());
+		}
+
+		let block = Block::from_id(args.block(6));
+		let mut game_world = world.resource_mut::<GameWorld>();
+		let count = game_world.set_blocks(region.iter().map(|pos| (pos, block)));
+		Ok(format!("placed {count} {}", block.id.to_debug_name()))
+	}
+}
+
+struct SeedCommand;
+
+impl Command for SeedCommand {
+	fn name(&self) -> &'static str {
+		"seed"
+	}
+
+	fn description(&self) -> &'static str {
+		"shows the seed of the world"
+	}
+
+	fn signatures(&self) -> &'static [&'static [Param]] {
+		const SIGNATURES: &[&[Param]] = &[&[]];
+		SIGNATURES
+	}
+
+	fn run(&self, _args: &Args, world: &mut World) -> Result<String, Box<dyn Error>> {
+		Ok(format!("seed: {}", world.resource::<GameWorld>().seed))
+	}
+}
+
+struct SaveCommand;
+
+impl Command for SaveCommand {
+	fn name(&self) -> &'static str {
+		"save"
+	}
+
+	fn description(&self) -> &'static str {
+		"saves the world"
+	}
+
+	fn signatures(&self) -> &'static [&'static [Param]] {
+		const SIGNATURES: &[&[Param]] = &[&[]];
+		SIGNATURES
+	}
+
+	fn run(&self, _args: &Args, world: &mut World) -> Result<String, Box<dyn Error>> {
+		let game_world = world.resource::<GameWorld>();
+		if game_world.is_remote {
+			return Err("only the server can save the world".into());
+		}
+		savedata::save_game_world("debug_world", game_world)?;
+		Ok("saved the world".to_owned())
+	}
+}
+
+struct RenderDistanceCommand;
+
+impl Command for RenderDistanceCommand {
+	fn name(&self) -> &'static str {
+		"render_distance"
+	}
+
+	fn description(&self) -> &'static str {
+		"changes how many chunks are loaded around the player"
+	}
+
+	fn signatures(&self) -> &'static [&'static [Param]] {
+		const SIGNATURES: &[&[Param]] = &[
+			&[],
+			&[Param::new("horizontal", ParamKind::Int)],
+			&[
+				Param::new("horizontal", ParamKind::Int),
+				Param::new("vertical", ParamKind::Int),
+			],
+		];
+		SIGNATURES
+	}
+
+	fn run(&self, args: &Args, world: &mut World) -> Result<String, Box<dyn Error>> {
+		let mut config = world.resource_mut::<Config>();
+		let to_distance = |value: i64| {
+			u32::try_from(value)
+				.ok()
+				.filter(|&distance| distance <= 32)
+				.ok_or("the render distance has to be between 0 and 32")
+		};
+		if !args.is_empty() {
+			config.horizontal_render_distance = to_distance(args.int(0))?;
+		}
+		if args.len() > 1 {
+			config.vertical_render_distance = to_distance(args.int(1))?;
+		}
+		Ok(format!(
+			"render distance: {} horizontal, {} vertical",
+			config.horizontal_render_distance, config.vertical_render_distance
+		))
+	}
+}
@@ -0,0 +1,327 @@
+//! a chat-like console for typing commands, which is opened with `/`.<br>
+//! while the console is open, all other keyboard and mouse button input is ignored.
+
+mod command;
+mod commands;
+mod stdin;
+
+use crate::{entity::player::CanRotateCam, GlobalState};
+use bevy::{
+	input::{
+		keyboard::{Key, KeyboardInput},
+		ButtonState, InputSystem,
+	},
+	prelude::*,
+};
+
+pub use self::{
+	command::{AddCommand, Args, Command, Param, ParamKind},
+	stdin::StdinConsolePlugin,
+};
+
+pub struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+	fn build(&self, app: &mut App) {
+		commands::add_commands(app);
+		app.add_sub_state::<ConsoleOpen>()
+			.init_resource::<Console>()
+			.init_resource::<ConsoleLog>()
+			.add_systems(OnEnter(GlobalState::InWorld), spawn)
+			.add_systems(OnExit(GlobalState::InWorld), despawn)
+			.add_systems(OnEnter(ConsoleOpen(true)), show_cursor)
+			.add_systems(OnExit(ConsoleOpen(true)), hide_cursor)
+			.add_systems(
+				PreUpdate,
+				type_in_console
+					.after(InputSystem)
+					.run_if(in_state(GlobalState::InWorld)),
+			)
+			.add_systems(
+				Update,
+				(run_commands, update_input_text, update_log_text)
+					.chain()
+					.run_if(in_state(GlobalState::InWorld)),
+			);
+	}
+}
+
+/// how many lines of the log are shown while the console is open
+const MAX_VISIBLE_LINES: usize = 12;
+/// how many seconds a new line stays visible after the console is closed
+const LINE_DURATION: f32 = 8.0;
+const BACKGROUND_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.5);
+const ERROR_COLOR: Color = Color::srgb(1.0, 0.4, 0.4);
+
+#[derive(SubStates, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[source(GlobalState = GlobalState::InWorld)]
+pub struct ConsoleOpen(pub bool);
+
+/// what is currently being typed into the console
+#[derive(Resource, Debug, Default)]
+struct Console {
+	input: String,
+	/// lines that were entered before, from oldest to newest
+	history: Vec<String>,
+	/// which line of the history is shown, if the player is scrolling through it
+	history_index: Option<usize>,
+	/// lines that were entered, but not executed yet
+	submitted: Vec<String>,
+}
+
+/// the messages that were shown in the console
+#[derive(Resource, Debug, Default)]
+pub struct ConsoleLog {
+	lines: Vec<LogLine>,
+}
+
+#[derive(Debug)]
+struct LogLine {
+	text: String,
+	is_error: bool,
+	/// the elapsed time when the line was added
+	time: f32,
+}
+
+#[derive(Component)]
+struct ConsoleRoot;
+
+/// contains a text for every line of the log that is shown
+#[derive(Component)]
+struct ConsoleLogNode;
+
+#[derive(Component)]
+struct ConsoleInputText;
+
+impl ConsoleLog {
+	/// shows a message in the console. the message can have multiple lines
+	pub fn print(&mut self, message: &str, is_error: bool, time: &Time) {
+		for line in message.lines() {
+			self.lines.push(LogLine {
+				text: line.to_owned(),
+				is_error,
+				time: time.elapsed_secs(),
+			});
+		}
+	}
+}
+
+fn spawn(mut commands: Commands) {
+	commands
+		.spawn((
+			ConsoleRoot,
+			Node {
+				position_type: PositionType::Absolute,
+				left: Val::VMin(1.0),
+				bottom: Val::VMin(16.0),
+				width: Val::Percent(50.0),
+				flex_direction: FlexDirection::Column,
+				..default()
+			},
+		))
+		.with_children(|parent| {
+			parent.spawn((
+				ConsoleLogNode,
+				Node {
+					flex_direction: FlexDirection::Column,
+					align_items: AlignItems::FlexStart,
+					..default()
+				},
+			));
+			parent.spawn((
+				ConsoleInputText,
+				Text::default(),
+				TextFont::from_font_size(16.0),
+				TextColor::from(Color::WHITE),
+				BackgroundColor::from(BACKGROUND_COLOR),
+				Visibility::Hidden,
+			));
+		});
+}
+
+fn despawn(
+	mut commands: Commands,
+	root: Query<Entity, With<ConsoleRoot>>,
+	mut console: ResMut<Console>,
+	mut log: ResMut<ConsoleLog>,
+) {
+	for entity in &root {
+		commands.entity(entity).despawn_recursive();
+	}
+	*console = Console::default();
+	*log = ConsoleLog::default();
+}
+
+fn show_cursor(mut can_rotate: ResMut<NextState<CanRotateCam>>) {
+	can_rotate.set(CanRotateCam(false));
+}
+
+fn hide_cursor(mut can_rotate: ResMut<NextState<CanRotateCam>>) {
+	can_rotate.set(CanRotateCam(true));
+}
+
+/// opens the console when pressing `/`, and handles typing while it is open
+#[allow(clippy::too_many_arguments)]
+fn type_in_console(
+	mut keyboard_events: EventReader<KeyboardInput>,
+	mut keys: ResMut<ButtonInput<KeyCode>>,
+	mut mouse_buttons: ResMut<ButtonInput<MouseButton>>,
+	mut console: ResMut<Console>,
+	mut log: ResMut<ConsoleLog>,
+	registry: Res<command::CommandRegistry>,
+	time: Res<Time>,
+	is_open: Res<State<ConsoleOpen>>,
+	mut next_is_open: ResMut<NextState<ConsoleOpen>>,
+	can_rotate: Res<State<CanRotateCam>>,
+) {
+	let events = keyboard_events
+		.read()
+		.filter(|event| event.state == ButtonState::Pressed)
+		.collect::<Vec<_>>();
+
+	if !is_open.0 {
+		// only open the console while playing, and not while in another screen
+		let slash = Key::Character("/".into());
+		if can_rotate.0 && events.iter().any(|event| event.logical_key == slash) {
+			console.input = "/".to_owned();
+			console.history_index = None;
+			next_is_open.set(ConsoleOpen(true));
+		}
+		return;
+	}
+
+	for event in events {
+		match &event.logical_key {
+			Key::Character(s) => console.input.extend(s.chars().filter(|c| !c.is_control())),
+			Key::Space => console.input.push(' '),
+			Key::Backspace => {
+				console.input.pop();
+			}
+			Key::Escape => next_is_open.set(ConsoleOpen(false)),
+			Key::Enter => {
+				let line = std::mem::take(&mut console.input);
+				if !line.trim().is_empty() {
+					console.history.push(line.clone());
+					console.submitted.push(line);
+				}
+				next_is_open.set(ConsoleOpen(false));
+			}
+			Key::Tab => complete(&mut console, &mut log, &registry, &time),
+			Key::ArrowUp => scroll_history(&mut console, -1),
+			Key::ArrowDown => scroll_history(&mut console, 1),
+			_ => {}
+		}
+	}
+
+	// the typed keys should not also move the player or toggle other things
+	keys.reset_all();
+	mouse_buttons.reset_all();
+}
+
+/// replaces the last word with the only possible completion,
+/// or shows all possible completions if there are multiple
+fn complete(
+	console: &mut Console,
+	log: &mut ConsoleLog,
+	registry: &command::CommandRegistry,
+	time: &Time,
+) {
+	let completions = registry.completions(&console.input);
+	let start = console
+		.input
+		.rfind(|c: char| c.is_whitespace() || c == '/')
+		.map_or(0, |i| i + 1);
+	match completions.as_slice() {
+		[] => {}
+		[completion] => {
+			console.input.truncate(start);
+			console.input.push_str(completion);
+			console.input.push(' ');
+		}
+		completions => log.print(&completions.join("  "), false, time),
+	}
+}
+
+/// moves through the lines that were entered before, where a negative offset goes back.<br>
+/// going past the newest line empties the input again.
+fn scroll_history(console: &mut Console, offset: isize) {
+	let len = console.history.len();
+	let index = console.history_index.unwrap_or(len) as isize + offset;
+	let index = index.clamp(0, len as isize) as usize;
+	console.history_index = (index < len).then_some(index);
+	console.input = match console.history.get(index) {
+		Some(line) => line.clone(),
+		None => "/".to_owned(),
+	};
+}
+
+fn run_commands(world: &mut World) {
+	let submitted = std::mem::take(&mut world.resource_mut::<Console>().submitted);
+	for line in submitted {
+		let result = command::execute(&line, world);
+		world.resource_scope(|world, mut log: Mut<ConsoleLog>| {
+			let time = world.resource::<Time>();
+			log.print(&line, false, time);
+			match result {
+				Ok(message) => log.print(&message, false, time),
+				Err(error) => log.print(&error.to_string(), true, time),
+			}
+		});
+	}
+}
+
+fn update_input_text(
+	console: Res<Console>,
+	is_open: Res<State<ConsoleOpen>>,
+	mut input_text: Query<(&mut Text, &mut Visibility), With<ConsoleInputText>>,
+) {
+	let Ok((mut text, mut visibility)) = input_text.get_single_mut() else {
+		return;
+	};
+	if console.is_changed() {
+		text.0 = format!("{}_", console.input);
+	}
+	*visibility = if is_open.0 {
+		Visibility::Inherited
+	} else {
+		Visibility::Hidden
+	};
+}
+
+fn update_log_text(
+	mut commands: Commands,
+	log: Res<ConsoleLog>,
+	time: Res<Time>,
+	is_open: Res<State<ConsoleOpen>>,
+	log_node: Query<Entity, With<ConsoleLogNode>>,
+	mut shown_lines: Local<Vec<usize>>,
+) {
+	// while the console is closed, only recent lines are shown
+	let first_line = log.lines.len().saturating_sub(MAX_VISIBLE_LINES);
+	let lines = (first_line..log.lines.len())
+		.filter(|&i| is_open.0 || time.elapsed_secs() - log.lines[i].time < LINE_DURATION)
+		.collect::<Vec<_>>();
+	if *shown_lines == lines {
+		return;
+	}
+	let Ok(log_node) = log_node.get_single() else {
+		return;
+	};
+	commands.entity(log_node).despawn_descendants();
+	commands.entity(log_node).with_children(|parent| {
+		for &i in &lines {
+			let line = &log.lines[i];
+			parent.spawn((
+				Text::new(line.text.clone()),
+				TextFont::from_font_size(16.0),
+				TextColor::from(if line.is_error {
+					ERROR_COLOR
+				} else {
+					Color::WHITE
+				}),
+				BackgroundColor::from(BACKGROUND_COLOR),
+			));
+		}
+	});
+	*shown_lines = lines;
+}
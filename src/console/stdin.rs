@@ -0,0 +1,60 @@
+//! a console that reads commands from the terminal, for the headless server which has no window
+
+use super::{command::execute, commands};
+use crate::GlobalState;
+use bevy::prelude::*;
+use std::{
+	io::{self, BufRead},
+	sync::{
+		mpsc::{self, Receiver},
+		Mutex,
+	},
+	thread,
+};
+
+pub struct StdinConsolePlugin;
+
+impl Plugin for StdinConsolePlugin {
+	fn build(&self, app: &mut App) {
+		commands::add_commands(app);
+		let (sender, receiver) = mpsc::channel();
+		// reading from stdin blocks, so it can't happen in a system
+		thread::spawn(move || {
+			for line in io::stdin().lock().lines() {
+				let Ok(line) = line else {
+					break;
+				};
+				if sender.send(line).is_err() {
+					break;
+				}
+			}
+		});
+		app.insert_resource(StdinLines(Mutex::new(receiver)))
+			.add_systems(
+				Update,
+				run_stdin_commands.run_if(in_state(GlobalState::InWorld)),
+			);
+	}
+}
+
+/// the lines that were typed into the terminal, but not executed yet
+#[derive(Resource)]
+struct StdinLines(Mutex<Receiver<String>>);
+
+fn run_stdin_commands(world: &mut World) {
+	let lines = world
+		.resource::<StdinLines>()
+		.0
+		.lock()
+		.map(|receiver| receiver.try_iter().collect::<Vec<_>>())
+		.unwrap_or_default();
+	for line in lines {
+		if line.trim().is_empty() {
+			continue;
+		}
+		match execute(&line, world) {
+			Ok(message) => info!("{message}"),
+			Err(err) => error!("{err}"),
+		}
+	}
+}
@@ -0,0 +1,408 @@
+//! the screen that shows the inventory of the player together with a crafting grid.<br>
+//! it either has a 2x2 grid when opening the inventory,
+//! or a 3x3 grid when interacting with a crafting table.
+
+use crate::{
+	entity::{
+		item_entity::spawn_item_entity,
+		player::{CanRotateCam, Player},
+	},
+	input::{InputSet, InventoryInput},
+	item::{load_recipes, CraftingGrid, Inventory, ItemStack, Recipe, HOTBAR_SIZE, INVENTORY_SIZE},
+	GlobalState,
+};
+use bevy::prelude::*;
+
+pub struct CraftingScreenPlugin;
+
+impl Plugin for CraftingScreenPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_sub_state::<CraftingScreen>()
+			.add_systems(Startup, insert_recipes)
+			.add_systems(OnEnter(CraftingScreen::Inventory), open(2))
+			.add_systems(OnEnter(CraftingScreen::CraftingTable), open(3))
+			.add_systems(OnExit(CraftingScreen::Inventory), close)
+			.add_systems(OnExit(CraftingScreen::CraftingTable), close)
+			.add_systems(
+				Update,
+				(
+					toggle_crafting_screen.in_set(InputSet::Use),
+					(click_slot, update_slots, move_held_stack)
+						.chain()
+						.run_if(not(in_state(CraftingScreen::Closed))),
+				)
+					.run_if(in_state(GlobalState::InWorld)),
+			);
+	}
+}
+
+/// which crafting grid is open, if any
+#[derive(SubStates, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[source(GlobalState = GlobalState::InWorld)]
+pub enum CraftingScreen {
+	#[default]
+	Closed,
+	/// the inventory of the player, with a 2x2 grid
+	Inventory,
+	/// a crafting table, with a 3x3 grid
+	CraftingTable,
+}
+
+/// all recipes that can be crafted
+#[derive(Resource, Debug, Default)]
+struct Recipes(Vec<Recipe>);
+
+/// the items in the crafting grid and the one that is held by the cursor
+#[derive(Resource, Debug)]
+struct CraftingState {
+	grid: CraftingGrid,
+	held: Option<ItemStack>,
+}
+
+/// refers to a slot on the crafting screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SlotRef {
+	Inventory(usize),
+	Grid(usize),
+	Result,
+}
+
+#[derive(Component)]
+struct CraftingScreenRoot;
+
+#[derive(Component)]
+struct Slot(SlotRef);
+
+/// the text inside of a [`Slot`]
+#[derive(Component)]
+struct SlotText(SlotRef);
+
+/// shows the stack that is held by the cursor
+#[derive(Component)]
+struct HeldStackText;
+
+const SLOT_COLOR: Color = Color::srgba(0.15, 0.15, 0.15, 0.9);
+const SLOT_BORDER: Color = Color::srgb(0.3, 0.3, 0.3);
+const SLOT_SIZE: Val = Val::VMin(8.0);
+
+fn insert_recipes(mut commands: Commands) {
+	let recipes = load_recipes().unwrap_or_else(|e| {
+		error!("couldn't load the recipes: {}", e);
+		Vec::new()
+	});
+	commands.insert_resource(Recipes(recipes));
+}
+
+fn toggle_crafting_screen(
+	input: Res<InventoryInput>,
+	state: Res<State<CraftingScreen>>,
+	mut next_state: ResMut<NextState<CraftingScreen>>,
+) {
+	if !input.started {
+		return;
+	}
+	next_state.set(match state.get() {
+		CraftingScreen::Closed => CraftingScreen::Inventory,
+		_ => CraftingScreen::Closed,
+	});
+}
+
+/// creates a system that opens the crafting screen with a grid of the given size
+fn open(grid_size: usize) -> impl Fn(Commands, ResMut<NextState<CanRotateCam>>) {
+	move |mut commands, mut can_rotate| {
+		commands.insert_resource(CraftingState {
+			grid: CraftingGrid::new(grid_size),
+			held: None,
+		});
+		// show the cursor, so that the slots can be clicked
+		can_rotate.set(CanRotateCam(false));
+		spawn_ui(&mut commands, grid_size);
+	}
+}
+
+/// puts all items that are still in the grid or held by the cursor back into the inventory,
+/// and drops them if they don't fit
+fn close(
+	mut commands: Commands,
+	root: Query<Entity, With<CraftingScreenRoot>>,
+	state: Option<ResMut<CraftingState>>,
+	mut player: Query<(&Transform, &mut Inventory), With<Player>>,
+	mut can_rotate: ResMut<NextState<CanRotateCam>>,
+) {
+	for entity in &root {
+		commands.entity(entity).despawn_recursive();
+	}
+	commands.remove_resource::<CraftingState>();
+	can_rotate.set(CanRotateCam(true));
+
+	let Some(mut state) = state else {
+		return;
+	};
+	let mut stacks = state.grid.take_all();
+	stacks.extend(state.held.take());
+	let Ok((trans, mut inventory)) = player.get_single_mut() else {
+		return;
+	};
+	for stack in stacks {
+		let leftover = inventory.add(stack);
+		if leftover > 0 {
+			let pos = trans.translation + Vec3::Y;
+			spawn_item_entity(&mut commands, pos, ItemStack::new(stack.item, leftover));
+		}
+	}
+}
+
+fn spawn_ui(commands: &mut Commands, grid_size: usize) {
+	commands
+		.spawn((
+			CraftingScreenRoot,
+			Node {
+				width: Val::Percent(100.),
+				height: Val::Percent(100.),
+				position_type: PositionType::Absolute,
+				flex_direction: FlexDirection::Column,
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				row_gap: Val::VMin(3.),
+				..default()
+			},
+			BackgroundColor::from(Color::srgba(0., 0., 0., 0.5)),
+			GlobalZIndex(1),
+		))
+		.with_children(|parent| {
+			// the crafting grid and the result
+			parent
+				.spawn(Node {
+					align_items: AlignItems::Center,
+					column_gap: Val::VMin(3.),
+					..default()
+				})
+				.with_children(|parent| {
+					parent
+						.spawn(Node {
+							flex_direction: FlexDirection::Column,
+							row_gap: Val::VMin(0.5),
+							..default()
+						})
+						.with_children(|parent| {
+							for y in 0..grid_size {
+								spawn_slot_row(
+									parent,
+									(0..grid_size).map(|x| SlotRef::Grid(x + y * grid_size)),
+								);
+							}
+						});
+					parent.spawn((
+						Text::new("->"),
+						TextColor::from(Color::WHITE),
+						TextFont::from_font_size(30.),
+					));
+					spawn_slot(parent, SlotRef::Result);
+				});
+
+			// the inventory, with the hotbar at the bottom
+			parent
+				.spawn(Node {
+					flex_direction: FlexDirection::Column,
+					row_gap: Val::VMin(0.5),
+					..default()
+				})
+				.with_children(|parent| {
+					for row_start in (HOTBAR_SIZE..INVENTORY_SIZE).step_by(HOTBAR_SIZE) {
+						spawn_slot_row(
+							parent,
+							(row_start..row_start + HOTBAR_SIZE).map(SlotRef::Inventory),
+						);
+					}
+					parent.spawn(Node {
+						height: Val::VMin(1.),
+						..default()
+					});
+					spawn_slot_row(parent, (0..HOTBAR_SIZE).map(SlotRef::Inventory));
+				});
+
+			parent.spawn((
+				HeldStackText,
+				Text::new(""),
+				TextColor::from(Color::WHITE),
+				TextFont::from_font_size(12.),
+				Node {
+					position_type: PositionType::Absolute,
+					..default()
+				},
+			));
+		});
+}
+
+fn spawn_slot_row(parent: &mut ChildBuilder, slots: impl Iterator<Item = SlotRef>) {
+	parent
+		.spawn(Node {
+			column_gap: Val::VMin(0.5),
+			..default()
+		})
+		.with_children(|parent| {
+			for slot in slots {
+				spawn_slot(parent, slot);
+			}
+		});
+}
+
+fn spawn_slot(parent: &mut ChildBuilder, slot: SlotRef) {
+	parent
+		.spawn((
+			Slot(slot),
+			Button,
+			Node {
+				width: SLOT_SIZE,
+				height: SLOT_SIZE,
+				border: UiRect::all(Val::VMin(0.4)),
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				..default()
+			},
+			BackgroundColor::from(SLOT_COLOR),
+			BorderColor::from(SLOT_BORDER),
+		))
+		.with_children(|parent| {
+			parent.spawn((
+				SlotText(slot),
+				Text::new(""),
+				TextColor::from(Color::WHITE),
+				TextFont::from_font_size(12.),
+				TextLayout::new_with_justify(JustifyText::Center),
+			));
+		});
+}
+
+fn click_slot(
+	mouse: Res<ButtonInput<MouseButton>>,
+	slots: Query<(&Slot, &Interaction)>,
+	mut state: ResMut<CraftingState>,
+	mut inventory: Query<&mut Inventory, With<Player>>,
+	recipes: Res<Recipes>,
+) {
+	let left = mouse.just_pressed(MouseButton::Left);
+	let right = mouse.just_pressed(MouseButton::Right);
+	if !left && !right {
+		return;
+	}
+	let Some((slot, _)) = slots.iter().find(|(_, i)| **i != Interaction::None) else {
+		return;
+	};
+	let Ok(mut inventory) = inventory.get_single_mut() else {
+		return;
+	};
+	let CraftingState { grid, held } = &mut *state;
+
+	match slot.0 {
+		SlotRef::Inventory(i) => click_stack(&mut inventory.slots_mut()[i], held, left),
+		SlotRef::Grid(i) => click_stack(&mut grid.slots_mut()[i], held, left),
+		SlotRef::Result => {
+			let Some(result) = grid.find_recipe(&recipes.0).map(Recipe::result) else {
+				return;
+			};
+			match held {
+				None => *held = Some(result),
+				Some(stack)
+					if stack.item == result.item
+						&& stack.count + result.count <= result.item.max_stack_size() =>
+				{
+					stack.count += result.count;
+				}
+				// the result doesn't fit onto the held stack
+				Some(_) => return,
+			}
+			grid.consume_ingredients();
+		}
+	}
+}
+
+/// picks up, puts down, merges or swaps the stack in a slot with the held stack.<br>
+/// a left click moves entire stacks, while a right click moves half a stack or a single item.
+fn click_stack(slot: &mut Option<ItemStack>, held: &mut Option<ItemStack>, left: bool) {
+	match (slot.as_mut(), held.as_mut()) {
+		(None, None) => {}
+		(Some(stack), None) => {
+			if left {
+				*held = slot.take();
+			} else {
+				let taken = stack.count.div_ceil(2);
+				stack.count -= taken;
+				*held = Some(ItemStack::new(stack.item, taken));
+				if stack.count == 0 {
+					*slot = None;
+				}
+			}
+		}
+		(None, Some(held_stack)) => {
+			if left {
+				*slot = held.take();
+			} else {
+				*slot = Some(ItemStack::new(held_stack.item, 1));
+				held_stack.count -= 1;
+				if held_stack.count == 0 {
+					*held = None;
+				}
+			}
+		}
+		(Some(stack), Some(held_stack)) if stack.item == held_stack.item => {
+			let space = stack.item.max_stack_size().saturating_sub(stack.count);
+			let moved = if left {
+				held_stack.count.min(space)
+			} else {
+				space.min(1)
+			};
+			stack.count += moved;
+			held_stack.count -= moved;
+			if held_stack.count == 0 {
+				*held = None;
+			}
+		}
+		(Some(_), Some(_)) => {
+			if left {
+				std::mem::swap(slot, held);
+			}
+		}
+	}
+}
+
+fn update_slots(
+	state: Res<CraftingState>,
+	inventory: Query<&Inventory, With<Player>>,
+	recipes: Res<Recipes>,
+	mut texts: Query<(&SlotText, &mut Text), Without<HeldStackText>>,
+	mut held_text: Query<&mut Text, With<HeldStackText>>,
+) {
+	let Ok(inventory) = inventory.get_single() else {
+		return;
+	};
+	let result = state.grid.find_recipe(&recipes.0).map(Recipe::result);
+	for (slot, mut text) in &mut texts {
+		let stack = match slot.0 {
+			SlotRef::Inventory(i) => inventory.slots()[i],
+			SlotRef::Grid(i) => state.grid.slots()[i],
+			SlotRef::Result => result,
+		};
+		text.0 = stack_text(stack);
+	}
+	if let Ok(mut text) = held_text.get_single_mut() {
+		text.0 = stack_text(state.held);
+	}
+}
+
+fn stack_text(stack: Option<ItemStack>) -> String {
+	match stack {
+		Some(stack) => format!("{}\n{}", stack.item.name(), stack.count),
+		None => String::new(),
+	}
+}
+
+fn move_held_stack(windows: Query<&Window>, mut held_text: Query<&mut Node, With<HeldStackText>>) {
+	let Some(cursor) = windows.iter().find_map(|w| w.cursor_position()) else {
+		return;
+	};
+	for mut node in &mut held_text {
+		node.left = Val::Px(cursor.x + 8.);
+		node.top = Val::Px(cursor.y + 8.);
+	}
+}
@@ -0,0 +1,118 @@
+use crate::entity::{
+	health::{DamageSource, DeathEvent},
+	player::{IsAlive, Player, RespawnEvent},
+};
+use bevy::prelude::*;
+
+pub struct DeathScreenPlugin;
+
+impl Plugin for DeathScreenPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(OnEnter(IsAlive(false)), spawn)
+			.add_systems(OnExit(IsAlive(false)), despawn)
+			.add_systems(
+				Update,
+				(
+					remember_cause_of_death,
+					click_respawn_button.run_if(in_state(IsAlive(false))),
+				),
+			);
+	}
+}
+
+#[derive(Component)]
+struct DeathScreenRoot;
+
+#[derive(Component)]
+struct RespawnButton;
+
+/// what killed the player the last time they died
+#[derive(Resource, Debug, Clone, Copy)]
+struct CauseOfDeath(DamageSource);
+
+fn remember_cause_of_death(
+	mut commands: Commands,
+	mut death_events: EventReader<DeathEvent>,
+	player: Query<Entity, With<Player>>,
+) {
+	for event in death_events.read() {
+		if player.get(event.entity).is_ok() {
+			commands.insert_resource(CauseOfDeath(event.source));
+		}
+	}
+}
+
+fn spawn(mut commands: Commands, cause: Option<Res<CauseOfDeath>>) {
+	let message = match cause.map(|c| c.0) {
+		Some(DamageSource::Fall) => "You hit the ground too hard",
+		None => "You died",
+	};
+
+	commands
+		.spawn((
+			DeathScreenRoot,
+			Node {
+				width: Val::Percent(100.),
+				height: Val::Percent(100.),
+				position_type: PositionType::Absolute,
+				flex_direction: FlexDirection::Column,
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				..default()
+			},
+			BackgroundColor::from(Color::srgba(0.5, 0., 0., 0.4)),
+			// draw on top of the rest of the ui
+			GlobalZIndex(1),
+		))
+		.with_children(|parent| {
+			parent.spawn((
+				Text::new("You Died!"),
+				TextColor::from(Color::WHITE),
+				TextFont::from_font_size(40.),
+			));
+			parent.spawn((
+				Text::new(message),
+				TextColor::from(Color::WHITE),
+				TextFont::from_font_size(20.),
+			));
+			parent
+				.spawn((
+					RespawnButton,
+					Button,
+					Node {
+						width: Val::VMin(20.),
+						height: Val::VMin(10.),
+						margin: UiRect::all(Val::VMin(2.)),
+						justify_content: JustifyContent::Center,
+						align_items: AlignItems::Center,
+						..default()
+					},
+					BackgroundColor::from(Color::srgb(0.15, 0.15, 0.15)),
+				))
+				.with_children(|parent| {
+					parent.spawn((
+						Text::new("Respawn"),
+						TextColor::from(Color::WHITE),
+						TextFont::from_font_size(20.),
+					));
+				});
+		});
+}
+
+fn despawn(mut commands: Commands, root: Query<Entity, With<DeathScreenRoot>>) {
+	for entity in &root {
+		commands.entity(entity).despawn_recursive();
+	}
+	commands.remove_resource::<CauseOfDeath>();
+}
+
+fn click_respawn_button(
+	mut respawn_events: EventWriter<RespawnEvent>,
+	intercation_query: Query<&Interaction, (Changed<Interaction>, With<RespawnButton>)>,
+) {
+	for interaction in intercation_query.iter() {
+		if interaction == &Interaction::Pressed {
+			respawn_events.send(RespawnEvent);
+		}
+	}
+}
@@ -1,7 +1,8 @@
 use crate::{
 	entity::{collision::ray::FiniteRay, player::Player},
-	game_world::chunk::CHUNK_LENGTH,
+	game_world::{chunk::CHUNK_LENGTH, locate_structure, GameWorld, StructureKind},
 	pos::{BlockPos, Vec3Utils},
+	GlobalState,
 };
 use bevy::{color::palettes::basic::YELLOW, prelude::*};
 
@@ -12,6 +13,8 @@ impl Plugin for DebugPlugin {
 		app.init_resource::<DrawChunkBorders>()
 			.register_type::<DrawChunkBorders>()
 			.init_resource::<DebugRes>()
+			.init_resource::<LocateStructure>()
+			.register_type::<LocateStructure>()
 			.add_systems(
 				Update,
 				(
@@ -20,6 +23,7 @@ impl Plugin for DebugPlugin {
 					despawn_temp_cubes,
 					toggle_chunk_borders,
 					draw_chunk_borders,
+					locate_nearest_structure.run_if(in_state(GlobalState::InWorld)),
 				),
 			);
 	}
@@ -44,6 +48,13 @@ impl Default for DrawChunkBorders {
 	}
 }
 
+/// which kind of structure is searched for when pressing L
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+struct LocateStructure {
+	kind: StructureKind,
+}
+
 #[derive(Resource, Default)]
 pub struct DebugRes {
 	queued_temp_cubes: Vec<(BlockPos, f32)>,
@@ -154,3 +165,47 @@ fn draw_chunk_borders(
 		)
 		.outer_edges();
 }
+
+/// L finds the closest structure, and shows the direction to it with a line.<br>
+/// Shift + L changes the kind of structure that is searched for.
+fn locate_nearest_structure(
+	input: Res<ButtonInput<KeyCode>>,
+	mut locate: ResMut<LocateStructure>,
+	mut debug_res: ResMut<DebugRes>,
+	game_world: Res<GameWorld>,
+	player: Query<&Transform, With<Player>>,
+) {
+	if !input.just_pressed(KeyCode::KeyL) {
+		return;
+	}
+	if input.pressed(KeyCode::ShiftLeft) {
+		let kinds = StructureKind::ALL;
+		let index = kinds.iter().position(|k| *k == locate.kind).unwrap_or(0);
+		locate.kind = kinds[(index + 1) % kinds.len()];
+		info!("now locating structures of kind {}", locate.kind);
+		return;
+	}
+	let Ok(player) = player.get_single() else {
+		return;
+	};
+	let from = player.translation.to_block_pos();
+	let seed = game_world.seed;
+	let settings = &game_world.worldgen_settings;
+	let Some(pos) = locate_structure(seed, settings, locate.kind, from) else {
+		info!("there is no {} nearby", locate.kind);
+		return;
+	};
+	let offset = pos.to_world_pos() - player.translation;
+	info!(
+		"the closest {} is at {} ({:.0} blocks away)",
+		locate.kind,
+		pos,
+		offset.length()
+	);
+	debug_res.spawn_temp_line(
+		player.translation,
+		offset.normalize_or_zero(),
+		offset.length(),
+		10.0,
+	);
+}
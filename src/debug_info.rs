@@ -1,5 +1,6 @@
 use crate::{
 	entity::{movement::Velocity, player::Player},
+	game_world::GameWorld,
 	GlobalState,
 };
 use bevy::prelude::*;
@@ -55,6 +56,7 @@ fn update_debug_info_text(
 	cam: Query<&Transform, With<Camera3d>>,
 	player: Query<(&Transform, &Velocity), With<Player>>,
 	entities: Query<Entity>,
+	game_world: Res<GameWorld>,
 ) {
 	let mut text = String::new();
 
@@ -87,6 +89,19 @@ fn update_debug_info_text(
 		));
 	}
 
+	{
+		let world_time = game_world.time;
+		let day = world_time.day();
+		let time_of_day = world_time.time_of_day();
+		let frozen = if world_time.is_frozen() {
+			" (frozen)"
+		} else {
+			""
+		};
+
+		text.push_str(&format!("Day: {day}  Time: {time_of_day:.3}{frozen}\n"));
+	}
+
 	let mut text_obj = query.single_mut();
 	text_obj.0 = text;
 }
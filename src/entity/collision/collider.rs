@@ -8,11 +8,29 @@ use bevy::prelude::*;
 pub struct BoxCollider {
 	width: f32,
 	height: f32,
+	/// how high an obstacle can be to walk onto it without jumping
+	step_height: f32,
 }
 
 impl BoxCollider {
 	pub fn new(width: f32, height: f32) -> Self {
-		Self { width, height }
+		Self {
+			width,
+			height,
+			step_height: 0.0,
+		}
+	}
+
+	/// makes the entity walk onto obstacles up to the given height while it is on the ground
+	pub fn with_step_height(self, step_height: f32) -> Self {
+		Self {
+			step_height,
+			..self
+		}
+	}
+
+	pub fn step_height(self) -> f32 {
+		self.step_height
 	}
 
 	pub fn into_cuboid(self) -> Cuboid {
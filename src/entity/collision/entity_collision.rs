@@ -0,0 +1,221 @@
+//! collision between entities.<br>
+//! solid entities are sorted into a [`SpatialHash`] every tick, so that only nearby entities
+//! have to be compared. overlapping entities aren't stopped, but pushed apart over a few ticks.
+
+use super::collider::BoxCollider;
+use crate::{
+	block::Block,
+	cuboid::Cuboid,
+	entity::movement::{MovementSet, Velocity},
+	pos::BlockPos,
+	GlobalState,
+};
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap};
+
+pub struct EntityCollisionPlugin;
+
+impl Plugin for EntityCollisionPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<SpatialHash>()
+			.add_systems(OnExit(GlobalState::InWorld), clear_spatial_hash)
+			.add_systems(
+				FixedUpdate,
+				(update_spatial_hash, push_apart)
+					.chain()
+					.in_set(MovementSet::Push)
+					.run_if(in_state(GlobalState::InWorld)),
+			);
+	}
+}
+
+/// the length of the cubes that the [`SpatialHash`] splits the world into
+const CELL_SIZE: f32 = 2.;
+
+/// entities can have moved this far since the [`SpatialHash`] was updated,
+/// when it is used outside of a tick
+const HASH_MARGIN: f32 = 1.;
+
+/// how strongly overlapping entities are pushed apart, in m/s² per meter of overlap
+const PUSH_STRENGTH: f32 = 20.;
+
+/// an entity with a [`BoxCollider`] that other entities can't stand in.<br>
+/// blocks can't be placed inside of it, and it is pushed away from other solid entities
+/// if it has a [`Velocity`]
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct Solid;
+
+/// which [`Solid`] entities are close to which part of the world
+#[derive(Resource, Debug, Default)]
+pub struct SpatialHash {
+	cells: HashMap<IVec3, Vec<Entity>>,
+}
+
+impl SpatialHash {
+	fn cells_of(cuboid: Cuboid) -> impl Iterator<Item = IVec3> {
+		let min = (cuboid.min / CELL_SIZE).floor().as_ivec3();
+		let max = (cuboid.max / CELL_SIZE).floor().as_ivec3();
+		(min.x..=max.x).flat_map(move |x| {
+			(min.y..=max.y).flat_map(move |y| (min.z..=max.z).map(move |z| IVec3::new(x, y, z)))
+		})
+	}
+
+	fn insert(&mut self, entity: Entity, hitbox: Cuboid) {
+		for cell in Self::cells_of(hitbox) {
+			self.cells.entry(cell).or_default().push(entity);
+		}
+	}
+
+	/// all entities that are in the same cells as the cuboid, sorted and without duplicates.<br>
+	/// they aren't necessarily intersecting it
+	pub fn entities_near(&self, cuboid: Cuboid) -> Vec<Entity> {
+		let mut entities = Self::cells_of(cuboid)
+			.filter_map(|cell| self.cells.get(&cell))
+			.flatten()
+			.copied()
+			.collect::<Vec<_>>();
+		entities.sort_unstable();
+		entities.dedup();
+		entities
+	}
+}
+
+/// the hitboxes of all [`Solid`] entities, found through the [`SpatialHash`]
+#[derive(SystemParam)]
+pub struct SolidEntities<'w, 's> {
+	spatial_hash: Res<'w, SpatialHash>,
+	solids: Query<'w, 's, (&'static Transform, &'static BoxCollider), With<Solid>>,
+}
+
+impl SolidEntities<'_, '_> {
+	fn hitbox(&self, entity: Entity) -> Option<Cuboid> {
+		let (trans, collider) = self.solids.get(entity).ok()?;
+		Some(collider.into_cuboid() + trans.translation)
+	}
+
+	/// the solid entities whose hitboxes overlap the cuboid.<br>
+	/// touching it is not enough
+	pub fn intersecting(&self, cuboid: Cuboid) -> impl Iterator<Item = (Entity, Cuboid)> + '_ {
+		let area = Cuboid::from_corners(
+			cuboid.min - Vec3::splat(HASH_MARGIN),
+			cuboid.max + Vec3::splat(HASH_MARGIN),
+		);
+		self.spatial_hash
+			.entities_near(area)
+			.into_iter()
+			.filter_map(|entity| Some((entity, self.hitbox(entity)?)))
+			.filter(move |(_, hitbox)| !hitbox.intersect(cuboid).is_empty())
+	}
+
+	/// whether the block would be inside of a solid entity other than `except`,
+	/// if it was placed at the position
+	pub fn is_in_the_way(&self, block: Block, pos: BlockPos, except: Option<Entity>) -> bool {
+		block.get_collision().into_iter().any(|collision| {
+			let collision = collision + pos.to_world_pos();
+			self.intersecting(collision)
+				.any(|(entity, _)| Some(entity) != except)
+		})
+	}
+}
+
+fn clear_spatial_hash(mut spatial_hash: ResMut<SpatialHash>) {
+	spatial_hash.cells.clear();
+}
+
+fn update_spatial_hash(
+	mut spatial_hash: ResMut<SpatialHash>,
+	solids: Query<(Entity, &Transform, &BoxCollider), With<Solid>>,
+) {
+	spatial_hash.cells.clear();
+	for (entity, trans, collider) in &solids {
+		spatial_hash.insert(entity, collider.into_cuboid() + trans.translation);
+	}
+}
+
+/// accelerates overlapping entities away from each other horizontally,
+/// depending on how far they overlap
+fn push_apart(
+	mut pushed: Query<(Entity, &mut Velocity), With<Solid>>,
+	solids: SolidEntities,
+	time: Res<Time>,
+) {
+	let dt = time.delta_secs();
+	for (entity, mut vel) in &mut pushed {
+		let Some(hitbox) = solids.hitbox(entity) else {
+			continue;
+		};
+		for (other, other_hitbox) in solids.intersecting(hitbox) {
+			if other == entity {
+				continue;
+			}
+			let overlap = hitbox.intersect(other_hitbox);
+			let depth = overlap.width().min(overlap.length());
+			let away = (hitbox.center() - other_hitbox.center()).with_y(0.);
+			// entities in the exact same place are pushed in opposite directions
+			let dir =
+				away.try_normalize()
+					.unwrap_or(if entity < other { Vec3::X } else { Vec3::NEG_X });
+			vel.vel += dir * depth * PUSH_STRENGTH * dt;
+		}
+	}
+}
+
+#[cfg(test)]
+impl SpatialHash {
+	/// sorts the solid entities of the world into its spatial hash, like every tick
+	pub fn update(world: &mut World) {
+		use bevy::ecs::system::RunSystemOnce;
+		world.run_system_once(update_spatial_hash).unwrap();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bevy::ecs::system::RunSystemOnce;
+	use std::time::Duration;
+
+	fn world_with_solids(translations: &[Vec3]) -> (World, Vec<Entity>) {
+		let mut world = World::new();
+		let mut time = Time::<()>::default();
+		time.advance_by(Duration::from_secs_f32(1. / 60.));
+		world.insert_resource(time);
+		world.init_resource::<SpatialHash>();
+		let entities = translations
+			.iter()
+			.map(|&translation| {
+				world
+					.spawn((
+						Solid,
+						Transform::from_translation(translation),
+						BoxCollider::new(0.8, 1.85),
+						Velocity::default(),
+					))
+					.id()
+			})
+			.collect();
+		SpatialHash::update(&mut world);
+		(world, entities)
+	}
+
+	#[test]
+	fn the_spatial_hash_only_finds_nearby_entities() {
+		let (world, entities) =
+			world_with_solids(&[Vec3::ZERO, Vec3::new(0.5, 0., 0.), Vec3::new(20., 0., 0.)]);
+		let spatial_hash = world.resource::<SpatialHash>();
+		let near = spatial_hash.entities_near(Cuboid::from_center_size(Vec3::Y, Vec3::ONE));
+		assert_eq!(near, entities[..2]);
+	}
+
+	#[test]
+	fn overlapping_entities_are_pushed_apart() {
+		let (mut world, entities) =
+			world_with_solids(&[Vec3::ZERO, Vec3::new(0.5, 0., 0.), Vec3::new(3., 0., 0.)]);
+		world.run_system_once(push_apart).unwrap();
+		let vel = |entity| world.get::<Velocity>(entity).unwrap().vel;
+		assert!(vel(entities[0]).x < 0.);
+		assert!(vel(entities[1]).x > 0.);
+		assert_eq!(vel(entities[0]).x, -vel(entities[1]).x);
+		assert_eq!(vel(entities[0]).y, 0.);
+		assert_eq!(vel(entities[2]), Vec3::ZERO);
+	}
+}
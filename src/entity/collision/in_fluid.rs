@@ -0,0 +1,65 @@
+use super::collider::BoxCollider;
+use crate::{
+	block::fluid::FluidKind,
+	entity::movement::MovementSet,
+	game_world::GameWorld,
+	pos::{BlockPos, Vec3Utils},
+	GlobalState,
+};
+use bevy::prelude::*;
+
+pub struct InFluidPlugin;
+
+impl Plugin for InFluidPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			FixedUpdate,
+			detect_fluids
+				.in_set(MovementSet::CleanUp)
+				.run_if(in_state(GlobalState::InWorld)),
+		);
+	}
+}
+
+/// the fluid that an entity is currently in, and how deep it is in it
+#[derive(Component, Default, Debug, Clone, Copy)]
+pub struct InFluid {
+	pub kind: Option<FluidKind>,
+	/// how much of the entity's height is below the surface of the fluid, from 0 to 1
+	pub depth: f32,
+}
+
+fn detect_fluids(
+	mut query: Query<(&Transform, &BoxCollider, &mut InFluid)>,
+	game_world: Res<GameWorld>,
+) {
+	for (trans, col, mut in_fluid) in &mut query {
+		let hitbox = col.into_cuboid() + trans.translation;
+		let center = hitbox.center().to_block_pos();
+		let min_y = hitbox.min.y.floor() as i32;
+		let max_y = hitbox.max.y.floor() as i32;
+
+		let mut kind = None;
+		let mut submerged_height = 0.0_f32;
+		for y in min_y..=max_y {
+			let pos = BlockPos::new(center.x, y, center.z);
+			let Some((fluid_kind, state)) =
+				game_world.get_block_at(pos).and_then(|b| b.get_fluid())
+			else {
+				continue;
+			};
+			let above = game_world.get_block_at(BlockPos::new(center.x, y + 1, center.z));
+			let height = if above.and_then(|b| b.get_fluid()).is_some() {
+				1.0
+			} else {
+				state.height()
+			};
+			let surface = y as f32 + height;
+			submerged_height = submerged_height.max(surface - hitbox.min.y);
+			kind = Some(fluid_kind);
+		}
+
+		in_fluid.kind = kind;
+		in_fluid.depth = (submerged_height / hitbox.height()).clamp(0.0, 1.0);
+	}
+}
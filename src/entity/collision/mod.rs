@@ -1,13 +1,27 @@
 pub mod collider;
+mod entity_collision;
+pub mod in_fluid;
 mod move_and_slide;
 pub mod ray;
 
 use bevy::prelude::*;
 
+pub use self::{
+	entity_collision::{Solid, SolidEntities},
+	move_and_slide::{collides_with_blocks, StepOffset},
+};
+
+#[cfg(test)]
+pub use self::entity_collision::SpatialHash;
+
 pub struct CollisionPlugin;
 
 impl Plugin for CollisionPlugin {
 	fn build(&self, app: &mut App) {
-		app.add_plugins(move_and_slide::MoveAndSlidePlugin);
+		app.add_plugins((
+			move_and_slide::MoveAndSlidePlugin,
+			in_fluid::InFluidPlugin,
+			entity_collision::EntityCollisionPlugin,
+		));
 	}
 }
@@ -1,21 +1,20 @@
 use super::collider::BoxCollider;
 use crate::{
+	axis::Axis,
 	cuboid::Cuboid,
-	entity::movement::{MovementSet, OnGround, Velocity},
-	face::Face,
+	entity::movement::{Crouching, LandEvent, MovementSet, OnGround, Velocity},
 	game_world::GameWorld,
-	pos::{BlockPos, IVec3Utils, Vec3Utils},
+	pos::{BlockPos, Vec3Utils},
 	GlobalState,
 };
 use bevy::prelude::*;
-use float_next_after::NextAfter;
 
 pub struct MoveAndSlidePlugin;
 
 impl Plugin for MoveAndSlidePlugin {
 	fn build(&self, app: &mut App) {
 		app.add_systems(
-			Update,
+			FixedUpdate,
 			move_and_slide
 				.in_set(MovementSet::Translate)
 				.run_if(in_state(GlobalState::InWorld)),
@@ -23,186 +22,233 @@ impl Plugin for MoveAndSlidePlugin {
 	}
 }
 
-// FIXME can still sometimes walk through walls
+/// hitboxes that are closer than this to a block are touching it.<br>
+/// this makes up for rounding errors, so that hitboxes never have to be pushed away from blocks
+const COLLISION_EPSILON: f32 = 1e-3;
+
+/// 1 / the amount of seconds it takes to half the [`StepOffset`]
+const STEP_SMOOTHING: f32 = 24.;
+
+/// how far a crouching entity can stick out over a ledge, at most.<br>
+/// it has to overlap the ground by more than [`COLLISION_EPSILON`], so that it still stands on it
+const LEDGE_OVERLAP: f32 = COLLISION_EPSILON * 5.;
+
+/// blocks this far below the entity still count as ground for crouching,
+/// in addition to the step height
+const LEDGE_TOLERANCE: f32 = 0.01;
+
+/// how far an entity has moved up from stepping onto obstacles recently.<br>
+/// this goes back to 0 over time, and is used to make the camera move up smoothly
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct StepOffset(pub f32);
 
 /// moves the hitbox using its velocity and
 /// slides along any blocks in the way
+#[allow(clippy::type_complexity)]
 fn move_and_slide(
 	mut query: Query<(
+		Entity,
 		&mut Transform,
 		&mut Velocity,
 		&BoxCollider,
 		Option<&mut OnGround>,
+		Option<&mut StepOffset>,
+		Option<&Crouching>,
 	)>,
 	time: Res<Time>,
 	game_world: Res<GameWorld>,
+	mut land_events: EventWriter<LandEvent>,
 ) {
 	let dt = time.delta_secs();
-	for (mut trans, mut vel, col, mut on_ground) in &mut query {
-		let local_hitbox = col.into_cuboid();
+	for (entity, mut trans, mut vel, col, mut on_ground, mut step_offset, crouching) in &mut query {
+		let hitbox = col.into_cuboid() + trans.translation;
 
-		if let Some(ref mut on_ground) = on_ground {
-			on_ground.0 = false;
+		if let Some(ref mut step_offset) = step_offset {
+			step_offset.0 *= (-STEP_SMOOTHING * dt).exp2();
 		}
 
+		let was_on_ground = on_ground.as_ref().is_some_and(|on_ground| on_ground.0);
+
 		// v += a/2;  s += v;  v += a/2;
 		// this is the velocity after the first step
 		// since `vel.vel` is the velocity after the last step
-		let mut v = vel.vel - vel.delta() / 2.;
+		let v = vel.vel - vel.delta() / 2.;
+		let mut delta = v * dt;
 
-		let mut t = dt;
-		while t > 0. {
-			let hitbox = local_hitbox + trans.translation;
-
-			// get the relevant cuboids
-			let positions = get_all_block_pos_for_cuboid_cast(hitbox, v * t);
-			let block_collisions = get_block_collisions(positions, &game_world);
+		if was_on_ground && delta.y <= 0. && crouching.is_some_and(|crouching| crouching.0) {
+			delta = keep_on_ledge(hitbox, delta, col.step_height(), &game_world);
+		}
 
-			let block_hit = earliest_block_hit(&block_collisions, hitbox, v, dt);
-			let Some((hit_t, face)) = block_hit else {
-				// since there are no more collisions in a straight line,
-				// we can just move linearly for the rest of t
-				trans.translation += v * t;
-				break;
-			};
+		// the blocks are only gathered once, including the room above the hitbox
+		// that is needed for stepping up
+		let reach = Cuboid::from_corners(hitbox.min, hitbox.max + Vec3::Y * col.step_height());
+		let positions = get_all_block_pos_for_cuboid_cast(reach, delta);
+		let block_collisions = get_block_collisions(positions, &game_world);
 
-			if let Some(ref mut on_ground) = on_ground {
-				if face == Face::Up {
-					on_ground.0 = true;
+		let mut moved = sweep(&block_collisions, hitbox, delta);
+		let is_blocked_horizontally = moved.x != delta.x || moved.z != delta.z;
+		if was_on_ground && col.step_height() > 0. && is_blocked_horizontally {
+			let stepped = sweep_with_step(&block_collisions, hitbox, delta, col.step_height());
+			if stepped.xz().length_squared() > moved.xz().length_squared() {
+				if let Some(ref mut step_offset) = step_offset {
+					step_offset.0 += (stepped.y - moved.y).max(0.);
 				}
+				moved = stepped;
 			}
+		}
+		trans.translation += moved;
 
-			// move by hit_t
-			t -= hit_t;
-			trans.translation += v * hit_t;
-
-			// FIXME moving slightly makes this not 100% precise :(
-			trans.translation = move_slighty_in_direction(trans.translation, face);
+		// the velocity is stopped in every direction that was blocked
+		for axis in Axis::all() {
+			let i = axis.index();
+			if moved[i] != v[i] * dt {
+				vel.vel[i] = 0.;
+			}
+		}
 
-			// set velocity to 0 in direction of face
-			let vel_mask = 1. - face.normal().to_vec3().abs();
-			v *= vel_mask;
-			vel.vel *= vel_mask;
+		if let Some(ref mut on_ground) = on_ground {
+			on_ground.0 = delta.y < 0. && moved.y > delta.y;
+			if on_ground.0 && !was_on_ground {
+				land_events.send(LandEvent {
+					entity,
+					speed: -v.y,
+				});
+			}
 		}
 	}
 }
 
-/// Moves a position in the direction of the normal of a face.
-/// Note that this is not necessarily the smallest possible value,
-/// but it will never get rounded away.
-fn move_slighty_in_direction(pos: Vec3, face: Face) -> Vec3 {
-	let normal = face.normal().to_vec3();
-	let after_pos = Vec3::new(
-		if normal.x.abs() < f32::EPSILON {
-			pos.x
-		} else {
-			normal.x.signum() * f32::INFINITY
-		},
-		if normal.y.abs() < f32::EPSILON {
-			pos.y
-		} else {
-			normal.y.signum() * f32::INFINITY
-		},
-		if normal.z.abs() < f32::EPSILON {
-			pos.z
-		} else {
-			normal.z.signum() * f32::INFINITY
-		},
-	);
-	Vec3::new(
-		pos.x.next_after(after_pos.x),
-		pos.y.next_after(after_pos.y),
-		pos.z.next_after(after_pos.z),
-	) + normal * f32::EPSILON
+/// moves the hitbox by `delta`, one axis at a time, and stops each axis at the first block in the way.<br>
+/// the vertical movement comes first, so that the ground can't stop horizontal movement
+fn sweep(block_collisions: &[Cuboid], hitbox: Cuboid, delta: Vec3) -> Vec3 {
+	let y = sweep_axis(block_collisions, hitbox, Axis::Y, delta.y);
+	let horizontal = sweep_horizontal(block_collisions, hitbox + Vec3::Y * y, delta);
+	Vec3::new(horizontal.x, y, horizontal.z)
 }
 
-/// Figures out how far a hitbox can be moved with a given velocity until it
-/// hits any block in any direction. Will return `None` if no block is
-/// hit, and `Some((t, f))` if it hits, where `t` is how long the hitbox
-/// has to move to hit a block, and `f` is the face of the block.
-fn earliest_block_hit(
+/// like [`sweep`], except that the hitbox moves up by the step height first, and back down at the end.<br>
+/// this lets it walk onto obstacles that aren't higher than the step height
+fn sweep_with_step(
 	block_collisions: &[Cuboid],
 	hitbox: Cuboid,
-	vel: Vec3,
-	max_t: f32,
-) -> Option<(f32, Face)> {
+	delta: Vec3,
+	step_height: f32,
+) -> Vec3 {
+	let up = sweep_axis(block_collisions, hitbox, Axis::Y, step_height);
+	let raised = hitbox + Vec3::Y * up;
+	let horizontal = sweep_horizontal(block_collisions, raised, delta);
+	let down = sweep_axis(
+		block_collisions,
+		raised + horizontal,
+		Axis::Y,
+		delta.y.min(0.) - up,
+	);
+	Vec3::new(horizontal.x, up + down, horizontal.z)
+}
+
+/// moves the hitbox along x and z, starting with the axis it moves further along.<br>
+/// this way, sliding along a wall at a shallow angle doesn't get stuck on the edges between blocks
+fn sweep_horizontal(block_collisions: &[Cuboid], hitbox: Cuboid, delta: Vec3) -> Vec3 {
+	let axes = if delta.x.abs() >= delta.z.abs() {
+		[Axis::X, Axis::Z]
+	} else {
+		[Axis::Z, Axis::X]
+	};
+	let mut moved = Vec3::ZERO;
+	for axis in axes {
+		let i = axis.index();
+		moved[i] = sweep_axis(block_collisions, hitbox + moved, axis, delta[i]);
+	}
+	moved
+}
+
+/// how far the hitbox can move along a single axis, up to `delta`, until it touches a block.<br>
+/// blocks the hitbox is already inside of are ignored, so that it can get out of them
+fn sweep_axis(block_collisions: &[Cuboid], hitbox: Cuboid, axis: Axis, delta: f32) -> f32 {
+	let i = axis.index();
+	let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+	// only blocks next to the hitbox on the other axes can be hit
+	let overlaps = |block: &&Cuboid| {
+		[j, k].into_iter().all(|j| {
+			block.min[j] < hitbox.max[j] - COLLISION_EPSILON
+				&& hitbox.min[j] < block.max[j] - COLLISION_EPSILON
+		})
+	};
 	block_collisions
 		.iter()
-		.flat_map(|block_collision| {
-			Face::all()
-				.flat_map(|face| {
-					find_plane_intersect(hitbox, *block_collision, face, vel)
-						.map(|t| (t, face))
-						.filter(|&(t, _)| t <= max_t)
-				})
-				.min_by(|(a, _), (b, _)| a.total_cmp(b))
+		.filter(overlaps)
+		.fold(delta, |delta, block| {
+			if delta > 0. && block.min[i] >= hitbox.max[i] - COLLISION_EPSILON {
+				delta.min((block.min[i] - hitbox.max[i]).max(0.))
+			} else if delta < 0. && block.max[i] <= hitbox.min[i] + COLLISION_EPSILON {
+				delta.max((block.max[i] - hitbox.min[i]).min(0.))
+			} else {
+				delta
+			}
 		})
-		.min_by(|(a, _), (b, _)| a.total_cmp(b))
 }
 
-/// similar to [`find_min_movement`], except only checks
-/// for a single given block and a single given direction
-fn find_plane_intersect(
-	hitbox: Cuboid,
-	block_col: Cuboid,
-	block_face: Face,
-	vel: Vec3,
-) -> Option<f32> {
-	let hitbox_face = block_face.opposite();
-
-	macro_rules! faces_intersect {
-		($($face:ident => $axis:ident, $hitbox_side:ident, $block_side:ident,
-		$other_plane:ident, $v_cmp:tt, $b_cmp:tt);* $(;)?) => {
-			match hitbox_face { $(
-				Face::$face => {
-					if vel.$axis $v_cmp 0. {
-						return None;
-					}
-
-					let hitbox_max = hitbox.$hitbox_side.$axis;
-					let block_min = block_col.$block_side.$axis;
-					if hitbox_max $b_cmp block_min {
-						return None;
-					}
-
-					// time to hit the block
-					let t = (block_min - hitbox_max) / vel.$axis;
-					if t < 0. {
-						return None;
-					}
-					let delta = vel.$other_plane() * t;
-
-					// check whether the planes intersect
-					let corner_min = hitbox.min.$other_plane() + delta;
-					let corner_max = hitbox.max.$other_plane() + delta;
-					let hitbox_plane = Rect::from_corners(corner_min, corner_max);
-
-					let corner_min = block_col.min.$other_plane();
-					let corner_max = block_col.max.$other_plane();
-					let block_plane = Rect::from_corners(corner_min, corner_max);
-
-					rects_intersect(hitbox_plane, block_plane).then_some(t)
-				}
-			)* }
-		};
-	}
-
-	faces_intersect!(
-		Right   => x, max, min, yz, <=, >;
-		Left    => x, min, max, yz, >=, <;
-		Up      => y, max, min, xz, <=, >;
-		Down    => y, min, max, xz, >=, <;
-		Back    => z, max, min, xy, <=, >;
-		Forward => z, min, max, xy, >=, <;
-	)
+/// whether the hitbox is inside of any block
+pub fn collides_with_blocks(hitbox: Cuboid, game_world: &GameWorld) -> bool {
+	let positions = get_all_block_pos_for_cuboid_cast(hitbox, Vec3::ZERO);
+	get_block_collisions(positions, game_world)
+		.into_iter()
+		.any(|block| !hitbox.intersect(block).is_empty())
 }
 
-fn rects_intersect(a: Rect, b: Rect) -> bool {
-	// if allow_thin {
-	a.min.cmple(b.max).all() && b.min.cmple(a.max).all()
-	// } else {
-	// a.min.cmplt(b.max).all() && b.min.cmplt(a.max).all()
-	// }
+/// shortens a horizontal movement, so that the hitbox doesn't leave the ground it stands on.<br>
+/// the movement along x is limited first, and then the movement along z
+fn keep_on_ledge(hitbox: Cuboid, delta: Vec3, step_height: f32, game_world: &GameWorld) -> Vec3 {
+	// the blocks the hitbox could stand on during the movement
+	let below = Cuboid::from_corners(
+		hitbox.min - Vec3::Y * (step_height + LEDGE_TOLERANCE),
+		Vec3::new(hitbox.max.x, hitbox.min.y, hitbox.max.z),
+	);
+	let positions = get_all_block_pos_for_cuboid_cast(below, delta);
+	let ground = get_block_collisions(positions, game_world)
+		.into_iter()
+		.filter(|block| block.max.y > below.min.y && block.min.y < below.max.y)
+		.collect::<Vec<_>>();
+	let x = limit_to_ground(&ground, hitbox, delta.x, Axis::X);
+	let z = limit_to_ground(&ground, hitbox + Vec3::X * x, delta.z, Axis::Z);
+	Vec3::new(x, delta.y, z)
+}
+
+/// limits how far the hitbox moves along a horizontal axis,
+/// so that it still overlaps some of the ground afterwards
+fn limit_to_ground(ground: &[Cuboid], hitbox: Cuboid, delta: f32, axis: Axis) -> f32 {
+	let along = axis.index();
+	// the other horizontal axis
+	let across = 2 - along;
+	// the range of movements that keep the hitbox above each block
+	let ranges = ground
+		.iter()
+		.filter(|block| {
+			block.min[across] < hitbox.max[across] && hitbox.min[across] < block.max[across]
+		})
+		.map(|block| {
+			(
+				block.min[along] - hitbox.max[along] + LEDGE_OVERLAP,
+				block.max[along] - hitbox.min[along] - LEDGE_OVERLAP,
+			)
+		});
+	if delta > 0. {
+		ranges
+			.filter(|&(min, _)| min <= delta)
+			.map(|(_, max)| max.min(delta))
+			.max_by(f32::total_cmp)
+			.unwrap_or(0.)
+			.max(0.)
+	} else if delta < 0. {
+		ranges
+			.filter(|&(_, max)| max >= delta)
+			.map(|(min, _)| min.max(delta))
+			.min_by(f32::total_cmp)
+			.unwrap_or(0.)
+			.min(0.)
+	} else {
+		0.
+	}
 }
 
 // NOTE the current impl does not work with blocks that are larger than 1x1x1
@@ -239,3 +285,323 @@ fn get_all_block_pos_for_cuboid_cast(cuboid: Cuboid, delta: Vec3) -> Vec<BlockPo
 		.map(|[x, y, z]| BlockPos::new(x, y, z))
 		.collect()
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{block::prelude::*, pos::ChunkPos};
+	use bevy::ecs::system::RunSystemOnce;
+	use std::time::Duration;
+
+	const GRAVITY: f32 = -20.;
+
+	/// an empty world with a stone floor at y = -1, so that entities stand at y = 0.<br>
+	/// the floor ends at x = 16 and z = 16
+	fn world_with_floor() -> World {
+		let mut world = World::new();
+		let chunks = (-1..=1).flat_map(|x| {
+			(-1..=1).flat_map(move |y| (-1..=1).map(move |z| ChunkPos::new(x, y, z)))
+		});
+		let mut game_world = GameWorld::with_air_chunks(chunks);
+		for x in -16..16 {
+			for z in -16..16 {
+				game_world.replace_block(BlockPos::new(x, -1, z), Stone::BLOCK);
+			}
+		}
+		world.insert_resource(game_world);
+		world.insert_resource(Time::<()>::default());
+		world.init_resource::<Events<LandEvent>>();
+		world
+	}
+
+	fn set_block(world: &mut World, pos: BlockPos, block: Block) {
+		world.resource_mut::<GameWorld>().replace_block(pos, block);
+	}
+
+	/// an entity with the size of the player, standing on the ground
+	fn spawn_walker(world: &mut World, translation: Vec3, vel: Vec3) -> Entity {
+		world
+			.spawn((
+				Transform::from_translation(translation),
+				Velocity::new(vel),
+				BoxCollider::new(0.8, 1.85).with_step_height(0.6),
+				OnGround(true),
+				StepOffset::default(),
+			))
+			.id()
+	}
+
+	/// like [`spawn_walker`], but the entity starts in the air
+	fn spawn_faller(world: &mut World, translation: Vec3, vel: Vec3) -> Entity {
+		let entity = spawn_walker(world, translation, vel);
+		world.get_mut::<OnGround>(entity).unwrap().0 = false;
+		entity
+	}
+
+	/// runs `frames` frames that each take `dt` seconds, with gravity like in the game
+	fn run(world: &mut World, frames: usize, dt: f32) {
+		for _ in 0..frames {
+			world
+				.resource_mut::<Time>()
+				.advance_by(Duration::from_secs_f32(dt));
+			for mut vel in world.query::<&mut Velocity>().iter_mut(world) {
+				vel.vel.y += GRAVITY * dt;
+			}
+			world.run_system_once(move_and_slide).unwrap();
+			for mut vel in world.query::<&mut Velocity>().iter_mut(world) {
+				*vel = Velocity::new(vel.vel);
+			}
+		}
+	}
+
+	fn translation(world: &World, entity: Entity) -> Vec3 {
+		world.get::<Transform>(entity).unwrap().translation
+	}
+
+	fn velocity(world: &World, entity: Entity) -> Vec3 {
+		world.get::<Velocity>(entity).unwrap().vel
+	}
+
+	fn on_ground(world: &World, entity: Entity) -> bool {
+		world.get::<OnGround>(entity).unwrap().0
+	}
+
+	#[test]
+	fn landing_puts_the_hitbox_exactly_on_the_ground() {
+		let mut world = world_with_floor();
+		let entity = spawn_faller(&mut world, Vec3::new(0.5, 3.3, 0.5), Vec3::ZERO);
+		run(&mut world, 60, 1. / 60.);
+
+		assert_eq!(translation(&world, entity), Vec3::new(0.5, 0., 0.5));
+		assert_eq!(velocity(&world, entity).y, 0.);
+		assert!(on_ground(&world, entity));
+		assert_eq!(world.resource::<Events<LandEvent>>().len(), 1);
+	}
+
+	#[test]
+	fn fast_falls_dont_go_through_the_floor() {
+		let mut world = world_with_floor();
+		let entity = spawn_faller(&mut world, Vec3::new(0.5, 40., 0.5), Vec3::Y * -500.);
+		run(&mut world, 1, 0.1);
+
+		assert_eq!(translation(&world, entity).y, 0.);
+		assert!(on_ground(&world, entity));
+	}
+
+	#[test]
+	fn falls_shorter_than_the_step_height_dont_go_through_the_floor() {
+		let mut world = world_with_floor();
+		// falls 0.4 blocks in the tick, which is less than the step height of 0.6
+		let entity = spawn_faller(&mut world, Vec3::new(0.5, 0.3, 0.5), Vec3::Y * -24.);
+		run(&mut world, 1, 1. / 60.);
+
+		assert_eq!(translation(&world, entity).y, 0.);
+		assert!(on_ground(&world, entity));
+	}
+
+	#[test]
+	fn fast_movement_doesnt_go_through_walls() {
+		let mut world = world_with_floor();
+		set_block(&mut world, BlockPos::new(5, 0, 0), Stone::BLOCK);
+		set_block(&mut world, BlockPos::new(5, 1, 0), Stone::BLOCK);
+		let entity = spawn_walker(&mut world, Vec3::new(0.5, 0., 0.5), Vec3::X * 300.);
+		run(&mut world, 1, 0.1);
+
+		assert_eq!(translation(&world, entity).x, 4.6);
+		assert_eq!(velocity(&world, entity).x, 0.);
+	}
+
+	#[test]
+	fn low_frame_rates_end_up_in_the_same_place() {
+		let positions = [(60, 1. / 60.), (4, 0.25), (1, 1.)].map(|(frames, dt)| {
+			let mut world = world_with_floor();
+			for y in 0..2 {
+				for z in -2..=2 {
+					set_block(&mut world, BlockPos::new(3, y, z), Stone::BLOCK);
+				}
+			}
+			let entity = spawn_walker(&mut world, Vec3::new(0.5, 0., 0.5), Vec3::X * 5.);
+			run(&mut world, frames, dt);
+			translation(&world, entity)
+		});
+
+		for pos in positions {
+			assert_eq!(pos, Vec3::new(2.6, 0., 0.5));
+		}
+	}
+
+	#[test]
+	fn ceilings_stop_jumps() {
+		let mut world = world_with_floor();
+		set_block(&mut world, BlockPos::new(0, 3, 0), Stone::BLOCK);
+		let entity = spawn_walker(&mut world, Vec3::new(0.5, 0., 0.5), Vec3::Y * 20.);
+		for _ in 0..60 {
+			run(&mut world, 1, 1. / 60.);
+			if velocity(&world, entity).y <= 0. {
+				break;
+			}
+		}
+
+		// the jump ended at the ceiling, and not before it
+		assert_eq!(translation(&world, entity).y, 3. - 1.85);
+		assert_eq!(velocity(&world, entity).y, 0.);
+	}
+
+	#[test]
+	fn sliding_along_a_wall_continues_past_its_end() {
+		let mut world = world_with_floor();
+		// the wall ends at z = 0
+		for z in -16..0 {
+			for y in 0..2 {
+				set_block(&mut world, BlockPos::new(2, y, z), Stone::BLOCK);
+			}
+		}
+		let entity = spawn_walker(&mut world, Vec3::new(1.6, 0., -5.), Vec3::new(1., 0., 4.));
+		run(&mut world, 60, 1. / 60.);
+
+		let pos = translation(&world, entity);
+		// the wall stopped the movement towards it, and nothing else
+		assert_eq!(pos.x, 1.6);
+		assert!(pos.z > -1.01 && pos.z < -0.99, "{pos}");
+		assert_eq!(velocity(&world, entity).x, 0.);
+		assert_eq!(velocity(&world, entity).z, 4.);
+	}
+
+	#[test]
+	fn moving_into_an_inside_corner_stops_at_both_walls() {
+		let mut world = world_with_floor();
+		for i in -16..16 {
+			for y in 0..2 {
+				set_block(&mut world, BlockPos::new(3, y, i), Stone::BLOCK);
+				set_block(&mut world, BlockPos::new(i, y, 3), Stone::BLOCK);
+			}
+		}
+		let entity = spawn_walker(&mut world, Vec3::new(0.5, 0., 0.5), Vec3::new(5., 0., 7.));
+		run(&mut world, 60, 1. / 60.);
+
+		assert_eq!(translation(&world, entity), Vec3::new(2.6, 0., 2.6));
+	}
+
+	#[test]
+	fn falling_onto_the_corner_of_a_block_lands_on_it() {
+		let mut world = world_with_floor();
+		set_block(&mut world, BlockPos::new(0, 1, 0), Stone::BLOCK);
+		// the hitbox only overlaps the block by 0.01 on both axes
+		let entity = spawn_faller(&mut world, Vec3::new(1.39, 4., 1.39), Vec3::ZERO);
+		run(&mut world, 60, 1. / 60.);
+
+		assert_eq!(translation(&world, entity), Vec3::new(1.39, 2., 1.39));
+		assert!(on_ground(&world, entity));
+	}
+
+	#[test]
+	fn walking_off_a_corner_falls_down() {
+		let mut world = world_with_floor();
+		set_block(&mut world, BlockPos::new(0, 0, 0), Stone::BLOCK);
+		let entity = spawn_faller(&mut world, Vec3::new(0.5, 1., 0.5), Vec3::new(2., 0., 2.));
+		run(&mut world, 60, 1. / 60.);
+
+		let pos = translation(&world, entity);
+		assert_eq!(pos.y, 0., "{pos}");
+		assert!(pos.x > 2. && pos.z > 2., "{pos}");
+	}
+
+	#[test]
+	fn landing_on_a_slab() {
+		let mut world = world_with_floor();
+		set_block(&mut world, BlockPos::new(0, 0, 0), DebugSlab::BLOCK);
+		let entity = spawn_faller(&mut world, Vec3::new(0.5, 2., 0.5), Vec3::ZERO);
+		run(&mut world, 60, 1. / 60.);
+
+		assert_eq!(translation(&world, entity).y, 0.5);
+		assert!(on_ground(&world, entity));
+	}
+
+	#[test]
+	fn walking_into_a_slab_steps_onto_it() {
+		let mut world = world_with_floor();
+		for x in 2..8 {
+			set_block(&mut world, BlockPos::new(x, 0, 0), DebugSlab::BLOCK);
+		}
+		let entity = spawn_walker(&mut world, Vec3::new(0.5, 0., 0.5), Vec3::X * 4.);
+		run(&mut world, 60, 1. / 60.);
+
+		let pos = translation(&world, entity);
+		assert!(pos.x > 3., "didn't get past the slab: {pos}");
+		assert_eq!(pos.y, 0.5, "isn't standing on the slab: {pos}");
+		assert!(world.get::<StepOffset>(entity).unwrap().0 > 0.);
+	}
+
+	#[test]
+	fn stepping_works_at_low_frame_rates() {
+		let mut world = world_with_floor();
+		for x in 2..8 {
+			set_block(&mut world, BlockPos::new(x, 0, 0), DebugSlab::BLOCK);
+		}
+		let entity = spawn_walker(&mut world, Vec3::new(0.5, 0., 0.5), Vec3::X * 4.);
+		run(&mut world, 4, 0.25);
+
+		let pos = translation(&world, entity);
+		assert!(pos.x > 3. && pos.y == 0.5, "{pos}");
+	}
+
+	#[test]
+	fn full_blocks_are_too_high_to_step_onto() {
+		let mut world = world_with_floor();
+		set_block(&mut world, BlockPos::new(2, 0, 0), Stone::BLOCK);
+		let entity = spawn_walker(&mut world, Vec3::new(0.5, 0., 0.5), Vec3::X * 4.);
+		run(&mut world, 30, 1. / 60.);
+
+		assert_eq!(translation(&world, entity), Vec3::new(1.6, 0., 0.5));
+	}
+
+	#[test]
+	fn steps_need_room_above_them() {
+		let mut world = world_with_floor();
+		set_block(&mut world, BlockPos::new(2, 0, 0), DebugSlab::BLOCK);
+		set_block(&mut world, BlockPos::new(2, 2, 0), Stone::BLOCK);
+		let entity = spawn_walker(&mut world, Vec3::new(0.5, 0., 0.5), Vec3::X * 4.);
+		run(&mut world, 30, 1. / 60.);
+
+		assert_eq!(translation(&world, entity), Vec3::new(1.6, 0., 0.5));
+	}
+
+	#[test]
+	fn steps_only_happen_on_the_ground() {
+		let mut world = world_with_floor();
+		set_block(&mut world, BlockPos::new(2, 1, 0), DebugSlab::BLOCK);
+		let entity = spawn_faller(&mut world, Vec3::new(0.5, 1.2, 0.5), Vec3::X * 4.);
+		run(&mut world, 1, 0.3);
+
+		assert_eq!(translation(&world, entity).x, 1.6);
+	}
+
+	#[test]
+	fn crouching_stops_at_ledges() {
+		let mut world = world_with_floor();
+		let entity = spawn_walker(&mut world, Vec3::new(15., 0., 15.), Vec3::new(3., 0., 3.));
+		world.entity_mut(entity).insert(Crouching(true));
+		run(&mut world, 60, 1. / 60.);
+
+		let pos = translation(&world, entity);
+		assert_eq!(pos.y, 0., "fell off the ledge: {pos}");
+		// the hitbox sticks out over the edge as far as possible
+		assert!(pos.x > 16.39 && pos.x < 16.4, "{pos}");
+		assert!(pos.z > 16.39 && pos.z < 16.4, "{pos}");
+		assert!(on_ground(&world, entity));
+	}
+
+	#[test]
+	fn crouching_can_walk_down_low_steps() {
+		let mut world = world_with_floor();
+		for x in -16..16 {
+			set_block(&mut world, BlockPos::new(x, 0, 0), DebugSlab::BLOCK);
+		}
+		let entity = spawn_walker(&mut world, Vec3::new(0.5, 0.5, 0.5), Vec3::Z * 3.);
+		world.entity_mut(entity).insert(Crouching(true));
+		run(&mut world, 60, 1. / 60.);
+
+		let pos = translation(&world, entity);
+		assert!(pos.z > 3. && pos.y == 0., "{pos}");
+	}
+}
@@ -0,0 +1,122 @@
+use super::{
+	collision::in_fluid::InFluid,
+	movement::{Gravity, LandEvent, MovementSet},
+	player::GameMode,
+};
+use crate::GlobalState;
+use bevy::prelude::*;
+
+pub struct HealthPlugin;
+
+impl Plugin for HealthPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_event::<DamageEvent>()
+			.add_event::<DeathEvent>()
+			.add_systems(
+				FixedUpdate,
+				(fall_damage, apply_damage)
+					.chain()
+					.after(MovementSet::Translate)
+					.run_if(in_state(GlobalState::InWorld)),
+			);
+	}
+}
+
+/// how many blocks an entity can fall without taking damage
+const SAFE_FALL_HEIGHT: f32 = 3.0;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Health {
+	pub current: f32,
+	pub max: f32,
+}
+
+/// why an entity was damaged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageSource {
+	/// hitting the ground too fast
+	Fall,
+}
+
+/// damages an entity that has [`Health`]
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DamageEvent {
+	pub entity: Entity,
+	pub amount: f32,
+	pub source: DamageSource,
+}
+
+/// sent when the [`Health`] of an entity reaches 0
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DeathEvent {
+	pub entity: Entity,
+	pub source: DamageSource,
+}
+
+impl Health {
+	pub fn new(max: f32) -> Self {
+		Self { current: max, max }
+	}
+
+	pub fn is_dead(self) -> bool {
+		self.current <= 0.0
+	}
+
+	/// sets the health back to the maximum
+	pub fn heal_fully(&mut self) {
+		self.current = self.max;
+	}
+}
+
+/// damages entities that hit the ground after falling too far
+fn fall_damage(
+	mut land_events: EventReader<LandEvent>,
+	entities: Query<(&Gravity, Option<&InFluid>), With<Health>>,
+	mut damage_events: EventWriter<DamageEvent>,
+) {
+	for event in land_events.read() {
+		let Ok((gravity, in_fluid)) = entities.get(event.entity) else {
+			continue;
+		};
+		// fluids slow down the fall
+		if in_fluid.is_some_and(|in_fluid| in_fluid.kind.is_some()) {
+			continue;
+		}
+		let gravity = gravity.0.length();
+		if gravity <= 0.0 {
+			continue;
+		}
+		// v² = 2gh
+		let fall_height = event.speed * event.speed / (2.0 * gravity);
+		let amount = (fall_height - SAFE_FALL_HEIGHT).floor();
+		if amount > 0.0 {
+			damage_events.send(DamageEvent {
+				entity: event.entity,
+				amount,
+				source: DamageSource::Fall,
+			});
+		}
+	}
+}
+
+fn apply_damage(
+	mut damage_events: EventReader<DamageEvent>,
+	mut entities: Query<(&mut Health, Option<&GameMode>)>,
+	mut death_events: EventWriter<DeathEvent>,
+) {
+	for event in damage_events.read() {
+		let Ok((mut health, game_mode)) = entities.get_mut(event.entity) else {
+			continue;
+		};
+		if health.is_dead() || game_mode.is_some_and(|mode| !mode.takes_damage()) {
+			continue;
+		}
+		health.current = (health.current - event.amount).max(0.0);
+		if health.is_dead() {
+			death_events.send(DeathEvent {
+				entity: event.entity,
+				source: event.source,
+			});
+		}
+	}
+}
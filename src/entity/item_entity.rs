@@ -0,0 +1,178 @@
+use super::{
+	collision::collider::BoxCollider,
+	movement::{Gravity, MovementSet, OnGround, Velocity},
+	player::Player,
+};
+use crate::{
+	block_model::GlobalTexture,
+	game_world::chunk::{create_block_mesh, has_loaded_global_material, GlobalChunkMaterial},
+	item::{Inventory, Item, ItemStack},
+	GlobalState,
+};
+use bevy::{prelude::*, utils::HashMap};
+use rand::Rng;
+
+pub struct ItemEntityPlugin;
+
+impl Plugin for ItemEntityPlugin {
+	fn build(&self, app: &mut App) {
+		app.insert_resource(ItemMeshes::default())
+			.add_systems(OnExit(GlobalState::InWorld), despawn_all)
+			.add_systems(
+				FixedUpdate,
+				(
+					ground_friction.in_set(MovementSet::Accel),
+					pick_up_items.after(MovementSet::Translate),
+				)
+					.run_if(in_state(GlobalState::InWorld)),
+			)
+			.add_systems(
+				Update,
+				(
+					update_pickup_delay,
+					(spawn_item_models, rotate_item_models).run_if(has_loaded_global_material),
+				)
+					.run_if(in_state(GlobalState::InWorld)),
+			);
+	}
+}
+
+const SIZE: f32 = 0.25;
+const GRAVITY: f32 = -20.0;
+/// how many seconds an item has to exist, before it can be picked up
+const PICKUP_DELAY: f32 = 0.3;
+/// how fast the model of an item rotates, in radians per second
+const ROTATION_SPEED: f32 = 1.5;
+
+/// an item lying around in the world, that can be picked up
+#[derive(Component, Debug, Clone)]
+#[require(Transform, Visibility)]
+pub struct ItemEntity {
+	pub stack: ItemStack,
+	/// how many seconds are left until this can be picked up
+	pickup_delay: f32,
+}
+
+/// the visuals of an [`ItemEntity`], which are a child of it
+#[derive(Component)]
+struct ItemModel;
+
+/// the mesh of every item that has been dropped so far, so that they are only created once
+#[derive(Resource, Default)]
+struct ItemMeshes {
+	meshes: HashMap<Item, Handle<Mesh>>,
+}
+
+/// spawns an item at the given position, which jumps in a random direction
+pub fn spawn_item_entity(commands: &mut Commands, pos: Vec3, stack: ItemStack) {
+	let mut rng = rand::thread_rng();
+	let velocity = Velocity::new(Vec3::new(
+		rng.gen_range(-1.5..1.5),
+		4.0,
+		rng.gen_range(-1.5..1.5),
+	));
+	commands.spawn((
+		ItemEntity {
+			stack,
+			pickup_delay: PICKUP_DELAY,
+		},
+		Transform::from_translation(pos - Vec3::Y * SIZE / 2.),
+		velocity,
+		Gravity::vertical(GRAVITY),
+		BoxCollider::new(SIZE, SIZE),
+		OnGround::default(),
+		Name::new(format!("Item ({})", stack.item.name())),
+	));
+}
+
+fn despawn_all(mut commands: Commands, items: Query<Entity, With<ItemEntity>>) {
+	for item in &items {
+		commands.entity(item).despawn_recursive();
+	}
+}
+
+/// slows down items that are lying on the ground, so they dont slide around forever
+fn ground_friction(
+	mut items: Query<(&mut Velocity, &OnGround), With<ItemEntity>>,
+	time: Res<Time>,
+) {
+	let factor = (1. - 10. * time.delta_secs()).max(0.);
+	for (mut vel, on_ground) in &mut items {
+		if on_ground.0 {
+			vel.vel.x *= factor;
+			vel.vel.z *= factor;
+		}
+	}
+}
+
+fn update_pickup_delay(mut items: Query<&mut ItemEntity>, time: Res<Time>) {
+	for mut item in &mut items {
+		if item.pickup_delay > 0. {
+			item.pickup_delay -= time.delta_secs();
+		}
+	}
+}
+
+/// puts every item that touches the player into its inventory
+fn pick_up_items(
+	mut commands: Commands,
+	mut player: Query<(&Transform, &BoxCollider, &mut Inventory), With<Player>>,
+	mut items: Query<(Entity, &Transform, &BoxCollider, &mut ItemEntity), Without<Player>>,
+) {
+	let Ok((player_trans, player_col, mut inventory)) = player.get_single_mut() else {
+		return;
+	};
+	let player_hitbox = player_col.into_cuboid() + player_trans.translation;
+	for (entity, trans, col, mut item) in &mut items {
+		if item.pickup_delay > 0. {
+			continue;
+		}
+		let hitbox = col.into_cuboid() + trans.translation;
+		if player_hitbox.intersect(hitbox).is_empty() {
+			continue;
+		}
+		let leftover = inventory.add(item.stack);
+		if leftover == 0 {
+			commands.entity(entity).despawn_recursive();
+		} else {
+			item.stack.count = leftover;
+		}
+	}
+}
+
+fn spawn_item_models(
+	mut commands: Commands,
+	items: Query<(Entity, &ItemEntity), Added<ItemEntity>>,
+	mut item_meshes: ResMut<ItemMeshes>,
+	mut meshes: ResMut<Assets<Mesh>>,
+	global_texture: Res<GlobalTexture>,
+	global_material: Res<GlobalChunkMaterial>,
+) {
+	for (entity, item) in &items {
+		let mesh = item_meshes
+			.meshes
+			.entry(item.stack.item)
+			.or_insert_with(|| {
+				let Item::Block(id) = item.stack.item;
+				let block_model = global_texture
+					.mappings
+					.get(&id)
+					.unwrap_or_else(|| panic!("tried to get the model of block with id {:?}", id));
+				// center the mesh, so that it rotates around its center
+				meshes.add(create_block_mesh(block_model).translated_by(Vec3::splat(-0.5)))
+			})
+			.clone();
+		commands.entity(entity).with_child((
+			ItemModel,
+			Mesh3d(mesh),
+			MeshMaterial3d(global_material.material.clone()),
+			Transform::from_xyz(0., SIZE / 2., 0.).with_scale(Vec3::splat(SIZE)),
+		));
+	}
+}
+
+fn rotate_item_models(mut models: Query<&mut Transform, With<ItemModel>>, time: Res<Time>) {
+	for mut trans in &mut models {
+		trans.rotate_y(ROTATION_SPEED * time.delta_secs());
+	}
+}
@@ -1,16 +1,21 @@
 pub mod collision;
+pub mod health;
+pub mod item_entity;
 pub mod movement;
 pub mod player;
 
 use bevy::prelude::*;
 
+/// the physics of all entities. the player you control is added by [`player::PlayerPlugin`],
+/// since a server without a window doesn't have one
 pub struct EntityPlugin;
 
 impl Plugin for EntityPlugin {
 	fn build(&self, app: &mut App) {
 		app.add_plugins((
-			player::PlayerPlugin,
 			collision::CollisionPlugin,
+			health::HealthPlugin,
+			item_entity::ItemEntityPlugin,
 			movement::MovementPlugin,
 		));
 	}
@@ -1,13 +1,14 @@
 use super::collision::collider::BoxCollider;
-use crate::GlobalState;
-use bevy::prelude::*;
+use crate::{global_config::Config, GlobalState};
+use bevy::{app::RunFixedMainLoopSystem, prelude::*};
 
 pub struct MovementPlugin;
 
 impl Plugin for MovementPlugin {
 	fn build(&self, app: &mut App) {
+		app.add_event::<LandEvent>();
 		app.add_systems(
-			Update,
+			FixedUpdate,
 			(
 				move_without_collision.in_set(MovementSet::Translate),
 				gravity.in_set(MovementSet::Accel),
@@ -16,12 +17,21 @@ impl Plugin for MovementPlugin {
 				// NOTE not sure if this should only run InWorld
 				.run_if(in_state(GlobalState::InWorld)),
 		)
+		.add_systems(FixedFirst, start_tick)
+		.add_systems(
+			RunFixedMainLoop,
+			(
+				restore_ticked_translation.in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop),
+				interpolate_translation.in_set(RunFixedMainLoopSystem::AfterFixedMainLoop),
+			),
+		)
+		.add_systems(Update, set_tick_rate.run_if(resource_changed::<Config>))
 		.configure_sets(
-			Update,
+			FixedUpdate,
 			(
 				MovementSet::Accel,
+				MovementSet::Push,
 				MovementSet::Translate,
-				MovementSet::Camera,
 				// MovementSet::Get,
 				MovementSet::CleanUp,
 			)
@@ -30,28 +40,39 @@ impl Plugin for MovementPlugin {
 	}
 }
 
+/// the physics of entities run in [`FixedUpdate`], at the tick rate from the [`Config`],
+/// so that they behave the same at every frame rate.<br>
+/// only [`MovementSet::Camera`] is in [`Update`], since it follows the interpolated [`Transform`]
 #[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
 pub enum MovementSet {
 	/// changes velocity / accelerates
 	Accel,
+	/// pushes overlapping entities apart, on top of how they accelerated themselves
+	Push,
 	/// uses velocity to change position
 	Translate,
-	/// moves the camera. this needs to be done after
-	/// [`MovementSet::Translate`], to make the camera not jittery
+	/// moves the camera, in [`Update`]. this is always done after the ticks of the frame,
+	/// so the camera follows the interpolated position and isn't jittery
 	Camera,
 	// /// Gets information about movement without changing anything about it.
 	// Get,
-	/// done at the end of the frame to clean up (set previous velocity, etc.)
+	/// done at the end of the tick to clean up (set previous velocity, etc.)
 	CleanUp,
 }
 
 #[derive(Component, Debug, Default, Clone, Copy)]
+#[require(TickTranslation)]
 pub struct Velocity {
 	pub vel: Vec3,
 	prev_vel: Vec3,
 }
 
 impl Velocity {
+	/// a velocity that didn't change since the last frame
+	pub fn new(vel: Vec3) -> Self {
+		Self { vel, prev_vel: vel }
+	}
+
 	pub fn delta(self) -> Vec3 {
 		self.vel - self.prev_vel
 	}
@@ -71,6 +92,31 @@ impl Gravity {
 #[derive(Component, Default, Debug, Clone, Copy)]
 pub struct OnGround(pub bool);
 
+/// whether the entity is crouching.<br>
+/// crouching entities on the ground don't walk off ledges
+#[derive(Component, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crouching(pub bool);
+
+/// the translation of a moving entity at the last two ticks.<br>
+/// between frames the [`Transform`] is interpolated between them, so that movement looks smooth
+/// even when the frame rate is higher than the tick rate.
+/// during ticks the [`Transform`] holds the actual translation.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct TickTranslation {
+	previous: Vec3,
+	current: Vec3,
+	/// what the [`Transform`] was set to after the ticks, to notice when something else moved it
+	shown: Vec3,
+}
+
+/// sent when an entity with [`OnGround`] hits the ground after being in the air
+#[derive(Event, Debug, Clone, Copy)]
+pub struct LandEvent {
+	pub entity: Entity,
+	/// how fast the entity was moving downwards when it hit the ground
+	pub speed: f32,
+}
+
 fn move_without_collision(
 	mut query: Query<(&mut Transform, &Velocity), Without<BoxCollider>>,
 	time: Res<Time>,
@@ -96,3 +142,114 @@ fn update_prev_vel(mut query: Query<&mut Velocity>) {
 		vel.prev_vel = vel.vel;
 	}
 }
+
+fn set_tick_rate(config: Res<Config>, mut fixed_time: ResMut<Time<Fixed>>) {
+	fixed_time.set_timestep_hz(config.tick_rate.max(1.));
+}
+
+/// puts the actual translation back into the [`Transform`] before the ticks of this frame.<br>
+/// if something outside of the ticks moved the entity (like a teleport), it isn't interpolated
+fn restore_ticked_translation(mut query: Query<(&mut Transform, &mut TickTranslation)>) {
+	for (mut trans, mut ticked) in &mut query {
+		if trans.translation != ticked.shown {
+			ticked.previous = trans.translation;
+			ticked.current = trans.translation;
+		}
+		trans.translation = ticked.current;
+	}
+}
+
+fn start_tick(mut query: Query<(&Transform, &mut TickTranslation)>) {
+	for (trans, mut ticked) in &mut query {
+		ticked.previous = trans.translation;
+	}
+}
+
+/// shows the entities between their last two ticks, depending on how far it is until the next one
+fn interpolate_translation(
+	mut query: Query<(&mut Transform, &mut TickTranslation)>,
+	fixed_time: Res<Time<Fixed>>,
+) {
+	let s = fixed_time.overstep_fraction();
+	for (mut trans, mut ticked) in &mut query {
+		ticked.current = trans.translation;
+		if ticked.is_added() {
+			// entities spawned during a tick have no previous translation yet
+			ticked.previous = trans.translation;
+		}
+		trans.translation = ticked.previous.lerp(ticked.current, s);
+		ticked.shown = trans.translation;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bevy::ecs::system::SystemId;
+
+	struct Ticks {
+		world: World,
+		restore: SystemId,
+		start: SystemId,
+		interpolate: SystemId,
+	}
+
+	impl Ticks {
+		fn new() -> Self {
+			let mut world = World::new();
+			world.insert_resource(Time::<Fixed>::from_hz(10.));
+			Self {
+				restore: world.register_system(restore_ticked_translation),
+				start: world.register_system(start_tick),
+				interpolate: world.register_system(interpolate_translation),
+				world,
+			}
+		}
+
+		/// runs one frame in which the entity moved by `tick_movement` in one tick,
+		/// and which ended exactly at that tick, so the previous tick is shown
+		fn run_frame(&mut self, entity: Entity, tick_movement: Vec3) {
+			self.world.run_system(self.restore).unwrap();
+			self.world.run_system(self.start).unwrap();
+			self.transform_mut(entity).translation += tick_movement;
+			self.world.run_system(self.interpolate).unwrap();
+		}
+
+		fn transform_mut(&mut self, entity: Entity) -> Mut<'_, Transform> {
+			self.world.get_mut::<Transform>(entity).unwrap()
+		}
+
+		fn shown(&self, entity: Entity) -> Vec3 {
+			self.world.get::<Transform>(entity).unwrap().translation
+		}
+	}
+
+	#[test]
+	fn transforms_are_shown_between_ticks() {
+		let mut ticks = Ticks::new();
+		let entity = ticks
+			.world
+			.spawn((Transform::default(), Velocity::default()))
+			.id();
+		ticks.run_frame(entity, Vec3::ZERO);
+		ticks.run_frame(entity, Vec3::X * 4.);
+		assert_eq!(ticks.shown(entity).x, 0.);
+
+		// the next tick continues from the actual translation
+		ticks.run_frame(entity, Vec3::X * 4.);
+		assert_eq!(ticks.shown(entity).x, 4.);
+	}
+
+	#[test]
+	fn teleports_are_not_interpolated() {
+		let mut ticks = Ticks::new();
+		let entity = ticks
+			.world
+			.spawn((Transform::default(), Velocity::default()))
+			.id();
+		ticks.run_frame(entity, Vec3::ZERO);
+		ticks.transform_mut(entity).translation = Vec3::Y * 100.;
+		ticks.run_frame(entity, Vec3::ZERO);
+		assert_eq!(ticks.shown(entity).y, 100.);
+	}
+}
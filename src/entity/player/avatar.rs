@@ -0,0 +1,210 @@
+//! the body of a player, with a head that looks where the player looks.<br>
+//! this is used for other players when playing online, and for your own player when the camera is outside of it
+
+use super::{cam::PlayerCam, EYE_HEIGHT, HEIGHT, WIDTH};
+use crate::{entity::LookDirection, GlobalState};
+use bevy::{prelude::*, transform::TransformSystem, ui::UiSystem};
+
+pub struct AvatarPlugin;
+
+impl Plugin for AvatarPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(Startup, insert_avatar_assets)
+			.add_systems(OnExit(GlobalState::InWorld), despawn_nametags)
+			.add_systems(
+				PostUpdate,
+				(
+					(spawn_parts, turn_parts)
+						.chain()
+						.before(TransformSystem::TransformPropagate),
+					(spawn_nametags, move_nametags)
+						.chain()
+						.before(UiSystem::Layout),
+				)
+					.run_if(in_state(GlobalState::InWorld)),
+			);
+	}
+}
+
+/// the head is a cube, with the eyes in the middle
+const HEAD_SIZE: f32 = (HEIGHT - EYE_HEIGHT) * 2.;
+
+/// nametags of players that are further away than this are hidden
+const NAMETAG_DISTANCE: f32 = 48.;
+
+/// the nametag is centered in a box with this width, so it doesn't have to be measured
+const NAMETAG_WIDTH: f32 = 300.;
+
+/// roughly the height of the nametag in pixels, so that it ends above the head
+const NAMETAG_HEIGHT: f32 = 20.;
+
+/// shows the entity as a player.<br>
+/// the entity's translation is at its feet, and its [`LookDirection`] turns the body and head
+#[derive(Component, Debug, Default, Clone, Copy)]
+#[require(Transform, Visibility, LookDirection)]
+pub struct Avatar;
+
+/// a name that is shown above an [`Avatar`]
+#[derive(Component, Debug, Clone)]
+pub struct Nametag(pub String);
+
+#[derive(Component)]
+struct AvatarBody;
+
+#[derive(Component)]
+struct AvatarHead;
+
+/// the text of a [`Nametag`], which is moved to be above the avatar on the screen
+#[derive(Component)]
+struct NametagLabel {
+	avatar: Entity,
+}
+
+/// the meshes and materials shared by all avatars
+#[derive(Resource)]
+struct AvatarAssets {
+	body_mesh: Handle<Mesh>,
+	head_mesh: Handle<Mesh>,
+	face_mesh: Handle<Mesh>,
+	body_material: Handle<StandardMaterial>,
+	head_material: Handle<StandardMaterial>,
+	face_material: Handle<StandardMaterial>,
+}
+
+fn insert_avatar_assets(
+	mut commands: Commands,
+	mut meshes: ResMut<Assets<Mesh>>,
+	mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+	let mut unlit = |color| {
+		materials.add(StandardMaterial {
+			base_color: color,
+			unlit: true,
+			..default()
+		})
+	};
+	commands.insert_resource(AvatarAssets {
+		body_mesh: meshes.add(Cuboid::new(WIDTH, HEIGHT - HEAD_SIZE, WIDTH / 2.)),
+		head_mesh: meshes.add(Cuboid::from_length(HEAD_SIZE)),
+		face_mesh: meshes.add(Cuboid::new(HEAD_SIZE * 0.7, HEAD_SIZE * 0.2, 0.02)),
+		body_material: unlit(Color::srgb(0.2, 0.35, 0.6)),
+		head_material: unlit(Color::srgb(0.85, 0.7, 0.55)),
+		face_material: unlit(Color::srgb(0.1, 0.1, 0.1)),
+	});
+}
+
+fn spawn_parts(
+	mut commands: Commands,
+	avatars: Query<Entity, Added<Avatar>>,
+	assets: Res<AvatarAssets>,
+) {
+	for entity in &avatars {
+		commands.entity(entity).with_children(|parent| {
+			parent.spawn((
+				AvatarBody,
+				Mesh3d(assets.body_mesh.clone()),
+				MeshMaterial3d(assets.body_material.clone()),
+				Transform::from_xyz(0., (HEIGHT - HEAD_SIZE) / 2., 0.),
+			));
+			parent
+				.spawn((
+					AvatarHead,
+					Mesh3d(assets.head_mesh.clone()),
+					MeshMaterial3d(assets.head_material.clone()),
+					Transform::from_xyz(0., EYE_HEIGHT, 0.),
+				))
+				.with_children(|parent| {
+					// shows which way the head is facing
+					parent.spawn((
+						Mesh3d(assets.face_mesh.clone()),
+						MeshMaterial3d(assets.face_material.clone()),
+						Transform::from_xyz(0., 0., -HEAD_SIZE / 2.),
+					));
+				});
+		});
+	}
+}
+
+/// turns the body with the yaw, and the head with both the yaw and the pitch
+fn turn_parts(
+	avatars: Query<(&LookDirection, &Children), With<Avatar>>,
+	mut bodies: Query<&mut Transform, (With<AvatarBody>, Without<AvatarHead>)>,
+	mut heads: Query<&mut Transform, (With<AvatarHead>, Without<AvatarBody>)>,
+) {
+	for (&look_direction, children) in &avatars {
+		for &child in children {
+			if let Ok(mut body) = bodies.get_mut(child) {
+				body.rotation = Quat::from_rotation_y(look_direction.yaw);
+			}
+			if let Ok(mut head) = heads.get_mut(child) {
+				head.rotation = look_direction.to_quat();
+			}
+		}
+	}
+}
+
+fn spawn_nametags(mut commands: Commands, nametags: Query<(Entity, &Nametag), Added<Nametag>>) {
+	for (avatar, nametag) in &nametags {
+		commands
+			.spawn((
+				NametagLabel { avatar },
+				Node {
+					position_type: PositionType::Absolute,
+					width: Val::Px(NAMETAG_WIDTH),
+					justify_content: JustifyContent::Center,
+					..default()
+				},
+				Visibility::Hidden,
+			))
+			.with_children(|parent| {
+				parent.spawn((
+					Text::new(nametag.0.clone()),
+					TextColor::from(Color::WHITE),
+					TextFont::from_font_size(16.),
+					Node {
+						padding: UiRect::horizontal(Val::Px(4.)),
+						..default()
+					},
+					BackgroundColor::from(Color::srgba(0., 0., 0., 0.4)),
+				));
+			});
+	}
+}
+
+/// keeps the nametags above the heads of their avatars on the screen.<br>
+/// avatars never have a parent, so their [`Transform`] is already up to date here
+fn move_nametags(
+	mut commands: Commands,
+	mut labels: Query<(Entity, &NametagLabel, &mut Node, &mut Visibility)>,
+	avatars: Query<&Transform, With<Avatar>>,
+	cam: Query<(&Camera, &Transform), With<PlayerCam>>,
+) {
+	let Ok((camera, cam_transform)) = cam.get_single() else {
+		return;
+	};
+	let cam_transform = GlobalTransform::from(*cam_transform);
+	for (entity, label, mut node, mut visibility) in &mut labels {
+		let Ok(avatar) = avatars.get(label.avatar) else {
+			commands.entity(entity).despawn_recursive();
+			continue;
+		};
+		let above_head = avatar.translation + Vec3::Y * (HEIGHT + 0.2);
+		let on_screen = (above_head.distance(cam_transform.translation()) < NAMETAG_DISTANCE)
+			.then(|| camera.world_to_viewport(&cam_transform, above_head).ok())
+			.flatten();
+		match on_screen {
+			Some(pos) => {
+				node.left = Val::Px(pos.x - NAMETAG_WIDTH / 2.);
+				node.top = Val::Px(pos.y - NAMETAG_HEIGHT);
+				*visibility = Visibility::Inherited;
+			}
+			None => *visibility = Visibility::Hidden,
+		}
+	}
+}
+
+fn despawn_nametags(mut commands: Commands, labels: Query<Entity, With<NametagLabel>>) {
+	for entity in &labels {
+		commands.entity(entity).despawn_recursive();
+	}
+}
@@ -4,8 +4,9 @@
 use super::{CanRotateCam, PlayerCam, PlayerCamMode};
 use crate::{
 	entity::{
-		movement::MovementSet,
-		player::{Player, EYE_HEIGHT},
+		collision::StepOffset,
+		movement::{Crouching, MovementSet},
+		player::{eye_height, Player, EYE_HEIGHT},
 		LookDirection,
 	},
 	global_config::Config,
@@ -61,12 +62,14 @@ fn despawn(mut commands: Commands, cam: Query<Entity, With<FirstPersonCam>>) {
 }
 
 fn move_cam(
-	mut cam: Query<&mut Transform, With<FirstPersonCam>>,
-	player: Query<&Transform, (With<Player>, Without<FirstPersonCam>)>,
+	mut cam: Query<&mut Transform, (With<FirstPersonCam>, Without<Player>)>,
+	player: Query<(&Transform, &StepOffset, &Crouching), With<Player>>,
 ) {
 	let mut cam_trans = cam.single_mut();
-	let player_trans = player.single();
-	cam_trans.translation = player_trans.translation + Vec3::new(0.0, EYE_HEIGHT, 0.0);
+	let (player_trans, step_offset, &crouching) = player.single();
+	// the camera lags behind when stepping up, so that it doesn't jump
+	let height = eye_height(crouching) - step_offset.0;
+	cam_trans.translation = player_trans.translation + Vec3::new(0.0, height, 0.0);
 }
 
 fn input_rotation(
@@ -1,5 +1,6 @@
 mod first_person_cam;
 mod free_cam;
+mod third_person_cam;
 
 use crate::GlobalState;
 use bevy::{prelude::*, window::CursorGrabMode};
@@ -11,6 +12,7 @@ impl Plugin for CamPlugin {
 		app.add_plugins((
 			first_person_cam::FirstPersonCamPlugin,
 			free_cam::FreeCamPlugin,
+			third_person_cam::ThirdPersonCamPlugin,
 		))
 		// TODO show cursor when opening inventory and other things
 		.add_systems(OnEnter(GlobalState::InWorld), init)
@@ -18,9 +20,11 @@ impl Plugin for CamPlugin {
 		.add_systems(OnExit(CanRotateCam(true)), show_cursor)
 		.add_systems(
 			Update,
-			(toggle_free_cam, toggle_can_rotate).run_if(in_state(GlobalState::InWorld)),
+			(toggle_free_cam, cycle_cam_mode, toggle_can_rotate)
+				.run_if(in_state(GlobalState::InWorld)),
 		)
 		.add_sub_state::<PlayerCamMode>()
+		.add_computed_state::<CamOutsidePlayer>()
 		.add_sub_state::<CanRotateCam>();
 	}
 }
@@ -30,17 +34,29 @@ impl Plugin for CamPlugin {
 pub enum PlayerCamMode {
 	#[default]
 	FirstPerson,
-	// TODO add ThirdPerson cam mode
-	// ThirdPerson,
+	/// behind the player, looking where the player looks
+	ThirdPersonBack,
+	/// in front of the player, looking at its face
+	ThirdPersonFront,
 	FreeCam,
 }
 
+/// exists while the camera is not inside the player, so that the player model has to be shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct CamOutsidePlayer;
+
+impl ComputedStates for CamOutsidePlayer {
+	type SourceStates = PlayerCamMode;
+
+	fn compute(cam_mode: PlayerCamMode) -> Option<Self> {
+		(cam_mode != PlayerCamMode::FirstPerson).then_some(Self)
+	}
+}
+
 #[derive(SubStates, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 #[source(GlobalState = GlobalState::InWorld)]
 pub struct CanRotateCam(pub bool);
 
-// FIXME for some reason, the player camera moves faster during lag
-
 /// the camera the player currently sees through<br>
 /// this does not mean that it has to be close to the player; it can be a free cam<br>
 /// exactly one `PlayerCam` should exist while in [`GlobalState::InWorld`]
@@ -62,8 +78,23 @@ fn toggle_free_cam(
 ) {
 	if input.just_pressed(KeyCode::KeyF) {
 		next_cam_state.set(match current_cam_state.get() {
-			PlayerCamMode::FirstPerson => PlayerCamMode::FreeCam,
 			PlayerCamMode::FreeCam => PlayerCamMode::FirstPerson,
+			_ => PlayerCamMode::FreeCam,
+		});
+	}
+}
+
+/// switches between first person and the third person views
+fn cycle_cam_mode(
+	input: Res<ButtonInput<KeyCode>>,
+	current_cam_state: Res<State<PlayerCamMode>>,
+	mut next_cam_state: ResMut<NextState<PlayerCamMode>>,
+) {
+	if input.just_pressed(KeyCode::F5) {
+		next_cam_state.set(match current_cam_state.get() {
+			PlayerCamMode::FirstPerson => PlayerCamMode::ThirdPersonBack,
+			PlayerCamMode::ThirdPersonBack => PlayerCamMode::ThirdPersonFront,
+			PlayerCamMode::ThirdPersonFront | PlayerCamMode::FreeCam => PlayerCamMode::FirstPerson,
 		});
 	}
 }
@@ -0,0 +1,120 @@
+//! handles the camera while in one of the third person modes<br>
+//! like in first person, this rotates the actual player's [`LookDirection`] component
+
+use super::{CanRotateCam, PlayerCam, PlayerCamMode};
+use crate::{
+	entity::{
+		collision::{
+			ray::{send_out_ray, FiniteRay},
+			StepOffset,
+		},
+		movement::{Crouching, MovementSet},
+		player::{eye_height, Player},
+		LookDirection,
+	},
+	game_world::GameWorld,
+	global_config::Config,
+	input::{InputSet, RotateInput},
+	GlobalState,
+};
+use bevy::prelude::*;
+use std::f32::consts::PI;
+
+pub struct ThirdPersonCamPlugin;
+
+impl Plugin for ThirdPersonCamPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_computed_state::<InThirdPerson>()
+			.add_systems(OnEnter(InThirdPerson), spawn)
+			.add_systems(OnExit(InThirdPerson), despawn)
+			.add_systems(
+				Update,
+				(
+					move_cam.in_set(MovementSet::Camera),
+					input_rotation
+						.run_if(in_state(CanRotateCam(true)))
+						.in_set(InputSet::Use),
+				)
+					.run_if(in_state(GlobalState::InWorld))
+					.run_if(in_state(InThirdPerson)),
+			);
+	}
+}
+
+/// how far the camera stays away from the terrain it is pulled in by
+const WALL_DISTANCE: f32 = 0.2;
+
+/// exists in both third person modes, so that the camera isn't respawned when switching between them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct InThirdPerson;
+
+impl ComputedStates for InThirdPerson {
+	type SourceStates = PlayerCamMode;
+
+	fn compute(cam_mode: PlayerCamMode) -> Option<Self> {
+		matches!(
+			cam_mode,
+			PlayerCamMode::ThirdPersonBack | PlayerCamMode::ThirdPersonFront
+		)
+		.then_some(Self)
+	}
+}
+
+#[derive(Component)]
+#[require(PlayerCam, Transform, Camera3d)]
+struct ThirdPersonCam;
+
+fn spawn(mut commands: Commands, global_config: Res<Config>) {
+	// the position is set by move_cam
+	commands.spawn((
+		ThirdPersonCam,
+		Projection::Perspective(PerspectiveProjection {
+			fov: global_config.fov,
+			..default()
+		}),
+	));
+}
+
+fn despawn(mut commands: Commands, cam: Query<Entity, With<ThirdPersonCam>>) {
+	let entity = cam.single();
+	commands.entity(entity).despawn_recursive();
+}
+
+/// orbits the camera around the player's eyes.<br>
+/// if there is terrain in the way, the camera is pulled in front of it
+fn move_cam(
+	mut cam: Query<&mut Transform, (With<ThirdPersonCam>, Without<Player>)>,
+	player: Query<(&Transform, &LookDirection, &StepOffset, &Crouching), With<Player>>,
+	cam_mode: Res<State<PlayerCamMode>>,
+	game_world: Res<GameWorld>,
+	global_config: Res<Config>,
+) {
+	let mut cam_trans = cam.single_mut();
+	let (player_trans, look_dir, step_offset, &crouching) = player.single();
+	let eye_pos = player_trans.translation + Vec3::Y * (eye_height(crouching) - step_offset.0);
+	let (offset_dir, rotation) = match cam_mode.get() {
+		PlayerCamMode::ThirdPersonFront => (
+			look_dir.dir(),
+			look_dir.to_quat() * Quat::from_rotation_y(PI),
+		),
+		_ => (-look_dir.dir(), look_dir.to_quat()),
+	};
+
+	let max_distance = global_config.third_person_distance.max(0.);
+	let ray = FiniteRay::new(eye_pos, offset_dir, max_distance);
+	let distance = match send_out_ray(ray, &game_world) {
+		Some(hit) => (hit.pos.distance(eye_pos) - WALL_DISTANCE).max(0.),
+		None => max_distance,
+	};
+
+	cam_trans.translation = eye_pos + offset_dir * distance;
+	cam_trans.rotation = rotation;
+}
+
+fn input_rotation(
+	rotate_input: Res<RotateInput>,
+	mut player: Query<&mut LookDirection, With<Player>>,
+) {
+	let mut look_dir = player.single_mut();
+	*look_dir = rotate_input.rotate_look_dir(*look_dir);
+}
@@ -0,0 +1,84 @@
+use super::interact_block::MiningProgress;
+use crate::{input::InputSet, GlobalState};
+use bevy::prelude::*;
+
+pub struct CrackOverlayPlugin;
+
+impl Plugin for CrackOverlayPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(OnEnter(GlobalState::InWorld), spawn)
+			.add_systems(OnExit(GlobalState::InWorld), despawn)
+			.add_systems(
+				Update,
+				update_crack_overlay
+					.after(InputSet::Use)
+					.run_if(in_state(GlobalState::InWorld)),
+			);
+	}
+}
+
+/// how many different textures there are for the cracks
+const CRACK_STAGES: usize = 10;
+/// how much larger the overlay is than a block, so that it is drawn on top of it
+const OVERLAY_SCALE: f32 = 1.002;
+
+/// a cube around the block that is being broken, that shows how far it is broken
+#[derive(Component)]
+#[require(Transform, Visibility)]
+struct CrackOverlay {
+	/// a material for every stage of breaking
+	stages: Vec<Handle<StandardMaterial>>,
+}
+
+fn spawn(
+	mut commands: Commands,
+	asset_server: Res<AssetServer>,
+	mut meshes: ResMut<Assets<Mesh>>,
+	mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+	let stages = (0..CRACK_STAGES)
+		.map(|stage| {
+			materials.add(StandardMaterial {
+				base_color_texture: Some(asset_server.load(format!("sprite/Crack{}.png", stage))),
+				alpha_mode: AlphaMode::Blend,
+				unlit: true,
+				..default()
+			})
+		})
+		.collect::<Vec<_>>();
+	commands.spawn((
+		Mesh3d(meshes.add(Cuboid::from_length(OVERLAY_SCALE))),
+		MeshMaterial3d(stages[0].clone()),
+		CrackOverlay { stages },
+		Visibility::Hidden,
+		Name::new("CrackOverlay"),
+	));
+}
+
+fn despawn(mut commands: Commands, overlays: Query<Entity, With<CrackOverlay>>) {
+	for overlay in &overlays {
+		commands.entity(overlay).despawn();
+	}
+}
+
+fn update_crack_overlay(
+	mining: Res<MiningProgress>,
+	mut overlay: Query<(
+		&CrackOverlay,
+		&mut Transform,
+		&mut Visibility,
+		&mut MeshMaterial3d<StandardMaterial>,
+	)>,
+) {
+	let Ok((overlay, mut trans, mut visibility, mut material)) = overlay.get_single_mut() else {
+		return;
+	};
+	let Some(target) = mining.target.filter(|_| mining.progress > 0.0) else {
+		*visibility = Visibility::Hidden;
+		return;
+	};
+	*visibility = Visibility::Inherited;
+	trans.translation = target.to_world_pos() + Vec3::splat(0.5);
+	let stage = ((mining.progress * CRACK_STAGES as f32) as usize).min(CRACK_STAGES - 1);
+	material.0 = overlay.stages[stage].clone();
+}
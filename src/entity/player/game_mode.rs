@@ -0,0 +1,155 @@
+use super::Player;
+use crate::{
+	console::{AddCommand, Args, Command, Param, ParamKind},
+	game_world::GameWorld,
+	GlobalState,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{error::Error, fmt};
+
+pub struct GameModePlugin;
+
+impl Plugin for GameModePlugin {
+	fn build(&self, app: &mut App) {
+		app.add_event::<SetGameModeEvent>()
+			.register_type::<GameMode>()
+			.add_command(GameModeCommand)
+			.add_systems(
+				Update,
+				(cycle_game_mode, set_game_mode)
+					.chain()
+					.run_if(in_state(GlobalState::InWorld)),
+			);
+	}
+}
+
+/// decides which abilities a player has
+#[derive(
+	Component, Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect,
+)]
+pub enum GameMode {
+	/// blocks take time to break and are used up when placing them
+	#[default]
+	Survival,
+	/// the player can fly, breaks blocks instantly and never runs out of blocks
+	Creative,
+}
+
+/// changes the game mode of the player
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SetGameModeEvent(pub GameMode);
+
+impl GameMode {
+	pub const ALL: [Self; 2] = [Self::Survival, Self::Creative];
+
+	pub fn can_fly(self) -> bool {
+		self == Self::Creative
+	}
+
+	/// whether placing a block does not take it out of the inventory
+	pub fn has_infinite_blocks(self) -> bool {
+		self == Self::Creative
+	}
+
+	/// whether every block breaks instantly, regardless of its hardness
+	pub fn breaks_instantly(self) -> bool {
+		self == Self::Creative
+	}
+
+	/// whether the player can be damaged and die
+	pub fn takes_damage(self) -> bool {
+		self != Self::Creative
+	}
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Survival => "Survival",
+			Self::Creative => "Creative",
+		}
+	}
+
+	/// the inverse of [`GameMode::name`], ignoring upper and lower case
+	pub fn from_name(name: &str) -> Option<Self> {
+		Self::ALL
+			.into_iter()
+			.find(|mode| mode.name().eq_ignore_ascii_case(name))
+	}
+
+	/// gets the game mode that comes after this one in [`GameMode::ALL`]
+	pub fn next(self) -> Self {
+		let index = Self::ALL.iter().position(|&m| m == self).unwrap_or(0);
+		Self::ALL[(index + 1) % Self::ALL.len()]
+	}
+}
+
+impl fmt::Display for GameMode {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.name())
+	}
+}
+
+fn cycle_game_mode(
+	input: Res<ButtonInput<KeyCode>>,
+	player: Query<&GameMode, With<Player>>,
+	mut events: EventWriter<SetGameModeEvent>,
+) {
+	if !input.just_pressed(KeyCode::F4) {
+		return;
+	}
+	if let Ok(game_mode) = player.get_single() {
+		events.send(SetGameModeEvent(game_mode.next()));
+	}
+}
+
+/// changes the game mode of the player and stores it in the [`GameWorld`], so that it is saved
+fn set_game_mode(
+	mut events: EventReader<SetGameModeEvent>,
+	mut player: Query<&mut GameMode, With<Player>>,
+	mut game_world: ResMut<GameWorld>,
+) {
+	for &SetGameModeEvent(new_game_mode) in events.read() {
+		// the server decides which game mode its players have
+		if game_world.is_remote {
+			warn!("only the server can change the game mode");
+			continue;
+		}
+		let Ok(mut game_mode) = player.get_single_mut() else {
+			continue;
+		};
+		*game_mode = new_game_mode;
+		game_world.player_game_mode = new_game_mode;
+		info!("changed game mode to {}", new_game_mode);
+	}
+}
+
+struct GameModeCommand;
+
+impl Command for GameModeCommand {
+	fn name(&self) -> &'static str {
+		"gamemode"
+	}
+
+	fn description(&self) -> &'static str {
+		"shows or changes the game mode of the player"
+	}
+
+	fn signatures(&self) -> &'static [&'static [Param]] {
+		const SIGNATURES: &[&[Param]] = &[&[], &[Param::new("mode", ParamKind::GameMode)]];
+		SIGNATURES
+	}
+
+	fn run(&self, args: &Args, world: &mut World) -> Result<String, Box<dyn Error>> {
+		if args.is_empty() {
+			let mut player = world.query_filtered::<&GameMode, With<Player>>();
+			let game_mode = player.get_single(world).map_err(|_| "there is no player")?;
+			return Ok(format!("the game mode is {}", game_mode));
+		}
+		if world.resource::<GameWorld>().is_remote {
+			return Err("only the server can change the game mode".into());
+		}
+		let game_mode = args.game_mode(0);
+		world.send_event(SetGameModeEvent(game_mode));
+		Ok(format!("changed the game mode to {}", game_mode))
+	}
+}
@@ -0,0 +1,48 @@
+use super::Player;
+use crate::{
+	game_world::GameWorld,
+	input::{HotbarInput, InputSet},
+	item::Inventory,
+	GlobalState,
+};
+use bevy::prelude::*;
+
+pub struct HotbarPlugin;
+
+impl Plugin for HotbarPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			Update,
+			(
+				select_hotbar_slot.in_set(InputSet::Use),
+				save_inventory_to_world.after(InputSet::Use),
+			)
+				.run_if(in_state(GlobalState::InWorld)),
+		);
+	}
+}
+
+fn select_hotbar_slot(
+	hotbar_input: Res<HotbarInput>,
+	mut inventory: Query<&mut Inventory, With<Player>>,
+) {
+	let Ok(mut inventory) = inventory.get_single_mut() else {
+		return;
+	};
+	if let Some(slot) = hotbar_input.slot() {
+		inventory.select_slot(slot);
+	}
+	if hotbar_input.offset() != 0 {
+		inventory.scroll(hotbar_input.offset());
+	}
+}
+
+/// copies the inventory of the player into the [`GameWorld`], so that it is saved with it
+fn save_inventory_to_world(
+	inventory: Query<&Inventory, (With<Player>, Changed<Inventory>)>,
+	mut game_world: ResMut<GameWorld>,
+) {
+	if let Ok(inventory) = inventory.get_single() {
+		game_world.player_inventory = inventory.clone();
+	}
+}
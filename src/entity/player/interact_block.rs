@@ -1,140 +1,241 @@
-use super::{Player, EYE_HEIGHT};
+use super::{eye_height, world_edit::WorldEditMode, GameMode, IsAlive, Player};
 use crate::{
 	block::prelude::*,
+	crafting_screen::CraftingScreen,
 	entity::{
-		collision::ray::{send_out_ray, FiniteRay},
+		collision::{
+			ray::{send_out_ray, FiniteRay},
+			SolidEntities,
+		},
+		item_entity::spawn_item_entity,
+		movement::Crouching,
 		LookDirection,
 	},
-	game_world::{chunk::ChunkUpdateEvent, GameWorld},
+	game_world::GameWorld,
 	input::{AttackInput, InputSet, InteractInput},
+	item::Inventory,
 	pos::BlockPos,
 	GlobalState,
 };
 use bevy::prelude::*;
 
+/// how far away from their eyes the player can break and place blocks
+pub const REACH: f32 = 10.0;
+
 pub struct InteractBlockPlugin;
 
 impl Plugin for InteractBlockPlugin {
 	fn build(&self, app: &mut App) {
-		app.insert_resource(CurrentBlock::default()).add_systems(
+		app.insert_resource(MiningProgress::default()).add_systems(
 			Update,
-			(
-				(break_block, place_block).in_set(InputSet::Use),
-				select_current_block,
-			)
-				.run_if(in_state(GlobalState::InWorld)),
+			(break_block, place_block)
+				.in_set(InputSet::Use)
+				.run_if(in_state(GlobalState::InWorld))
+				.run_if(in_state(IsAlive(true)))
+				.run_if(in_state(CraftingScreen::Closed))
+				.run_if(in_state(WorldEditMode(false))),
 		);
 	}
 }
 
+/// how far the player is in breaking the block they are looking at
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct MiningProgress {
+	/// the block that is being broken
+	pub target: Option<BlockPos>,
+	/// goes from `0.0` to `1.0`, where the block breaks at `1.0`
+	pub progress: f32,
+}
+
 fn break_block(
+	mut commands: Commands,
 	input: Res<AttackInput>,
-	player: Query<(&Transform, &LookDirection), With<Player>>,
+	player: Query<
+		(
+			&Transform,
+			&LookDirection,
+			&Crouching,
+			&Inventory,
+			&GameMode,
+		),
+		With<Player>,
+	>,
 	mut game_world: ResMut<GameWorld>,
-	mut chunk_updates: EventWriter<ChunkUpdateEvent>,
+	mut mining: ResMut<MiningProgress>,
+	time: Res<Time>,
 	// mut debug_res: ResMut<DebugRes>,
 ) {
-	if !input.started {
+	if !input.holding {
+		*mining = MiningProgress::default();
 		return;
 	}
-	let (player_trans, player_look_dir) = player.single();
+	let (player_trans, player_look_dir, &crouching, inventory, game_mode) = player.single();
 	let player_look_quat = player_look_dir.to_quat();
-	let eye_pos = player_trans.translation + Vec3::Y * EYE_HEIGHT;
+	let eye_pos = player_trans.translation + Vec3::Y * eye_height(crouching);
 	let dir = player_look_quat.mul_vec3(Vec3::new(0.0, 0.0, -1.0));
-	let ray = FiniteRay::new(eye_pos, dir, 10.0);
+	let ray = FiniteRay::new(eye_pos, dir, REACH);
 
-	if let Some(hit) = send_out_ray(ray, &game_world) {
-		if let Some(block) = game_world.get_block_at_mut(hit.block_pos) {
-			*block = Air::BLOCK;
-			send_block_update(hit.block_pos, &mut chunk_updates);
+	let Some(hit) = send_out_ray(ray, &game_world) else {
+		*mining = MiningProgress::default();
+		return;
+	};
+	let Some(&block) = game_world.get_block_at(hit.block_pos) else {
+		*mining = MiningProgress::default();
+		return;
+	};
+	if mining.target != Some(hit.block_pos) {
+		*mining = MiningProgress {
+			target: Some(hit.block_pos),
+			progress: 0.0,
+		};
+	}
+
+	let hardness = block.get_hardness();
+	let speed = inventory
+		.selected_stack()
+		.map_or(1.0, |stack| stack.item.mining_speed(block));
+	if hardness <= 0.0 || game_mode.breaks_instantly() {
+		// otherwise holding the button would break every block in the way immediately
+		if !input.started {
+			return;
 		}
+		mining.progress = 1.0;
+	} else {
+		mining.progress += time.delta_secs() * speed / hardness;
+	}
+	if mining.progress < 1.0 {
+		return;
+	}
+
+	let drops = block.get_drops(&mut rand::thread_rng());
+	game_world.set_block(hit.block_pos, Air::BLOCK);
+	game_world.finish_edit();
+	let center = hit.block_pos.to_world_pos() + Vec3::splat(0.5);
+	for stack in drops {
+		spawn_item_entity(&mut commands, center, stack);
 	}
+	*mining = MiningProgress::default();
 
 	// let positions = get_all_block_pos_in_ray(ray);
 	// debug_res.spawn_temp_cubes(&positions, 6.0);
 	// debug_res.spawn_temp_ray(ray, 10.0);
 }
 
-#[derive(Resource)]
-struct CurrentBlock {
-	block: Block,
-}
-
-impl Default for CurrentBlock {
-	fn default() -> Self {
-		Self {
-			block: Stone::BLOCK,
-		}
-	}
-}
-
 fn place_block(
 	input: Res<InteractInput>,
-	player: Query<(&Transform, &LookDirection), With<Player>>,
+	mut player: Query<
+		(
+			&Transform,
+			&LookDirection,
+			&Crouching,
+			&mut Inventory,
+			&GameMode,
+		),
+		With<Player>,
+	>,
 	mut game_world: ResMut<GameWorld>,
-	mut chunk_updates: EventWriter<ChunkUpdateEvent>,
-	current_block: Res<CurrentBlock>,
+	mut crafting_screen: ResMut<NextState<CraftingScreen>>,
+	solids: SolidEntities,
 ) {
 	if !input.started {
 		return;
 	}
-	let (player_trans, player_look_dir) = player.single();
+	let (player_trans, player_look_dir, &crouching, mut inventory, game_mode) = player.single_mut();
 	let player_look_quat = player_look_dir.to_quat();
-	let eye_pos = player_trans.translation + Vec3::Y * EYE_HEIGHT;
+	let eye_pos = player_trans.translation + Vec3::Y * eye_height(crouching);
 	let dir = player_look_quat.mul_vec3(Vec3::new(0.0, 0.0, -1.0));
-	let ray = FiniteRay::new(eye_pos, dir, 10.0);
+	let ray = FiniteRay::new(eye_pos, dir, REACH);
 
 	if let Some(hit) = send_out_ray(ray, &game_world) {
+		if game_world.get_block_at(hit.block_pos) == Some(&CraftingTable::BLOCK) {
+			crafting_screen.set(CraftingScreen::CraftingTable);
+			return;
+		}
+		let Some(new_block) = inventory.selected_stack().and_then(|s| s.item.to_block()) else {
+			return;
+		};
 		let block_pos = hit.block_pos + hit.face.normal();
 
-		if let Some(block) = game_world.get_block_at_mut(block_pos) {
+		if let Some(block) = game_world.get_block_at(block_pos) {
 			if !block.is_replacable() {
 				return;
 			}
-			*block = current_block.block;
-			send_block_update(hit.block_pos, &mut chunk_updates);
+			// blocks can't be placed inside of entities, including the player
+			if solids.is_in_the_way(new_block, block_pos, None) {
+				return;
+			}
+			game_world.set_block(block_pos, new_block);
+			game_world.finish_edit();
+			if !game_mode.has_infinite_blocks() {
+				inventory.take_selected();
+			}
 		}
 	}
 }
 
-fn select_current_block(input: Res<ButtonInput<KeyCode>>, mut current_block: ResMut<CurrentBlock>) {
-	macro_rules! blocks {
-		($key0:ident, $block0:ident; $($key:ident, $block:ident);* $(;)?) => {
-			if input.just_pressed(KeyCode::$key0) {
-				current_block.block = $block0::BLOCK;
-			}
-			$(
-				else if input.just_pressed(KeyCode::$key) {
-					current_block.block = $block::BLOCK;
-				}
-			)*
-		};
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		entity::{
+			collision::{Solid, SpatialHash},
+			player::collider,
+		},
+		pos::ChunkPos,
+	};
+	use bevy::ecs::system::RunSystemOnce;
+	use std::f32::consts::FRAC_PI_2;
+
+	/// a player that stands on a stone floor and looks straight down at it
+	fn world_with_player() -> World {
+		let mut world = World::new();
+		let mut game_world =
+			GameWorld::with_air_chunks([ChunkPos::new(0, -1, 0), ChunkPos::new(0, 0, 0)]);
+		game_world.replace_block(BlockPos::new(0, -1, 0), Stone::BLOCK);
+		world.insert_resource(game_world);
+		world.init_resource::<SpatialHash>();
+		world.init_resource::<NextState<CraftingScreen>>();
+		world.insert_resource(InteractInput {
+			holding: true,
+			started: true,
+		});
+		world.spawn((
+			Player,
+			Solid,
+			collider(Crouching(false)),
+			Transform::from_xyz(0.5, 0., 0.5),
+			LookDirection {
+				pitch: -FRAC_PI_2,
+				yaw: 0.,
+			},
+			Crouching(false),
+			Inventory::with_starting_items(),
+			GameMode::Survival,
+		));
+		world
 	}
-	blocks![
-		Digit1, Stone;
-		Digit2, GrassBlock;
-		Digit3, Dirt;
-		Digit4, Cobblestone;
-		Digit5, DebugBlock;
-		Digit6, DebugSlab;
-		Digit7, Log;
-		Digit8, Planks;
-		Digit9, Leaves;
-	];
-}
 
-fn send_block_update(block_pos: BlockPos, chunk_updates: &mut EventWriter<ChunkUpdateEvent>) {
-	let chunk_pos = block_pos.to_chunk_pos();
-	chunk_updates.send(ChunkUpdateEvent { chunk_pos });
+	#[test]
+	fn blocks_cant_be_placed_inside_of_the_player() {
+		let mut world = world_with_player();
+		SpatialHash::update(&mut world);
+		world.run_system_once(place_block).unwrap();
+		let game_world = world.resource::<GameWorld>();
+		assert_eq!(
+			game_world.get_block_at(BlockPos::new(0, 0, 0)),
+			Some(&Air::BLOCK)
+		);
+	}
 
-	// update neighbouring chunks
-	for neighbour_pos in block_pos.neighbours() {
-		let neighbour_chunk_pos = neighbour_pos.to_chunk_pos();
-		if neighbour_chunk_pos == chunk_pos {
-			continue;
-		}
-		chunk_updates.send(ChunkUpdateEvent {
-			chunk_pos: neighbour_chunk_pos,
-		});
+	#[test]
+	fn blocks_are_placed_where_the_player_looks() {
+		// the player isn't in the spatial hash yet, so it isn't in the way
+		let mut world = world_with_player();
+		world.run_system_once(place_block).unwrap();
+		let game_world = world.resource::<GameWorld>();
+		assert_eq!(
+			game_world.get_block_at(BlockPos::new(0, 0, 0)),
+			Some(&Stone::BLOCK)
+		);
 	}
 }
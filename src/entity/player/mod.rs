@@ -1,28 +1,50 @@
+mod avatar;
 mod cam;
+mod crack_overlay;
+mod game_mode;
+mod hotbar;
 mod interact_block;
 mod movement;
 mod player_model;
+mod respawn;
+mod world_edit;
 
 use super::{
-	collision::collider::BoxCollider,
-	movement::{Gravity, OnGround, Velocity},
+	collision::{collider::BoxCollider, in_fluid::InFluid, Solid, StepOffset},
+	health::Health,
+	movement::{Crouching, Gravity, OnGround, Velocity},
 	LookDirection,
 };
 use crate::{
-	game_world::{get_height_at_with_seed, GameWorld},
+	game_world::{ChunkLoader, GameWorld},
+	item::Inventory,
 	GlobalState,
 };
 use bevy::prelude::*;
 
+pub use self::{
+	avatar::{Avatar, Nametag},
+	cam::CanRotateCam,
+	game_mode::GameMode,
+	interact_block::REACH,
+	respawn::{IsAlive, RespawnEvent},
+};
+
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
 	fn build(&self, app: &mut App) {
 		app.add_plugins((
+			avatar::AvatarPlugin,
 			cam::CamPlugin,
+			crack_overlay::CrackOverlayPlugin,
+			game_mode::GameModePlugin,
+			hotbar::HotbarPlugin,
 			interact_block::InteractBlockPlugin,
 			movement::MovementPlugin,
 			player_model::PlayerModelPlugin,
+			respawn::RespawnPlugin,
+			world_edit::WorldEditPlugin,
 		))
 		.add_systems(OnEnter(GlobalState::InWorld), spawn)
 		.add_systems(OnExit(GlobalState::InWorld), despawn);
@@ -32,6 +54,11 @@ impl Plugin for PlayerPlugin {
 const WIDTH: f32 = 0.8;
 const HEIGHT: f32 = 1.85;
 const EYE_HEIGHT: f32 = 1.65;
+const CROUCH_HEIGHT: f32 = 1.5;
+const CROUCH_EYE_HEIGHT: f32 = 1.3;
+/// high enough to walk onto slabs, but not onto full blocks
+const STEP_HEIGHT: f32 = 0.6;
+const MAX_HEALTH: f32 = 20.0;
 
 /// The entity representing the player you control.
 /// This is not responsible for visuals or other players when playing online.
@@ -46,8 +73,16 @@ struct PlayerBundle {
 	gravity: Gravity,
 	look_direction: LookDirection,
 	collider: BoxCollider,
+	solid: Solid,
 	on_ground: OnGround,
+	crouching: Crouching,
+	step_offset: StepOffset,
+	in_fluid: InFluid,
+	inventory: Inventory,
+	game_mode: GameMode,
+	health: Health,
 	name: Name,
+	chunk_loader: ChunkLoader,
 }
 
 impl Default for PlayerBundle {
@@ -58,17 +93,41 @@ impl Default for PlayerBundle {
 			velocity: Velocity::default(),
 			gravity: Gravity::vertical(movement::GRAVITY),
 			look_direction: LookDirection::default(),
-			collider: BoxCollider::new(WIDTH, HEIGHT),
+			collider: collider(Crouching(false)),
+			solid: Solid,
 			on_ground: OnGround::default(),
+			crouching: Crouching::default(),
+			step_offset: StepOffset::default(),
+			in_fluid: InFluid::default(),
+			inventory: Inventory::default(),
+			game_mode: GameMode::default(),
+			health: Health::new(MAX_HEALTH),
 			name: Name::new("Player"),
+			chunk_loader: ChunkLoader,
 		}
 	}
 }
 
+/// the collider of the player, which is shorter while crouching
+pub fn collider(crouching: Crouching) -> BoxCollider {
+	let height = if crouching.0 { CROUCH_HEIGHT } else { HEIGHT };
+	BoxCollider::new(WIDTH, height).with_step_height(STEP_HEIGHT)
+}
+
+/// how high the eyes of the player are above its feet
+fn eye_height(crouching: Crouching) -> f32 {
+	if crouching.0 {
+		CROUCH_EYE_HEIGHT
+	} else {
+		EYE_HEIGHT
+	}
+}
+
 fn spawn(mut commands: Commands, game_world: Res<GameWorld>) {
-	let height = get_height_at_with_seed(16, 16, game_world.seed) as f32 + 4.;
 	commands.spawn(PlayerBundle {
-		transform: Transform::from_xyz(16.5, height, 16.5),
+		transform: Transform::from_translation(game_world.spawn_point()),
+		inventory: game_world.player_inventory.clone(),
+		game_mode: game_world.player_game_mode,
 		..default()
 	});
 }
@@ -1,9 +1,11 @@
-use super::{cam::PlayerCamMode, Player};
+use super::{cam::PlayerCamMode, GameMode, IsAlive, Player};
 use crate::{
 	entity::{
-		movement::{Gravity, MovementSet, OnGround, Velocity},
+		collision::{collider::BoxCollider, collides_with_blocks, in_fluid::InFluid},
+		movement::{Crouching, Gravity, MovementSet, OnGround, Velocity},
 		LookDirection,
 	},
+	game_world::GameWorld,
 	input::{CrouchInput, InputSet, JumpInput, ScrollInput, WalkInput},
 	GlobalState,
 };
@@ -14,27 +16,48 @@ pub struct MovementPlugin;
 impl Plugin for MovementPlugin {
 	fn build(&self, app: &mut App) {
 		app.init_resource::<MovementValues>()
+			.init_resource::<TickInput>()
 			.register_type::<MovementValues>()
 			.add_sub_state::<IsFlying>()
 			.add_systems(
 				Update,
 				(
+					store_tick_input,
 					(
-						jump.run_if(in_state(IsFlying(false))),
-						fly_vertical.run_if(in_state(IsFlying(true))),
-						walk,
 						toggle_flying,
+						stop_flying_without_permission,
 						change_speed_mult,
 					)
-						.run_if(not(in_state(PlayerCamMode::FreeCam))),
+						.run_if(not(in_state(PlayerCamMode::FreeCam)))
+						.run_if(in_state(IsAlive(true))),
+				)
+					.run_if(in_state(GlobalState::InWorld))
+					.in_set(InputSet::Use),
+			)
+			.add_systems(
+				FixedUpdate,
+				(
+					(
+						(jump, swim_vertical).run_if(in_state(IsFlying(false))),
+						fly_vertical.run_if(in_state(IsFlying(true))),
+						crouch,
+						walk,
+					)
+						.run_if(not(in_state(PlayerCamMode::FreeCam)))
+						.run_if(in_state(IsAlive(true))),
 					friction,
 					update_gravity,
 				)
 					// chain ensures that walk and friction are always done in the same order
 					.chain()
 					.run_if(in_state(GlobalState::InWorld))
-					.in_set(MovementSet::Accel)
-					.in_set(InputSet::Use),
+					.in_set(MovementSet::Accel),
+			)
+			.add_systems(
+				FixedUpdate,
+				consume_tick_input
+					.in_set(MovementSet::CleanUp)
+					.run_if(in_state(GlobalState::InWorld)),
 			);
 	}
 }
@@ -54,14 +77,25 @@ struct MovementValues {
 	move_friction: f32,
 	/// the acceleration in the air in m/s²
 	move_air_accel: f32,
+	/// multiplies the walking acceleration while crouching
+	crouch_mult: f32,
 	/// 1 / the amount of seconds it takes to half the velocity due to friction in the air
 	move_drag: f32,
 	/// the upward velocity when jumping
 	jump_strength: f32,
-	/// the speed at which you move vertically when flying
+	/// the speed at which you move vertically when flying in m/s
 	vertical_fly_speed: f32,
+	/// the acceleration when moving through a fluid in m/s²
+	move_swim_accel: f32,
+	/// 1 / the amount of seconds it takes to half the velocity due to friction in a fluid
+	move_fluid_drag: f32,
+	/// the upward acceleration when swimming up or down in m/s²
+	vertical_swim_accel: f32,
 	/// the amount of downwards velocity added per frame
 	gravity: f32,
+	/// the amount of upwards velocity added per frame when completely submerged in a fluid,
+	/// which replaces gravity depending on how deep the player is in the fluid
+	buoyancy: f32,
 	/// multiplies all movement speed (except gravity)
 	mult: f32,
 }
@@ -72,27 +106,75 @@ impl Default for MovementValues {
 			move_ground_accel: 80.0,
 			move_friction: 16.0,
 			move_air_accel: 40.0,
+			crouch_mult: 0.3,
 			move_drag: 8.0,
 			jump_strength: 7.0,
-			vertical_fly_speed: 200.0,
+			vertical_fly_speed: 3.5,
+			move_swim_accel: 30.0,
+			move_fluid_drag: 4.0,
+			vertical_swim_accel: 30.0,
 			gravity: GRAVITY,
+			buoyancy: 2.0,
 			mult: 1.0,
 		}
 	}
 }
 
-fn walk(
+/// the input that moves the player, kept for the next tick.<br>
+/// the input resources are reset every frame, but there can be frames without ticks
+/// or with several of them
+#[derive(Resource, Debug, Default)]
+struct TickInput {
+	walk: WalkInput,
+	jump: JumpInput,
+	crouch: CrouchInput,
+}
+
+/// a jump that was started is kept until a tick used it, so that short presses aren't lost
+fn store_tick_input(
 	walk_input: Res<WalkInput>,
-	mut player: Query<(&mut Velocity, &LookDirection, &OnGround), With<Player>>,
+	jump_input: Res<JumpInput>,
+	crouch_input: Res<CrouchInput>,
+	mut tick_input: ResMut<TickInput>,
+) {
+	tick_input.walk = *walk_input;
+	tick_input.jump.holding = jump_input.holding;
+	tick_input.jump.started |= jump_input.started;
+	tick_input.crouch = *crouch_input;
+}
+
+fn consume_tick_input(mut tick_input: ResMut<TickInput>) {
+	tick_input.jump.started = false;
+	tick_input.crouch.started = false;
+}
+
+fn walk(
+	input: Res<TickInput>,
+	mut player: Query<
+		(
+			&mut Velocity,
+			&LookDirection,
+			&OnGround,
+			&InFluid,
+			&Crouching,
+		),
+		With<Player>,
+	>,
 	time: Res<Time>,
 	values: Res<MovementValues>,
 ) {
 	let dt = time.delta_secs();
-	let (mut player_vel, look_dir, on_ground) = player.single_mut();
-	let vec = walk_input.with_look_dir(*look_dir);
+	let (mut player_vel, look_dir, on_ground, in_fluid, crouching) = player.single_mut();
+	let mut vec = input.walk.with_look_dir(*look_dir);
+	if crouching.0 {
+		vec *= values.crouch_mult;
+	}
 
 	let prev_y = player_vel.vel.y;
-	if on_ground.0 {
+	if let Some(kind) = in_fluid.kind {
+		let accel = values.move_swim_accel / kind.viscosity();
+		player_vel.vel += vec * accel * values.mult * dt;
+	} else if on_ground.0 {
 		player_vel.vel += vec * values.move_ground_accel * values.mult * dt;
 	} else {
 		player_vel.vel += vec * values.move_air_accel * values.mult * dt;
@@ -101,12 +183,18 @@ fn walk(
 }
 
 fn friction(
-	mut player: Query<(&mut Velocity, &OnGround), With<Player>>,
+	mut player: Query<(&mut Velocity, &OnGround, &InFluid), With<Player>>,
 	time: Res<Time>,
 	values: Res<MovementValues>,
 ) {
 	let dt = time.delta_secs();
-	let (mut player_vel, on_ground) = player.single_mut();
+	let (mut player_vel, on_ground, in_fluid) = player.single_mut();
+
+	if let Some(kind) = in_fluid.kind {
+		// fluids slow you down vertically as well
+		player_vel.vel *= (-values.move_fluid_drag * kind.viscosity() * dt).exp2();
+		return;
+	}
 
 	let prev_y = player_vel.vel.y;
 	if on_ground.0 {
@@ -118,11 +206,11 @@ fn friction(
 }
 
 fn jump(
-	input: Res<JumpInput>,
+	input: Res<TickInput>,
 	mut player: Query<(&mut Velocity, &OnGround), With<Player>>,
 	values: Res<MovementValues>,
 ) {
-	if input.started {
+	if input.jump.started {
 		let (mut player_vel, on_ground) = player.single_mut();
 		if on_ground.0 {
 			player_vel.vel.y = values.jump_strength * values.mult.sqrt();
@@ -130,51 +218,110 @@ fn jump(
 	}
 }
 
+/// crouches while the crouch input is held, unless it is used to fly or swim down.<br>
+/// the player only stands up again if there is enough room above it
+fn crouch(
+	input: Res<TickInput>,
+	flying: Res<State<IsFlying>>,
+	mut player: Query<(&Transform, &InFluid, &mut Crouching, &mut BoxCollider), With<Player>>,
+	game_world: Res<GameWorld>,
+) {
+	let (trans, in_fluid, mut crouching, mut collider) = player.single_mut();
+	let wants_to_crouch = Crouching(input.crouch.holding && !flying.0 && in_fluid.kind.is_none());
+	if *crouching == wants_to_crouch {
+		return;
+	}
+	let new_collider = super::collider(wants_to_crouch);
+	if !wants_to_crouch.0
+		&& collides_with_blocks(new_collider.into_cuboid() + trans.translation, &game_world)
+	{
+		return;
+	}
+	*crouching = wants_to_crouch;
+	*collider = new_collider;
+}
+
 fn toggle_flying(
 	flying: Res<State<IsFlying>>,
 	mut next_flying: ResMut<NextState<IsFlying>>,
 	input: Res<ButtonInput<KeyCode>>,
-	mut player_grav: Query<&mut Gravity, With<Player>>,
-	values: Res<MovementValues>,
+	player: Query<&GameMode, With<Player>>,
 ) {
+	if !player.single().can_fly() {
+		return;
+	}
 	if input.just_pressed(KeyCode::KeyC) {
-		let new = !flying.0;
-		next_flying.set(IsFlying(new));
+		next_flying.set(IsFlying(!flying.0));
+	}
+}
 
-		let mut gravity = player_grav.single_mut();
-		*gravity = if new {
-			Gravity::ZERO
-		} else {
-			Gravity::vertical(values.gravity)
-		};
+/// makes the player fall down when they are flying in a game mode that does not allow it
+fn stop_flying_without_permission(
+	flying: Res<State<IsFlying>>,
+	mut next_flying: ResMut<NextState<IsFlying>>,
+	player: Query<&GameMode, (With<Player>, Changed<GameMode>)>,
+) {
+	let Ok(game_mode) = player.get_single() else {
+		return;
+	};
+	if flying.0 && !game_mode.can_fly() {
+		next_flying.set(IsFlying(false));
 	}
 }
 
-fn fly_vertical(
-	jump_input: Res<JumpInput>,
-	crouch_input: Res<CrouchInput>,
-	mut player: Query<&mut Velocity, With<Player>>,
+fn swim_vertical(
+	input: Res<TickInput>,
+	mut player: Query<(&mut Velocity, &InFluid), With<Player>>,
 	time: Res<Time>,
 	values: Res<MovementValues>,
 ) {
 	let dt = time.delta_secs();
+	let (mut player_vel, in_fluid) = player.single_mut();
+	let Some(kind) = in_fluid.kind else {
+		return;
+	};
+
+	let accel = values.vertical_swim_accel / kind.viscosity() * values.mult * dt;
+	if input.jump.holding {
+		player_vel.vel.y += accel;
+	}
+	if input.crouch.holding {
+		player_vel.vel.y -= accel;
+	}
+}
+
+fn fly_vertical(
+	input: Res<TickInput>,
+	mut player: Query<&mut Velocity, With<Player>>,
+	values: Res<MovementValues>,
+) {
 	let mut player_vel = player.single_mut();
 
 	let mut y_vel = 0.;
-	if crouch_input.holding {
-		y_vel = -values.vertical_fly_speed * values.mult * dt;
+	if input.crouch.holding {
+		y_vel = -values.vertical_fly_speed * values.mult;
 	}
-	if jump_input.holding {
-		y_vel = values.vertical_fly_speed * values.mult * dt;
+	if input.jump.holding {
+		y_vel = values.vertical_fly_speed * values.mult;
 	}
 	player_vel.vel.y = y_vel;
 }
 
-fn update_gravity(values: Res<MovementValues>, mut player: Query<&mut Gravity, With<Player>>) {
-	if values.is_changed() {
-		let mut gravity = player.single_mut();
-		*gravity = Gravity::vertical(values.gravity);
-	}
+fn update_gravity(
+	values: Res<MovementValues>,
+	flying: Res<State<IsFlying>>,
+	mut player: Query<(&mut Gravity, &InFluid), With<Player>>,
+) {
+	let (mut gravity, in_fluid) = player.single_mut();
+	*gravity = if flying.0 {
+		Gravity::ZERO
+	} else if in_fluid.kind.is_some() {
+		// buoyancy makes the player float up to the surface
+		let strength = values.gravity.lerp(values.buoyancy, in_fluid.depth);
+		Gravity::vertical(strength)
+	} else {
+		Gravity::vertical(values.gravity)
+	};
 }
 
 fn change_speed_mult(
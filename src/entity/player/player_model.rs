@@ -1,4 +1,4 @@
-use super::{cam::PlayerCamMode, Player};
+use super::{avatar::Avatar, cam::CamOutsidePlayer, Player};
 use crate::{entity::LookDirection, GlobalState};
 use bevy::prelude::*;
 
@@ -6,54 +6,46 @@ pub struct PlayerModelPlugin;
 
 impl Plugin for PlayerModelPlugin {
 	fn build(&self, app: &mut App) {
-		app.add_systems(OnEnter(PlayerCamMode::FreeCam), spawn)
-			.add_systems(OnExit(PlayerCamMode::FreeCam), despawn)
+		app.add_systems(OnEnter(CamOutsidePlayer), spawn)
+			.add_systems(OnExit(CamOutsidePlayer), despawn)
 			.add_systems(
 				Update,
 				(update_player_pos, draw_facing_arrow)
 					.run_if(in_state(GlobalState::InWorld))
-					.run_if(in_state(PlayerCamMode::FreeCam)),
+					.run_if(in_state(CamOutsidePlayer)),
 			);
 	}
 }
 
+/// the [`Avatar`] of the player you control, which follows it around
 #[derive(Component)]
-#[require(Transform)]
+#[require(Avatar)]
 struct PlayerModel;
 
-fn spawn(
-	mut commands: Commands,
-	player: Query<&Transform, With<Player>>,
-	mut materials: ResMut<Assets<StandardMaterial>>,
-	mut meshes: ResMut<Assets<Mesh>>,
-) {
-	let player_trans = player.single();
+fn spawn(mut commands: Commands, player: Query<(&Transform, &LookDirection), With<Player>>) {
+	let (player_trans, &look_dir) = player.single();
 	commands.spawn((
 		PlayerModel,
-		Mesh3d(meshes.add(Mesh::from(Cuboid {
-			half_size: Vec3::new(super::WIDTH, super::HEIGHT, super::WIDTH) / 2.,
-		}))),
-		MeshMaterial3d(materials.add(StandardMaterial {
-			unlit: true,
-			..default()
-		})),
-		Transform::from_translation(player_trans.translation + Vec3::Y * super::HEIGHT / 2.),
+		Transform::from_translation(player_trans.translation),
+		look_dir,
 	));
 }
 
 fn despawn(mut commands: Commands, model: Query<Entity, With<PlayerModel>>) {
 	for e in model.iter() {
-		commands.entity(e).despawn();
+		commands.entity(e).despawn_recursive();
 	}
 }
 
+#[allow(clippy::type_complexity)]
 fn update_player_pos(
-	player: Query<&Transform, (With<Player>, Without<PlayerModel>)>,
-	mut model: Query<&mut Transform, (With<PlayerModel>, Without<Player>)>,
+	player: Query<(&Transform, &LookDirection), (With<Player>, Without<PlayerModel>)>,
+	mut model: Query<(&mut Transform, &mut LookDirection), With<PlayerModel>>,
 ) {
-	let mut model = model.single_mut();
-	let player = player.single();
-	model.translation = player.translation + Vec3::Y * super::HEIGHT / 2.;
+	let (mut model_trans, mut model_look_dir) = model.single_mut();
+	let (player_trans, &look_dir) = player.single();
+	model_trans.translation = player_trans.translation;
+	*model_look_dir = look_dir;
 }
 
 fn draw_facing_arrow(
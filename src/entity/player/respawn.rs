@@ -0,0 +1,75 @@
+use super::{cam::CanRotateCam, Player};
+use crate::{
+	entity::{
+		health::{DeathEvent, Health},
+		movement::Velocity,
+	},
+	game_world::GameWorld,
+	GlobalState,
+};
+use bevy::prelude::*;
+
+pub struct RespawnPlugin;
+
+impl Plugin for RespawnPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_sub_state::<IsAlive>()
+			.add_event::<RespawnEvent>()
+			.add_systems(
+				Update,
+				(die, respawn).run_if(in_state(GlobalState::InWorld)),
+			);
+	}
+}
+
+/// whether the player is alive. while the player is dead, they can't move or interact.
+#[derive(SubStates, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[source(GlobalState = GlobalState::InWorld)]
+pub struct IsAlive(pub bool);
+
+impl Default for IsAlive {
+	fn default() -> Self {
+		Self(true)
+	}
+}
+
+/// brings the player back to life at the spawn point of the world
+#[derive(Event, Debug, Clone, Copy)]
+pub struct RespawnEvent;
+
+fn die(
+	mut death_events: EventReader<DeathEvent>,
+	player: Query<Entity, With<Player>>,
+	mut is_alive: ResMut<NextState<IsAlive>>,
+	mut can_rotate: ResMut<NextState<CanRotateCam>>,
+) {
+	let Ok(player) = player.get_single() else {
+		return;
+	};
+	for event in death_events.read() {
+		if event.entity == player {
+			is_alive.set(IsAlive(false));
+			// show the cursor, so that the respawn button can be clicked
+			can_rotate.set(CanRotateCam(false));
+		}
+	}
+}
+
+fn respawn(
+	mut respawn_events: EventReader<RespawnEvent>,
+	mut player: Query<(&mut Transform, &mut Velocity, &mut Health), With<Player>>,
+	game_world: Res<GameWorld>,
+	mut is_alive: ResMut<NextState<IsAlive>>,
+	mut can_rotate: ResMut<NextState<CanRotateCam>>,
+) {
+	for _ in respawn_events.read() {
+		let Ok((mut trans, mut vel, mut health)) = player.get_single_mut() else {
+			continue;
+		};
+		trans.translation = game_world.spawn_point();
+		*vel = Velocity::default();
+		health.heal_fully();
+		is_alive.set(IsAlive(true));
+		can_rotate.set(CanRotateCam(true));
+	}
+}
@@ -0,0 +1,104 @@
+//! copied blocks, that can be pasted somewhere else with a different rotation
+
+use crate::{
+	block::Block,
+	game_world::GameWorld,
+	pos::{BlockPos, BlockRegion},
+	savedata::Schematic,
+};
+use bevy::prelude::*;
+use std::error::Error;
+
+/// blocks that were copied with `/region copy`
+#[derive(Resource, Debug, Clone)]
+pub struct Clipboard {
+	/// where the smallest corner of the blocks was, relative to the player when copying
+	offset: IVec3,
+	size: IVec3,
+	/// the blocks in the order of [`BlockRegion::iter`]
+	blocks: Vec<Block>,
+}
+
+/// how the blocks in the [`Clipboard`] are turned when pasting them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PasteTransform {
+	/// how many times the blocks are turned clockwise when looking from above
+	pub quarter_turns: u8,
+	/// which axis is flipped before turning the blocks
+	pub mirror: Option<Mirror>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mirror {
+	X,
+	Z,
+}
+
+impl Clipboard {
+	/// copies all blocks in the region. fails if part of the region is not loaded
+	pub fn copy(
+		game_world: &GameWorld,
+		region: BlockRegion,
+		origin: BlockPos,
+	) -> Result<Self, Box<dyn Error>> {
+		let blocks = region
+			.iter()
+			.map(|pos| game_world.get_block_at(pos).copied())
+			.collect::<Option<Vec<_>>>()
+			.ok_or("part of the region is not loaded")?;
+		Ok(Self {
+			offset: IVec3::from(region.min()) - IVec3::from(origin),
+			size: region.size().as_ivec3(),
+			blocks,
+		})
+	}
+
+	pub fn from_schematic(schematic: &Schematic) -> Result<Self, Box<dyn Error>> {
+		Ok(Self {
+			offset: schematic.offset(),
+			size: schematic.size(),
+			blocks: schematic.to_blocks()?,
+		})
+	}
+
+	pub fn to_schematic(&self) -> Result<Schematic, Box<dyn Error>> {
+		Schematic::from_blocks(self.size, self.offset, self.blocks.iter().copied())
+	}
+
+	/// how many blocks were copied
+	pub fn len(&self) -> usize {
+		self.blocks.len()
+	}
+
+	/// where every block ends up when pasting relative to `origin`
+	pub fn paste(
+		&self,
+		origin: BlockPos,
+		transform: PasteTransform,
+	) -> impl Iterator<Item = (BlockPos, Block)> + '_ {
+		let local_region = BlockRegion::from_corners(BlockPos::default(), (self.size - 1).into());
+		local_region
+			.iter()
+			.zip(&self.blocks)
+			.map(move |(local_pos, &block)| {
+				let relative = self.offset + IVec3::from(local_pos);
+				(origin + transform.apply(relative), block)
+			})
+	}
+}
+
+impl PasteTransform {
+	/// mirrors and then turns a position around the origin
+	pub fn apply(self, pos: IVec3) -> IVec3 {
+		let mut pos = match self.mirror {
+			Some(Mirror::X) => pos * IVec3::new(-1, 1, 1),
+			Some(Mirror::Z) => pos * IVec3::new(1, 1, -1),
+			None => pos,
+		};
+		for _ in 0..self.quarter_turns % 4 {
+			// -z (north) becomes +x (east)
+			pos = IVec3::new(-pos.z, pos.y, pos.x);
+		}
+		pos
+	}
+}
@@ -0,0 +1,382 @@
+//! tools for editing big parts of the world at once.<br>
+//! in world edit mode, attacking and interacting select the corners of a region
+//! instead of breaking and placing blocks. the region is then changed with `/region`.
+
+mod clipboard;
+
+use self::clipboard::{Clipboard, Mirror, PasteTransform};
+use super::{eye_height, IsAlive, Player};
+use crate::{
+	block::prelude::*,
+	console::{AddCommand, Args, Command, ConsoleLog, Param, ParamKind},
+	crafting_screen::CraftingScreen,
+	entity::{
+		collision::ray::{send_out_ray, FiniteRay},
+		movement::Crouching,
+		LookDirection,
+	},
+	game_world::GameWorld,
+	input::{AttackInput, InputSet, InteractInput},
+	pos::{BlockPos, BlockRegion, Vec3Utils},
+	savedata, GlobalState,
+};
+use bevy::{color::palettes::basic::AQUA, prelude::*};
+use std::error::Error;
+
+pub struct WorldEditPlugin;
+
+impl Plugin for WorldEditPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_sub_state::<WorldEditMode>()
+			.init_resource::<Selection>()
+			.add_command(EditCommand)
+			.add_command(RegionCommand)
+			.add_command(SchematicCommand)
+			.add_systems(OnExit(GlobalState::InWorld), cleanup)
+			.add_systems(
+				Update,
+				(
+					select_corners
+						.in_set(InputSet::Use)
+						.run_if(in_state(IsAlive(true)))
+						.run_if(in_state(CraftingScreen::Closed)),
+					draw_selection,
+				)
+					.run_if(in_state(WorldEditMode(true))),
+			);
+	}
+}
+
+/// the most blocks that can be changed with a single command
+const MAX_EDIT_VOLUME: u64 = 64 * 64 * 64;
+
+/// whether attacking and interacting select corners, instead of breaking and placing blocks
+#[derive(SubStates, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[source(GlobalState = GlobalState::InWorld)]
+pub struct WorldEditMode(pub bool);
+
+/// the two corners of the region that is edited
+#[derive(Resource, Debug, Default, Clone, Copy)]
+struct Selection {
+	first: Option<BlockPos>,
+	second: Option<BlockPos>,
+}
+
+impl Selection {
+	fn region(self) -> Option<BlockRegion> {
+		Some(BlockRegion::from_corners(self.first?, self.second?))
+	}
+}
+
+fn cleanup(mut commands: Commands, mut selection: ResMut<Selection>) {
+	*selection = Selection::default();
+	commands.remove_resource::<Clipboard>();
+}
+
+fn select_corners(
+	attack_input: Res<AttackInput>,
+	interact_input: Res<InteractInput>,
+	player: Query<(&Transform, &LookDirection, &Crouching), With<Player>>,
+	game_world: Res<GameWorld>,
+	mut selection: ResMut<Selection>,
+	mut log: ResMut<ConsoleLog>,
+	time: Res<Time>,
+) {
+	if !attack_input.started && !interact_input.started {
+		return;
+	}
+	let Ok((trans, look_dir, &crouching)) = player.get_single() else {
+		return;
+	};
+	let eye_pos = trans.translation + Vec3::Y * eye_height(crouching);
+	let dir = look_dir.to_quat().mul_vec3(Vec3::NEG_Z);
+	let Some(hit) = send_out_ray(FiniteRay::new(eye_pos, dir, 100.0), &game_world) else {
+		return;
+	};
+
+	let (corner, name) = if attack_input.started {
+		(&mut selection.first, "first")
+	} else {
+		(&mut selection.second, "second")
+	};
+	*corner = Some(hit.block_pos);
+	let pos = IVec3::from(hit.block_pos);
+	log.print(
+		&format!("selected the {name} corner at {pos}"),
+		false,
+		&time,
+	);
+}
+
+fn draw_selection(selection: Res<Selection>, mut gizmos: Gizmos) {
+	let corners = selection.first.or(selection.second);
+	let Some(region) = selection
+		.region()
+		.or(corners.map(|corner| BlockRegion::from_corners(corner, corner)))
+	else {
+		return;
+	};
+	let (min, size) = (region.min(), region.size());
+	let size = size.as_vec3();
+	// slightly bigger, so that the lines are not hidden inside of the blocks
+	let transform = Transform::from_translation(min.to_world_pos() + size / 2.0)
+		.with_scale(size + Vec3::splat(0.02));
+	gizmos.cuboid(transform, AQUA);
+}
+
+/// the position of the player's feet, which copying and pasting is relative to
+fn player_block_pos(world: &mut World) -> Result<BlockPos, Box<dyn Error>> {
+	let mut player = world.query_filtered::<&Transform, With<Player>>();
+	let trans = player.get_single(world).map_err(|_| "there is no player")?;
+	Ok(trans.translation.to_block_pos())
+}
+
+/// the axis the player is looking along the most, as a unit vector
+fn player_facing(world: &mut World) -> Result<IVec3, Box<dyn Error>> {
+	let mut player = world.query_filtered::<&LookDirection, With<Player>>();
+	let look_dir = player.get_single(world).map_err(|_| "there is no player")?;
+	let dir = look_dir.to_quat().mul_vec3(Vec3::NEG_Z);
+	let abs = dir.abs();
+	let axis = if abs.x >= abs.y && abs.x >= abs.z {
+		IVec3::X * dir.x.signum() as i32
+	} else if abs.y >= abs.z {
+		IVec3::Y * dir.y.signum() as i32
+	} else {
+		IVec3::Z * dir.z.signum() as i32
+	};
+	Ok(axis)
+}
+
+fn selected_region(world: &World) -> Result<BlockRegion, Box<dyn Error>> {
+	let region = world
+		.get_resource::<Selection>()
+		.and_then(|selection| selection.region())
+		.ok_or("select two corners first")?;
+	if region.volume() > MAX_EDIT_VOLUME {
+		return Err(format!(
+			"{} blocks are too many, the limit is {MAX_EDIT_VOLUME}",
+			region.volume()
+		)
+		.into());
+	}
+	Ok(region)
+}
+
+struct EditCommand;
+
+impl Command for EditCommand {
+	fn name(&self) -> &'static str {
+		"edit"
+	}
+
+	fn description(&self) -> &'static str {
+		"turns world edit mode on or off"
+	}
+
+	fn signatures(&self) -> &'static [&'static [Param]] {
+		const SIGNATURES: &[&[Param]] = &[&[]];
+		SIGNATURES
+	}
+
+	fn run(&self, _args: &Args, world: &mut World) -> Result<String, Box<dyn Error>> {
+		let enabled = !world.resource::<State<WorldEditMode>>().0;
+		world
+			.resource_mut::<NextState<WorldEditMode>>()
+			.set(WorldEditMode(enabled));
+		if enabled {
+			Ok(
+				"world edit mode: attack to select the first corner, interact for the second"
+					.to_owned(),
+			)
+		} else {
+			Ok("left world edit mode".to_owned())
+		}
+	}
+}
+
+struct RegionCommand;
+
+impl Command for RegionCommand {
+	fn name(&self) -> &'static str {
+		"region"
+	}
+
+	fn description(&self) -> &'static str {
+		"changes the region selected in world edit mode"
+	}
+
+	fn signatures(&self) -> &'static [&'static [Param]] {
+		const ROTATION: Param =
+			Param::new("rotation", ParamKind::OneOf(&["0", "90", "180", "270"]));
+		const MIRROR: Param = Param::new("mirror", ParamKind::OneOf(&["none", "x", "z"]));
+		const SIGNATURES: &[&[Param]] = &[
+			&[
+				Param::literal(&["fill"]),
+				Param::new("block", ParamKind::Block),
+			],
+			&[
+				Param::literal(&["replace"]),
+				Param::new("from", ParamKind::Block),
+				Param::new("to", ParamKind::Block),
+			],
+			&[
+				Param::literal(&["hollow"]),
+				Param::new("block", ParamKind::Block),
+			],
+			&[Param::literal(&["copy"])],
+			&[Param::literal(&["paste"])],
+			&[Param::literal(&["paste"]), ROTATION],
+			&[Param::literal(&["paste"]), ROTATION, MIRROR],
+			&[
+				Param::literal(&["stack"]),
+				Param::new("count", ParamKind::Int),
+			],
+			&[Param::literal(&["deselect"])],
+		];
+		SIGNATURES
+	}
+
+	fn run(&self, args: &Args, world: &mut World) -> Result<String, Box<dyn Error>> {
+		match args.word(0) {
+			"fill" => {
+				let block = Block::from_id(args.block(1));
+				let region = selected_region(world)?;
+				let count = world
+					.resource_mut::<GameWorld>()
+					.set_blocks(region.iter().map(|pos| (pos, block)));
+				Ok(format!("filled {count} blocks"))
+			}
+			"replace" => {
+				let (from, to) = (args.block(1), Block::from_id(args.block(2)));
+				let region = selected_region(world)?;
+				let game_world = world.resource::<GameWorld>();
+				let changes = region
+					.iter()
+					.filter(|&pos| game_world.get_block_at(pos).map(|block| block.id) == Some(from))
+					.map(|pos| (pos, to))
+					.collect::<Vec<_>>();
+				let count = world.resource_mut::<GameWorld>().set_blocks(changes);
+				Ok(format!("replaced {count} blocks"))
+			}
+			"hollow" => {
+				let block = Block::from_id(args.block(1));
+				let region = selected_region(world)?;
+				let mut game_world = world.resource_mut::<GameWorld>();
+				// only the shell is counted, not the air that is set inside of it
+				let mut count = 0;
+				for pos in region.iter() {
+					if region.is_on_edge(pos) {
+						count += game_world.set_block(pos, block).is_some() as usize;
+					} else {
+						game_world.set_block(pos, Air::BLOCK);
+					}
+				}
+				game_world.finish_edit();
+				Ok(format!("built a hollow box out of {count} blocks"))
+			}
+			"copy" => {
+				let region = selected_region(world)?;
+				let origin = player_block_pos(world)?;
+				let clipboard = Clipboard::copy(world.resource::<GameWorld>(), region, origin)?;
+				let count = clipboard.len();
+				world.insert_resource(clipboard);
+				Ok(format!("copied {count} blocks"))
+			}
+			"paste" => {
+				let transform = PasteTransform {
+					quarter_turns: match args.get_word(1) {
+						Some("90") => 1,
+						Some("180") => 2,
+						Some("270") => 3,
+						_ => 0,
+					},
+					mirror: match args.get_word(2) {
+						Some("x") => Some(Mirror::X),
+						Some("z") => Some(Mirror::Z),
+						_ => None,
+					},
+				};
+				let origin = player_block_pos(world)?;
+				let clipboard = world
+					.get_resource::<Clipboard>()
+					.ok_or("the clipboard is empty, use /region copy first")?;
+				let changes = clipboard.paste(origin, transform).collect::<Vec<_>>();
+				let count = world.resource_mut::<GameWorld>().set_blocks(changes);
+				Ok(format!("pasted {count} blocks"))
+			}
+			"stack" => {
+				let count = u32::try_from(args.int(1))
+					.ok()
+					.filter(|&count| count > 0)
+					.ok_or("the count has to be positive")?;
+				let region = selected_region(world)?;
+				if region.volume() * count as u64 > MAX_EDIT_VOLUME {
+					return Err(format!(
+						"stacking more than {MAX_EDIT_VOLUME} blocks is not allowed"
+					)
+					.into());
+				}
+				let dir = player_facing(world)?;
+				let step = dir * region.size().as_ivec3();
+				let game_world = world.resource::<GameWorld>();
+				let changes = (1..=count as i32)
+					.flat_map(|i| {
+						region.iter().filter_map(move |pos| {
+							let block = *game_world.get_block_at(pos)?;
+							Some((pos + step * i, block))
+						})
+					})
+					.collect::<Vec<_>>();
+				let placed = world.resource_mut::<GameWorld>().set_blocks(changes);
+				Ok(format!("stacked {count} times, placing {placed} blocks"))
+			}
+			_ => {
+				world.insert_resource(Selection::default());
+				Ok("cleared the selection".to_owned())
+			}
+		}
+	}
+}
+
+struct SchematicCommand;
+
+impl Command for SchematicCommand {
+	fn name(&self) -> &'static str {
+		"schematic"
+	}
+
+	fn description(&self) -> &'static str {
+		"saves the clipboard to a file, or loads a file into the clipboard"
+	}
+
+	fn signatures(&self) -> &'static [&'static [Param]] {
+		const SIGNATURES: &[&[Param]] = &[
+			&[
+				Param::literal(&["export"]),
+				Param::new("name", ParamKind::Text),
+			],
+			&[
+				Param::literal(&["import"]),
+				Param::new("name", ParamKind::Text),
+			],
+		];
+		SIGNATURES
+	}
+
+	fn run(&self, args: &Args, world: &mut World) -> Result<String, Box<dyn Error>> {
+		let name = args.text(1);
+		if args.word(0) == "export" {
+			let clipboard = world
+				.get_resource::<Clipboard>()
+				.ok_or("the clipboard is empty, use /region copy first")?;
+			savedata::export_schematic(name, &clipboard.to_schematic()?)?;
+			return Ok(format!("exported the clipboard as {name}"));
+		}
+		let clipboard = Clipboard::from_schematic(&savedata::import_schematic(name)?)?;
+		let count = clipboard.len();
+		world.insert_resource(clipboard);
+		Ok(format!(
+			"imported {count} blocks from {name}, use /region paste to place them"
+		))
+	}
+}
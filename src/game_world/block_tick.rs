@@ -0,0 +1,95 @@
+//! blocks that change over time, like flowing fluids, are updated by scheduling a tick for them
+
+use super::{chunk::send_block_update, chunk::ChunkUpdateEvent, fluid, is_local_world, GameWorld};
+use crate::{pos::BlockPos, GlobalState};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub struct BlockTickPlugin;
+
+impl Plugin for BlockTickPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			Update,
+			tick_blocks
+				.run_if(in_state(GlobalState::InWorld))
+				.run_if(is_local_world),
+		);
+	}
+}
+
+/// how many block ticks happen in one second
+pub const TICKS_PER_SECOND: f32 = 20.0;
+
+/// the most ticks that are allowed to happen in a single frame,
+/// so that lag doesn't cause even more lag
+const MAX_TICKS_PER_FRAME: u32 = 10;
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ScheduledTicks {
+	/// how many block ticks have happened in this world
+	current_tick: u64,
+	/// a map from the tick at which blocks should be updated to their positions
+	queue: BTreeMap<u64, Vec<BlockPos>>,
+}
+
+impl ScheduledTicks {
+	/// makes the block at the given position get updated after `delay` ticks
+	pub fn schedule(&mut self, pos: BlockPos, delay: u64) {
+		let tick = self.current_tick + delay.max(1);
+		let positions = self.queue.entry(tick).or_default();
+		if !positions.contains(&pos) {
+			positions.push(pos);
+		}
+	}
+
+	/// advances by a single tick and gets all positions that should be updated on that tick
+	fn next_tick(&mut self) -> Vec<BlockPos> {
+		self.current_tick += 1;
+		self.queue.remove(&self.current_tick).unwrap_or_default()
+	}
+}
+
+impl GameWorld {
+	/// schedules a tick for the block at the given position and all blocks touching it,
+	/// if they react to changes around them.<br>
+	/// this should be called whenever a block is changed.
+	pub fn notify_neighbours(&mut self, pos: BlockPos) {
+		for pos in std::iter::once(pos).chain(pos.neighbours()) {
+			let Some(block) = self.get_block_at(pos) else {
+				continue;
+			};
+			if let Some((kind, _)) = block.get_fluid() {
+				self.scheduled_ticks.schedule(pos, kind.tick_delay());
+			}
+		}
+	}
+}
+
+fn tick_blocks(
+	mut game_world: ResMut<GameWorld>,
+	mut chunk_updates: EventWriter<ChunkUpdateEvent>,
+	mut time_since_tick: Local<f32>,
+	time: Res<Time>,
+) {
+	*time_since_tick += time.delta_secs();
+	let mut ticks = 0;
+	while *time_since_tick >= 1.0 / TICKS_PER_SECOND {
+		*time_since_tick -= 1.0 / TICKS_PER_SECOND;
+		ticks += 1;
+		if ticks > MAX_TICKS_PER_FRAME {
+			*time_since_tick = 0.0;
+			break;
+		}
+
+		for pos in game_world.scheduled_ticks.next_tick() {
+			let changed = fluid::tick_fluid(&mut game_world, pos);
+			for pos in changed {
+				send_block_update(pos, &mut chunk_updates);
+				game_world.notify_neighbours(pos);
+				game_world.changed_blocks.push(pos);
+			}
+		}
+	}
+}
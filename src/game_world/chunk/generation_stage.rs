@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
 
 /// describes how much of the chunk has **already** been generated.<br>
-/// for example: has the basic shape of the terrain been generated? or the trees?
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// for example: has the basic shape of the terrain been generated? or the trees?<br>
+/// the stages are ordered, so a later stage always compares greater than an earlier one
+#[derive(
+	Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
 pub enum GenerationStage {
 	/// nothing of the chunk has been generated.
 	/// it is literally empty.
@@ -11,10 +14,22 @@ pub enum GenerationStage {
 	/// the basic shape of it has been generated.
 	/// for example: the dirt and stone blocks, including the cave shapes.
 	Terrain,
+	/// structures like villages and dungeons have been placed in.
+	Structures,
 	/// the trees have been placed in.
 	Trees,
 }
 
 impl GenerationStage {
 	pub const COMPLETE: Self = Self::Trees;
+
+	/// the stage that comes directly after this one, or `None` if this is [`GenerationStage::COMPLETE`]
+	pub fn next(self) -> Option<Self> {
+		match self {
+			Self::Nothing => Some(Self::Terrain),
+			Self::Terrain => Some(Self::Structures),
+			Self::Structures => Some(Self::Trees),
+			Self::Trees => None,
+		}
+	}
 }
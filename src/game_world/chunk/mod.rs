@@ -3,7 +3,7 @@ mod generation_stage;
 mod is_loaded;
 mod render;
 
-use crate::pos::ChunkPos;
+use crate::pos::{BlockPos, ChunkPos};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -11,14 +11,14 @@ pub use self::{
 	block_array::{BlockArray, CHUNK_LENGTH},
 	generation_stage::GenerationStage,
 	is_loaded::IsLoaded,
+	render::{create_block_mesh, has_loaded_global_material, GlobalChunkMaterial, RenderPlugin},
 };
 
 pub struct ChunkPlugin;
 
 impl Plugin for ChunkPlugin {
 	fn build(&self, app: &mut App) {
-		app.add_plugins(render::RenderPlugin)
-			.add_event::<ChunkUpdateEvent>();
+		app.add_event::<ChunkUpdateEvent>();
 	}
 }
 
@@ -27,6 +27,25 @@ pub struct ChunkUpdateEvent {
 	pub chunk_pos: ChunkPos,
 }
 
+/// sends a [`ChunkUpdateEvent`] for the chunk the block is in,
+/// and for the neighbouring chunks if the block is at the edge
+pub fn send_block_update(block_pos: BlockPos, chunk_updates: &mut EventWriter<ChunkUpdateEvent>) {
+	for chunk_pos in chunks_affected_by_block(block_pos) {
+		chunk_updates.send(ChunkUpdateEvent { chunk_pos });
+	}
+}
+
+/// the chunk the block is in, and the neighbouring chunks if the block is at the edge.<br>
+/// these chunks need to be redrawn when the block changes.
+pub fn chunks_affected_by_block(block_pos: BlockPos) -> impl Iterator<Item = ChunkPos> {
+	let chunk_pos = block_pos.to_chunk_pos();
+	let neighbours = block_pos
+		.neighbours()
+		.map(BlockPos::to_chunk_pos)
+		.filter(move |&neighbour| neighbour != chunk_pos);
+	std::iter::once(chunk_pos).chain(neighbours)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Chunk {
 	pub blocks: Box<BlockArray>,
@@ -0,0 +1,170 @@
+//! creates the translucent meshes for fluids, which have sloped surfaces
+//! depending on how far the fluid is away from its source
+
+use crate::{
+	block::{fluid::FluidKind, Block, BlockId},
+	block_model::BlockModel,
+	face::{Face, FaceMap},
+	game_world::chunk::{Chunk, CHUNK_LENGTH},
+	pos::BlockInChunkPos,
+};
+use bevy::{
+	prelude::*,
+	render::{mesh::Indices, render_asset::RenderAssetUsages, render_resource::PrimitiveTopology},
+};
+use std::collections::HashMap;
+
+/// creates a single mesh for all fluids in the chunk,
+/// or `None` if there are no fluids in the chunk
+pub fn create_fluid_mesh(
+	chunk: &Chunk,
+	neighbour_chunks: &FaceMap<Chunk>,
+	block_models: &HashMap<BlockId, BlockModel<usize>>,
+) -> Option<Mesh> {
+	let mut positions: Vec<[f32; 3]> = Vec::new();
+	let mut normals: Vec<[f32; 3]> = Vec::new();
+	let mut colors: Vec<[f32; 4]> = Vec::new();
+	let mut indices: Vec<u32> = Vec::new();
+
+	for (pos, block) in chunk.blocks.iter_xyz() {
+		let Some((kind, _)) = block.get_fluid() else {
+			continue;
+		};
+		let pos = IVec3::from(pos);
+		let get_block = |offset: IVec3| get_block(chunk, neighbour_chunks, pos + offset);
+		let is_same_fluid = |offset: IVec3| is_fluid_of_kind(get_block(offset), kind);
+		let is_covered = |offset: IVec3| {
+			get_block(offset).is_some_and(|block| {
+				block_models
+					.get(&block.id)
+					.is_some_and(|model| model.should_cull)
+			})
+		};
+
+		let heights = if is_same_fluid(IVec3::Y) {
+			[[1.0; 2]; 2]
+		} else {
+			corner_heights(chunk, neighbour_chunks, pos, kind)
+		};
+		let h = |x: usize, z: usize| heights[x][z];
+
+		let mut add_face = |face: Face, corners: [[f32; 3]; 4]| {
+			let i = positions.len() as u32;
+			let offset = pos.as_vec3();
+			positions.extend(corners.map(|c| (Vec3::from(c) + offset).to_array()));
+			normals.extend([face.normal().as_vec3().to_array(); 4]);
+			colors.extend([kind.color().to_linear().to_f32_array(); 4]);
+			indices.extend([i, i + 1, i + 3, i + 2, i + 3, i + 1]);
+		};
+
+		for face in Face::all() {
+			let normal = face.normal();
+			if is_same_fluid(normal) {
+				continue;
+			}
+			// the top surface is visible even if something is above it, since it is sloped
+			if face != Face::Up && is_covered(normal) {
+				continue;
+			}
+			#[rustfmt::skip]
+			let corners = match face {
+				Face::Right => [[1., h(1, 1), 1.], [1., 0., 1.], [1., 0., 0.], [1., h(1, 0), 0.]],
+				Face::Left => [[0., h(0, 0), 0.], [0., 0., 0.], [0., 0., 1.], [0., h(0, 1), 1.]],
+				Face::Up => [[0., h(0, 0), 0.], [0., h(0, 1), 1.], [1., h(1, 1), 1.], [1., h(1, 0), 0.]],
+				Face::Down => [[0., 0., 1.], [0., 0., 0.], [1., 0., 0.], [1., 0., 1.]],
+				Face::Back => [[0., h(0, 1), 1.], [0., 0., 1.], [1., 0., 1.], [1., h(1, 1), 1.]],
+				Face::Forward => [[1., h(1, 0), 0.], [1., 0., 0.], [0., 0., 0.], [0., h(0, 0), 0.]],
+			};
+			add_face(face, corners);
+		}
+	}
+
+	if indices.is_empty() {
+		return None;
+	}
+
+	let mut mesh = Mesh::new(
+		PrimitiveTopology::TriangleList,
+		RenderAssetUsages::default(),
+	);
+	mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+	mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+	mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+	mesh.insert_indices(Indices::U32(indices));
+	Some(mesh)
+}
+
+/// gets the height of the fluid surface at each corner of the block,
+/// by averaging the heights of the 4 blocks that share that corner.<br>
+/// indexed by `[x][z]`
+fn corner_heights(
+	chunk: &Chunk,
+	neighbour_chunks: &FaceMap<Chunk>,
+	pos: IVec3,
+	kind: FluidKind,
+) -> [[f32; 2]; 2] {
+	let own_height = fluid_height(chunk, neighbour_chunks, pos, kind).unwrap_or(1.0);
+	let mut heights = [[own_height; 2]; 2];
+	for (x, row) in heights.iter_mut().enumerate() {
+		for (z, height) in row.iter_mut().enumerate() {
+			let sharing_corner = [[-1, -1], [-1, 0], [0, -1], [0, 0]]
+				.map(|[dx, dz]| pos + IVec3::new(x as i32 + dx, 0, z as i32 + dz))
+				.into_iter()
+				.filter_map(|pos| fluid_height(chunk, neighbour_chunks, pos, kind))
+				.collect::<Vec<_>>();
+			if sharing_corner.iter().any(|&h| h >= 1.0) {
+				// a fluid with more fluid above it always reaches the top
+				*height = 1.0;
+			} else if !sharing_corner.is_empty() {
+				*height = sharing_corner.iter().sum::<f32>() / sharing_corner.len() as f32;
+			}
+		}
+	}
+	heights
+}
+
+/// the height of the fluid at the given position, which is 1 if there is more of the same fluid above
+fn fluid_height(
+	chunk: &Chunk,
+	neighbour_chunks: &FaceMap<Chunk>,
+	pos: IVec3,
+	kind: FluidKind,
+) -> Option<f32> {
+	let block = get_block(chunk, neighbour_chunks, pos)?;
+	let (block_kind, state) = block.get_fluid()?;
+	if block_kind != kind {
+		return None;
+	}
+	let above = get_block(chunk, neighbour_chunks, pos + IVec3::Y);
+	if is_fluid_of_kind(above, kind) {
+		return Some(1.0);
+	}
+	Some(state.height())
+}
+
+fn is_fluid_of_kind(block: Option<Block>, kind: FluidKind) -> bool {
+	block
+		.and_then(Block::get_fluid)
+		.is_some_and(|(block_kind, _)| block_kind == kind)
+}
+
+/// gets the block at a position relative to the chunk, which may be in a neighbouring chunk.<br>
+/// returns `None` if the position is outside of the chunk and its direct neighbours
+fn get_block(chunk: &Chunk, neighbour_chunks: &FaceMap<Chunk>, pos: IVec3) -> Option<Block> {
+	let len = CHUNK_LENGTH as i32;
+	let outside = Face::all()
+		.filter(|face| {
+			let normal = face.normal();
+			let along_axis = (pos * normal.abs()).element_sum();
+			let direction = normal.element_sum();
+			(direction > 0 && along_axis >= len) || (direction < 0 && along_axis < 0)
+		})
+		.collect::<Vec<_>>();
+	let wrapped = pos.rem_euclid(IVec3::splat(len));
+	let wrapped = BlockInChunkPos::try_from(wrapped).ok()?;
+	match outside.as_slice() {
+		[] => Some(chunk.blocks[wrapped]),
+		[face] => Some(neighbour_chunks.get(*face).blocks[wrapped]),
+		_ => None,
+	}
+}
@@ -64,6 +64,18 @@ pub fn create_chunk_mesh(
 	combine_meshes(meshes)
 }
 
+/// creates the mesh of a single block, from `(0,0,0)` to `(1,1,1)`, without culling any faces
+pub fn create_block_mesh(block_model: &BlockModel<usize>) -> Mesh {
+	let meshes = block_model.cuboids.iter().map(|cuboid| {
+		create_cube_mesh(BlockMeshInfo {
+			cuboid: cuboid.clone(),
+			culled: FaceMask::none(),
+			pos: BlockInChunkPos::default(),
+		})
+	});
+	combine_meshes(meshes)
+}
+
 fn get_culled_faces_at(
 	chunk: &Chunk,
 	neighbour_chunks: &FaceMap<Chunk>,
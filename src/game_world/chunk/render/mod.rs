@@ -1,7 +1,8 @@
 mod combine_mesh;
+mod fluid_mesh;
 mod mesh;
 
-use self::mesh::create_chunk_mesh;
+use self::{fluid_mesh::create_fluid_mesh, mesh::create_chunk_mesh};
 use super::ChunkUpdateEvent;
 use crate::{
 	block_model::{ChunkMaterial, GlobalTexture, LoadingState},
@@ -17,6 +18,9 @@ use bevy::{
 	utils::HashMap,
 };
 
+pub use self::mesh::create_block_mesh;
+
+/// draws the chunks. this is not used by the headless server, which has no window
 pub struct RenderPlugin;
 
 impl Plugin for RenderPlugin {
@@ -36,6 +40,7 @@ impl Plugin for RenderPlugin {
 					stop_chunk_redraw_tasks_on_unload,
 					queue_loading_chunks,
 					queue_updating_chunks,
+					apply_sky_light,
 				)
 					.run_if(has_loaded_global_material)
 					.run_if(in_state(GlobalState::InWorld)),
@@ -68,11 +73,20 @@ struct ChunkMeshEntities {
 }
 
 #[derive(Resource)]
-struct GlobalChunkMaterial {
-	material: Handle<ExtendedMaterial<StandardMaterial, ChunkMaterial>>,
+pub struct GlobalChunkMaterial {
+	pub material: Handle<ExtendedMaterial<StandardMaterial, ChunkMaterial>>,
+	/// a translucent material for all fluids, that uses the vertex colors of the mesh
+	fluid_material: Handle<StandardMaterial>,
+}
+
+/// the meshes that are created for a single chunk
+#[derive(Debug)]
+struct ChunkMeshes {
+	blocks: Mesh,
+	fluids: Option<Mesh>,
 }
 
-fn has_loaded_global_material(world: &World) -> bool {
+pub fn has_loaded_global_material(world: &World) -> bool {
 	world.contains_resource::<GlobalChunkMaterial>()
 }
 
@@ -80,6 +94,7 @@ fn setup_global_material(
 	mut commands: Commands,
 	global_texture: Res<GlobalTexture>,
 	mut materials: ResMut<Assets<ExtendedMaterial<StandardMaterial, ChunkMaterial>>>,
+	mut standard_materials: ResMut<Assets<StandardMaterial>>,
 ) {
 	let global_material_handle = materials.add(ExtendedMaterial {
 		base: StandardMaterial {
@@ -88,11 +103,21 @@ fn setup_global_material(
 		},
 		extension: ChunkMaterial {
 			texture: global_texture.image.clone(),
+			sky_light: 1.0,
 		},
 	});
 
+	let fluid_material_handle = standard_materials.add(StandardMaterial {
+		alpha_mode: AlphaMode::Blend,
+		unlit: true,
+		double_sided: true,
+		cull_mode: None,
+		..default()
+	});
+
 	let global_material = GlobalChunkMaterial {
 		material: global_material_handle,
+		fluid_material: fluid_material_handle,
 	};
 
 	commands.insert_resource(global_material);
@@ -100,6 +125,29 @@ fn setup_global_material(
 	info!("global material inserted");
 }
 
+/// makes the chunks darker or brighter, depending on the time of day
+fn apply_sky_light(
+	game_world: Res<GameWorld>,
+	global_material: Res<GlobalChunkMaterial>,
+	mut materials: ResMut<Assets<ExtendedMaterial<StandardMaterial, ChunkMaterial>>>,
+	mut standard_materials: ResMut<Assets<StandardMaterial>>,
+	mut last_sky_light: Local<Option<f32>>,
+) {
+	let sky_light = game_world.time.sky_light();
+	// changing the materials every frame would upload them to the gpu every frame
+	if *last_sky_light == Some(sky_light) {
+		return;
+	}
+	*last_sky_light = Some(sky_light);
+
+	if let Some(material) = materials.get_mut(&global_material.material) {
+		material.extension.sky_light = sky_light;
+	}
+	if let Some(material) = standard_materials.get_mut(&global_material.fluid_material) {
+		material.base_color = Color::linear_rgb(sky_light, sky_light, sky_light);
+	}
+}
+
 fn init(mut commands: Commands) {
 	commands.spawn((ChunkMeshParent, Name::new("ChunkMeshParent")));
 }
@@ -169,7 +217,7 @@ fn despawn_chunk_on_unload(
 			commands
 				.entity(chunk_mesh_parent)
 				.remove_children(&[entity]);
-			commands.entity(entity).despawn();
+			commands.entity(entity).despawn_recursive();
 		}
 	}
 }
@@ -190,7 +238,7 @@ fn stop_chunk_redraw_tasks_on_unload(
 
 #[derive(Resource, Debug, Default)]
 struct MeshTasks {
-	tasks: HashMap<ChunkPos, Task<Mesh>>,
+	tasks: HashMap<ChunkPos, Task<ChunkMeshes>>,
 }
 
 fn create_chunk_redraw_tasks(
@@ -230,8 +278,11 @@ fn create_chunk_redraw_tasks(
 	}
 
 	let pool = AsyncComputeTaskPool::get();
-	let task = pool
-		.spawn(async move { create_chunk_mesh(&cloned_chunk, &neighbour_chunks, &block_models) });
+	let task = pool.spawn(async move {
+		let blocks = create_chunk_mesh(&cloned_chunk, &neighbour_chunks, &block_models);
+		let fluids = create_fluid_mesh(&cloned_chunk, &neighbour_chunks, &block_models);
+		ChunkMeshes { blocks, fluids }
+	});
 	mesh_tasks.tasks.insert(chunk_pos, task);
 }
 
@@ -256,8 +307,8 @@ fn spawn_chunk_meshes_from_tasks(
 			unreachable!()
 		};
 
-		let mesh = block_on(task);
-		let cube_mesh_handle = meshes.add(mesh);
+		let chunk_meshes = block_on(task);
+		let cube_mesh_handle = meshes.add(chunk_meshes.blocks);
 
 		// PERF it would be more efficient to update the entity instead of creating a new one
 		if mesh_entites.entities.contains_key(&chunk_pos) {
@@ -265,7 +316,7 @@ fn spawn_chunk_meshes_from_tasks(
 			commands
 				.entity(chunk_mesh_parent)
 				.remove_children(&[entity]);
-			commands.entity(entity).despawn();
+			commands.entity(entity).despawn_recursive();
 		}
 
 		let entity = commands
@@ -276,6 +327,16 @@ fn spawn_chunk_meshes_from_tasks(
 				ChunkMesh,
 				Name::new(format!("Chunk Mesh at {}", chunk_pos)),
 			))
+			.with_children(|parent| {
+				if let Some(fluid_mesh) = chunk_meshes.fluids {
+					parent.spawn((
+						Mesh3d(meshes.add(fluid_mesh)),
+						MeshMaterial3d(global_material.fluid_material.clone()),
+						Transform::default(),
+						Name::new("Fluid Mesh"),
+					));
+				}
+			})
 			.id();
 		commands.entity(chunk_mesh_parent).add_child(entity);
 
@@ -0,0 +1,358 @@
+//! every change to the blocks of the world made by the player is recorded, so that it can be undone.<br>
+//! changes that belong together, like all blocks of a `/fill`, are undone at once.
+
+use super::{chunk::chunks_affected_by_block, chunk::ChunkUpdateEvent, GameWorld};
+use crate::{
+	block::Block,
+	console::{AddCommand, Args, Command, Param},
+	entity::player::{CanRotateCam, GameMode, Player},
+	input::{InputSet, RedoInput, UndoInput},
+	pos::BlockPos,
+	GlobalState,
+};
+use bevy::prelude::*;
+use std::{collections::VecDeque, error::Error, mem};
+
+pub struct EditHistoryPlugin;
+
+impl Plugin for EditHistoryPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_command(UndoCommand)
+			.add_command(RedoCommand)
+			.add_systems(
+				Update,
+				undo_and_redo
+					.in_set(InputSet::Use)
+					.run_if(in_state(GlobalState::InWorld))
+					.run_if(in_state(CanRotateCam(true))),
+			)
+			.add_systems(
+				Last,
+				send_chunk_updates.run_if(in_state(GlobalState::InWorld)),
+			);
+	}
+}
+
+/// how much memory the history may use at most. the oldest edits are forgotten first
+const MAX_HISTORY_BYTES: usize = 16 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy)]
+struct BlockChange {
+	pos: BlockPos,
+	before: Block,
+	after: Block,
+}
+
+/// changes that are undone and redone together
+type Edit = Vec<BlockChange>;
+
+/// the changes that can be undone and redone. is not saved with the world
+#[derive(Debug, Default, Clone)]
+pub struct EditHistory {
+	/// from oldest to newest
+	undo_stack: VecDeque<Edit>,
+	/// the edits that were undone, where the last one is redone first
+	redo_stack: VecDeque<Edit>,
+	/// changes that were made since the last call to [`GameWorld::finish_edit`]
+	current: Edit,
+	/// how many changes are in both stacks together
+	len: usize,
+}
+
+impl EditHistory {
+	fn push_undo(&mut self, edit: Edit) {
+		self.len += edit.len();
+		self.undo_stack.push_back(edit);
+		self.shrink_to_budget();
+	}
+
+	fn push_redo(&mut self, edit: Edit) {
+		self.len += edit.len();
+		self.redo_stack.push_back(edit);
+	}
+
+	fn pop_undo(&mut self) -> Option<Edit> {
+		let edit = self.undo_stack.pop_back()?;
+		self.len -= edit.len();
+		Some(edit)
+	}
+
+	fn pop_redo(&mut self) -> Option<Edit> {
+		let edit = self.redo_stack.pop_back()?;
+		self.len -= edit.len();
+		Some(edit)
+	}
+
+	fn clear_redo(&mut self) {
+		self.len -= self.redo_stack.iter().map(Vec::len).sum::<usize>();
+		self.redo_stack.clear();
+	}
+
+	/// forgets the oldest edits until the history fits into [`MAX_HISTORY_BYTES`]
+	fn shrink_to_budget(&mut self) {
+		while self.len * mem::size_of::<BlockChange>() > MAX_HISTORY_BYTES {
+			let Some(edit) = self
+				.undo_stack
+				.pop_front()
+				.or_else(|| self.redo_stack.pop_front())
+			else {
+				break;
+			};
+			self.len -= edit.len();
+		}
+	}
+}
+
+impl GameWorld {
+	/// changes a single block, and redraws the chunks around it at the end of the frame.<br>
+	/// the change is recorded in the history, and can be undone after calling [`GameWorld::finish_edit`].<br>
+	/// returns the block that was there before, or `None` if the chunk is not loaded.
+	pub fn set_block(&mut self, pos: BlockPos, block: Block) -> Option<Block> {
+		let before = self.replace_block(pos, block)?;
+		if before != block {
+			self.changed_blocks.push(pos);
+			self.history.current.push(BlockChange {
+				pos,
+				before,
+				after: block,
+			});
+		}
+		Some(before)
+	}
+
+	/// changes many blocks as a single edit. returns how many of them were loaded
+	pub fn set_blocks(&mut self, changes: impl IntoIterator<Item = (BlockPos, Block)>) -> usize {
+		let count = changes
+			.into_iter()
+			.filter_map(|(pos, block)| self.set_block(pos, block))
+			.count();
+		self.finish_edit();
+		count
+	}
+
+	/// ends the current edit, so that all changes since the last edit are undone together
+	pub fn finish_edit(&mut self) {
+		let edit = mem::take(&mut self.history.current);
+		if edit.is_empty() {
+			return;
+		}
+		// the undone edits might not fit to the world anymore
+		self.history.clear_redo();
+		self.history.push_undo(edit);
+	}
+
+	/// reverts the newest edit. returns how many blocks were changed
+	pub fn undo(&mut self) -> Option<usize> {
+		self.finish_edit();
+		let edit = self.history.pop_undo()?;
+		for change in edit.iter().rev() {
+			self.replace_block(change.pos, change.before);
+			self.changed_blocks.push(change.pos);
+		}
+		let count = edit.len();
+		self.history.push_redo(edit);
+		Some(count)
+	}
+
+	/// applies the edit that was undone last again. returns how many blocks were changed
+	pub fn redo(&mut self) -> Option<usize> {
+		self.finish_edit();
+		let edit = self.history.pop_redo()?;
+		for change in &edit {
+			self.replace_block(change.pos, change.after);
+			self.changed_blocks.push(change.pos);
+		}
+		let count = edit.len();
+		self.history.push_undo(edit);
+		Some(count)
+	}
+
+	/// changes a block without recording it in the history or sending it to other players,
+	/// like when the change was made by a server
+	pub fn replace_block(&mut self, pos: BlockPos, block: Block) -> Option<Block> {
+		let old_block = self.get_block_at_mut(pos)?;
+		let before = mem::replace(old_block, block);
+		if before != block {
+			if !self.is_remote {
+				self.notify_neighbours(pos);
+			}
+			self.changed_chunks.extend(chunks_affected_by_block(pos));
+		}
+		Some(before)
+	}
+}
+
+/// sends a single [`ChunkUpdateEvent`] for every chunk that was changed with [`GameWorld::set_block`].<br>
+/// this happens at the end of the frame, after the changed blocks were sent to other players.
+fn send_chunk_updates(
+	mut game_world: ResMut<GameWorld>,
+	mut chunk_updates: EventWriter<ChunkUpdateEvent>,
+) {
+	if game_world.changed_chunks.is_empty() && game_world.changed_blocks.is_empty() {
+		return;
+	}
+	game_world.changed_blocks.clear();
+	chunk_updates.send_batch(
+		game_world
+			.changed_chunks
+			.drain()
+			.map(|chunk_pos| ChunkUpdateEvent { chunk_pos }),
+	);
+}
+
+/// undoing is only possible in creative mode,
+/// because otherwise the drops of broken blocks could be collected multiple times
+fn undo_and_redo(
+	undo_input: Res<UndoInput>,
+	redo_input: Res<RedoInput>,
+	player: Query<&GameMode, With<Player>>,
+	mut game_world: ResMut<GameWorld>,
+) {
+	if !undo_input.started && !redo_input.started {
+		return;
+	}
+	if !matches!(player.get_single(), Ok(GameMode::Creative)) {
+		return;
+	}
+	if undo_input.started {
+		game_world.undo();
+	} else {
+		game_world.redo();
+	}
+}
+
+/// the commands follow the same rule as [`undo_and_redo`].<br>
+/// a server without a window has no player, so its console can always undo
+fn check_game_mode(world: &mut World) -> Result<(), Box<dyn Error>> {
+	let mut player = world.query_filtered::<&GameMode, With<Player>>();
+	match player.get_single(world) {
+		Ok(GameMode::Creative) | Err(_) => Ok(()),
+		Ok(_) => Err("undoing is only possible in creative mode".into()),
+	}
+}
+
+struct UndoCommand;
+
+impl Command for UndoCommand {
+	fn name(&self) -> &'static str {
+		"undo"
+	}
+
+	fn description(&self) -> &'static str {
+		"reverts the last change to the blocks of the world"
+	}
+
+	fn signatures(&self) -> &'static [&'static [Param]] {
+		const SIGNATURES: &[&[Param]] = &[&[]];
+		SIGNATURES
+	}
+
+	fn run(&self, _args: &Args, world: &mut World) -> Result<String, Box<dyn Error>> {
+		check_game_mode(world)?;
+		let count = world
+			.resource_mut::<GameWorld>()
+			.undo()
+			.ok_or("there is nothing to undo")?;
+		Ok(format!("undid the change of {count} blocks"))
+	}
+}
+
+struct RedoCommand;
+
+impl Command for RedoCommand {
+	fn name(&self) -> &'static str {
+		"redo"
+	}
+
+	fn description(&self) -> &'static str {
+		"changes the blocks back, after using /undo"
+	}
+
+	fn signatures(&self) -> &'static [&'static [Param]] {
+		const SIGNATURES: &[&[Param]] = &[&[]];
+		SIGNATURES
+	}
+
+	fn run(&self, _args: &Args, world: &mut World) -> Result<String, Box<dyn Error>> {
+		check_game_mode(world)?;
+		let count = world
+			.resource_mut::<GameWorld>()
+			.redo()
+			.ok_or("there is nothing to redo")?;
+		Ok(format!("redid the change of {count} blocks"))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{block::prelude::*, pos::ChunkPos};
+
+	fn world() -> GameWorld {
+		GameWorld::with_air_chunks([ChunkPos::new(0, 0, 0)])
+	}
+
+	fn block(game_world: &GameWorld, x: i32) -> Block {
+		*game_world.get_block_at(BlockPos::new(x, 0, 0)).unwrap()
+	}
+
+	#[test]
+	fn undo_and_redo_round_trip() {
+		let mut game_world = world();
+		game_world.set_blocks([
+			(BlockPos::new(0, 0, 0), Stone::BLOCK),
+			(BlockPos::new(1, 0, 0), Dirt::BLOCK),
+		]);
+		game_world.set_blocks([(BlockPos::new(0, 0, 0), Planks::BLOCK)]);
+
+		assert_eq!(game_world.undo(), Some(1));
+		assert_eq!(block(&game_world, 0), Stone::BLOCK);
+		assert_eq!(game_world.undo(), Some(2));
+		assert_eq!(block(&game_world, 0), Air::BLOCK);
+		assert_eq!(block(&game_world, 1), Air::BLOCK);
+		assert_eq!(game_world.undo(), None);
+
+		assert_eq!(game_world.redo(), Some(2));
+		assert_eq!(game_world.redo(), Some(1));
+		assert_eq!(block(&game_world, 0), Planks::BLOCK);
+		assert_eq!(block(&game_world, 1), Dirt::BLOCK);
+		assert_eq!(game_world.redo(), None);
+	}
+
+	#[test]
+	fn new_edits_clear_the_redo_stack() {
+		let mut game_world = world();
+		game_world.set_blocks([(BlockPos::new(0, 0, 0), Stone::BLOCK)]);
+		game_world.undo();
+		game_world.set_block(BlockPos::new(1, 0, 0), Dirt::BLOCK);
+		// the unfinished edit is finished before redoing
+		assert_eq!(game_world.redo(), None);
+		assert_eq!(block(&game_world, 0), Air::BLOCK);
+		assert_eq!(game_world.history.len, 1);
+
+		assert_eq!(game_world.undo(), Some(1));
+		assert_eq!(block(&game_world, 1), Air::BLOCK);
+	}
+
+	#[test]
+	fn the_oldest_edits_are_forgotten_over_budget() {
+		let change = BlockChange {
+			pos: BlockPos::new(0, 0, 0),
+			before: Air::BLOCK,
+			after: Stone::BLOCK,
+		};
+		let half_budget = MAX_HISTORY_BYTES / mem::size_of::<BlockChange>() / 2;
+		let mut history = EditHistory::default();
+		history.push_undo(vec![change; 1]);
+		history.push_undo(vec![change; half_budget]);
+		assert_eq!(history.undo_stack.len(), 2);
+
+		history.push_undo(vec![change; half_budget]);
+		assert_eq!(history.undo_stack.len(), 2);
+		assert_eq!(history.len, half_budget * 2);
+		assert!(history
+			.undo_stack
+			.iter()
+			.all(|edit| edit.len() == half_budget));
+	}
+}
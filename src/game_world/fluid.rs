@@ -0,0 +1,175 @@
+//! the simulation of fluids flowing and draining
+
+use super::GameWorld;
+use crate::{
+	axis::Axis,
+	block::{
+		fluid::{FluidKind, FluidState},
+		prelude::*,
+	},
+	face::Face,
+	pos::BlockPos,
+};
+use bevy::math::IVec3;
+
+/// updates the fluid at the given position, which may change blocks around it.<br>
+/// returns the positions of all blocks that were changed.
+pub fn tick_fluid(world: &mut GameWorld, pos: BlockPos) -> Vec<BlockPos> {
+	let Some(block) = world.get_block_at(pos).copied() else {
+		return Vec::new();
+	};
+	let Some((kind, old_state)) = block.get_fluid() else {
+		return Vec::new();
+	};
+
+	if kind == FluidKind::Lava && touches_water(world, pos) {
+		let new_block = if old_state.is_source() {
+			Stone::BLOCK
+		} else {
+			Cobblestone::BLOCK
+		};
+		set_block(world, pos, new_block);
+		return vec![pos];
+	}
+
+	let mut changed = Vec::new();
+
+	let state = if old_state.is_source() {
+		Some(old_state)
+	} else {
+		expected_state(world, pos, kind)
+	};
+	let Some(state) = state else {
+		// nothing is feeding this fluid anymore, so it drains away
+		set_block(world, pos, Air::BLOCK);
+		return vec![pos];
+	};
+	if state != old_state {
+		set_block(world, pos, kind.block(state));
+		changed.push(pos);
+	}
+
+	spread(world, pos, kind, state, &mut changed);
+	changed
+}
+
+/// what the state of a flowing fluid should be, given the fluids around it.<br>
+/// returns `None` if the fluid should drain away.
+fn expected_state(world: &GameWorld, pos: BlockPos, kind: FluidKind) -> Option<FluidState> {
+	if fluid_at(world, pos + IVec3::Y).is_some_and(|(k, _)| k == kind) {
+		return Some(FluidState::FALLING);
+	}
+
+	let mut source_count = 0;
+	let mut min_distance = None;
+	for neighbour in horizontal_neighbours(pos) {
+		let Some((neighbour_kind, neighbour_state)) = fluid_at(world, neighbour) else {
+			continue;
+		};
+		if neighbour_kind != kind {
+			continue;
+		}
+		if neighbour_state.is_source() {
+			source_count += 1;
+		}
+		// fluids only spread sideways if they cant flow down
+		if can_flow_into(world, neighbour - IVec3::Y, kind) {
+			continue;
+		}
+		let distance = if neighbour_state.falling {
+			0
+		} else {
+			neighbour_state.distance
+		};
+		let distance = distance + 1;
+		if min_distance.is_none_or(|min| distance < min) {
+			min_distance = Some(distance);
+		}
+	}
+
+	// water between two sources becomes a source itself,
+	// which allows creating infinite water
+	if kind == FluidKind::Water && source_count >= 2 && !can_flow_into(world, pos - IVec3::Y, kind)
+	{
+		return Some(FluidState::SOURCE);
+	}
+
+	min_distance
+		.filter(|&distance| distance <= kind.max_distance())
+		.map(FluidState::flowing)
+}
+
+/// lets the fluid flow down if possible, and otherwise sideways
+fn spread(
+	world: &mut GameWorld,
+	pos: BlockPos,
+	kind: FluidKind,
+	state: FluidState,
+	changed: &mut Vec<BlockPos>,
+) {
+	let below = pos - IVec3::Y;
+	if can_flow_into(world, below, kind) {
+		if fluid_at(world, below) != Some((kind, FluidState::FALLING)) {
+			set_block(world, below, kind.block(FluidState::FALLING));
+			changed.push(below);
+		}
+		return;
+	}
+
+	let distance = if state.falling { 0 } else { state.distance };
+	let next_distance = distance + 1;
+	if next_distance > kind.max_distance() {
+		return;
+	}
+	for neighbour in horizontal_neighbours(pos) {
+		if !can_flow_into(world, neighbour, kind) {
+			continue;
+		}
+		if let Some((neighbour_kind, neighbour_state)) = fluid_at(world, neighbour) {
+			if neighbour_kind == kind
+				&& (neighbour_state.falling || neighbour_state.distance <= next_distance)
+			{
+				continue;
+			}
+		}
+		set_block(
+			world,
+			neighbour,
+			kind.block(FluidState::flowing(next_distance)),
+		);
+		changed.push(neighbour);
+	}
+}
+
+/// whether a fluid of the given kind is able to flow into the block at the given position
+fn can_flow_into(world: &GameWorld, pos: BlockPos, kind: FluidKind) -> bool {
+	let Some(block) = world.get_block_at(pos) else {
+		return false;
+	};
+	match block.get_fluid() {
+		Some((other_kind, other_state)) => other_kind == kind && !other_state.is_source(),
+		None => block.is_replacable(),
+	}
+}
+
+fn touches_water(world: &GameWorld, pos: BlockPos) -> bool {
+	pos.neighbours()
+		.filter(|&neighbour| neighbour != pos - IVec3::Y)
+		.any(|neighbour| fluid_at(world, neighbour).is_some_and(|(k, _)| k == FluidKind::Water))
+}
+
+fn fluid_at(world: &GameWorld, pos: BlockPos) -> Option<(FluidKind, FluidState)> {
+	world.get_block_at(pos)?.get_fluid()
+}
+
+fn horizontal_neighbours(pos: BlockPos) -> impl Iterator<Item = BlockPos> {
+	Face::all()
+		.filter(|face| face.axis() != Axis::Y)
+		.map(move |face| pos + face.normal())
+}
+
+fn set_block(world: &mut GameWorld, pos: BlockPos, block: Block) {
+	if let Some(old_block) = world.get_block_at_mut(pos) {
+		*old_block = block;
+	}
+}
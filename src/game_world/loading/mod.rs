@@ -3,19 +3,15 @@
 
 pub mod worldgen;
 
-use self::worldgen::{continue_generation_of_chunk, fully_generate_chunk};
+use self::worldgen::fully_generate_chunk;
 
-use super::{
-	chunk::{GenerationStage, IsLoaded},
-	GameWorld,
-};
+use super::{chunk::IsLoaded, is_local_world, GameWorld};
 use crate::{
-	entity::player::Player,
 	global_config,
 	pos::{ChunkPos, Vec3Utils},
 	GlobalState,
 };
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
 use std::collections::VecDeque;
 
 pub struct LoadingPlugin;
@@ -28,10 +24,14 @@ impl Plugin for LoadingPlugin {
 			.add_systems(
 				Update,
 				(
-					push_chunk_pos_to_load_queue,
-					push_chunk_pos_to_unload_queue,
-					load_chunks,
-					unload_chunks,
+					(
+						push_chunk_pos_to_load_queue,
+						push_chunk_pos_to_unload_queue,
+						load_chunks,
+						unload_chunks,
+					)
+						// the chunks of remote worlds are loaded by the server
+						.run_if(is_local_world),
 					update_chunk_visibility,
 				)
 					.run_if(in_state(GlobalState::InWorld)),
@@ -39,6 +39,11 @@ impl Plugin for LoadingPlugin {
 	}
 }
 
+/// chunks in the render distance around entities with this component are loaded.<br>
+/// this is the player, and other players when hosting a server.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct ChunkLoader;
+
 /// event that is sent when the loading state of a chunk changes.<br>
 /// the chunk may be added or removed from the game_world before sending this event
 #[derive(Event, Debug, Clone, Copy)]
@@ -79,21 +84,17 @@ fn load_chunks(
 		return;
 	};
 
-	let loaded;
-	if let Some(chunk) = game_world.chunks.get(&pos) {
-		if chunk.loaded.is_simple_loaded() {
-			return;
-		}
-		if chunk.generation_state != GenerationStage::COMPLETE {
-			continue_generation_of_chunk(&mut game_world, pos);
-		}
-		let chunk = game_world.chunks.get_mut(&pos).unwrap();
-		chunk.loaded.set_simple_loaded(true);
-		loaded = chunk.loaded;
-	} else {
-		fully_generate_chunk(&mut game_world, pos, IsLoaded::SIMPLE_LOADED);
-		loaded = game_world.chunks.get(&pos).unwrap().loaded;
+	if game_world
+		.chunks
+		.get(&pos)
+		.is_some_and(|c| c.loaded.is_simple_loaded())
+	{
+		return;
 	}
+	fully_generate_chunk(&mut game_world, pos, IsLoaded::SIMPLE_LOADED);
+	let chunk = game_world.chunks.get_mut(&pos).unwrap();
+	chunk.loaded.set_simple_loaded(true);
+	let loaded = chunk.loaded;
 
 	events.send(UpdateChunkIsLoadedEvent {
 		pos,
@@ -132,7 +133,7 @@ fn unload_chunks(
 
 fn push_chunk_pos_to_load_queue(
 	mut queue: ResMut<ChunkLoadingQueue>,
-	player: Query<&Transform, With<Player>>,
+	loaders: Query<&Transform, With<ChunkLoader>>,
 	game_world: Res<GameWorld>,
 	global_config: Res<global_config::Config>,
 ) {
@@ -140,26 +141,26 @@ fn push_chunk_pos_to_load_queue(
 		global_config.horizontal_render_distance,
 		global_config.vertical_render_distance,
 	);
-	let player = player.single();
-	let player_pos = player.translation;
-	let chunk_pos_to_load = chunk_pos_in_render_distance(player_pos, render_distance)
-		.into_iter()
-		.filter(|pos| {
-			game_world
-				.chunks
-				.get(pos)
-				.map(|c| !c.loaded.is_simple_loaded())
-				.unwrap_or(true)
-		})
-		.filter(|pos| !queue.queue.contains(pos))
-		.collect::<Vec<_>>();
-
-	queue.queue.extend(chunk_pos_to_load);
+	for loader in &loaders {
+		let chunk_pos_to_load = chunk_pos_in_render_distance(loader.translation, render_distance)
+			.into_iter()
+			.filter(|pos| {
+				game_world
+					.chunks
+					.get(pos)
+					.map(|c| !c.loaded.is_simple_loaded())
+					.unwrap_or(true)
+			})
+			.filter(|pos| !queue.queue.contains(pos))
+			.collect::<Vec<_>>();
+
+		queue.queue.extend(chunk_pos_to_load);
+	}
 }
 
 fn push_chunk_pos_to_unload_queue(
 	mut queue: ResMut<ChunkUnloadingQueue>,
-	player: Query<&Transform, With<Player>>,
+	loaders: Query<&Transform, With<ChunkLoader>>,
 	game_world: Res<GameWorld>,
 	global_config: Res<global_config::Config>,
 ) {
@@ -167,14 +168,15 @@ fn push_chunk_pos_to_unload_queue(
 		global_config.horizontal_render_distance,
 		global_config.vertical_render_distance,
 	);
-	let player = player.single();
-	let player_pos = player.translation;
-	let in_render_distance = chunk_pos_in_render_distance(player_pos, render_distance);
+	let in_render_distance = loaders
+		.iter()
+		.flat_map(|loader| chunk_pos_in_render_distance(loader.translation, render_distance))
+		.collect::<HashSet<_>>();
 	let chunk_pos_to_unload = game_world
 		.chunks
 		.iter()
 		.filter(|(_, chunk)| chunk.loaded.is_simple_loaded())
-		.filter(|(pos, _)| !in_render_distance.contains(pos))
+		.filter(|(pos, _)| !in_render_distance.contains(*pos))
 		.filter(|(pos, _)| !queue.queue.contains(pos))
 		.map(|(pos, _)| *pos)
 		.collect::<Vec<_>>();
@@ -184,7 +186,10 @@ fn push_chunk_pos_to_unload_queue(
 
 /// gets a list of chunk positions that are in the render distance<br>
 /// this is ordered from closest to farthest, so close chunks get loaded first
-fn chunk_pos_in_render_distance(player_pos: Vec3, render_distance: (u32, u32)) -> Vec<ChunkPos> {
+pub fn chunk_pos_in_render_distance(
+	player_pos: Vec3,
+	render_distance: (u32, u32),
+) -> Vec<ChunkPos> {
 	// current implementation will load in a square
 	// TODO load chunks in a sphere
 
@@ -1,16 +1,23 @@
+mod pending_edits;
+mod settings;
+mod structures;
 mod terrain;
 mod trees;
 
-use self::terrain::get_height_at;
+use self::{settings::TerrainSettings, terrain::get_height_at};
 use crate::{
+	block::Block,
 	game_world::{
 		chunk::{GenerationStage, IsLoaded},
 		GameWorld,
 	},
-	pos::ChunkPos,
+	pos::{BlockPos, ChunkPos},
 };
 use noise::Perlin;
 
+pub use self::pending_edits::PendingEdits;
+pub use self::settings::{WorldGenPreset, WorldGenSettings};
+pub use self::structures::{generate_structures, locate_structure, StructureCache, StructureKind};
 pub use self::terrain::generate_chunk_terrain;
 pub use self::trees::generate_trees;
 
@@ -18,28 +25,90 @@ pub type Seed = u32;
 
 // guarantees that `world.chunks.get(&pos)` will be `Some`
 pub fn fully_generate_chunk(world: &mut GameWorld, pos: ChunkPos, loaded: IsLoaded) {
-	let chunk = generate_chunk_terrain(pos, world.seed, loaded);
+	generate_chunk_to_stage(world, pos, GenerationStage::COMPLETE, loaded);
+}
+
+/// performs all generation steps needed to get the chunk to at least the `target` stage.<br>
+/// before a chunk can advance to some stage, all 26 chunks around it
+/// first have to reach the stage before that. this means a step that
+/// writes into neighbouring chunks (like trees) always finds them in a known state.<br>
+/// if the chunk has to be created, it will use the given `loaded` value.
+pub fn generate_chunk_to_stage(
+	world: &mut GameWorld,
+	pos: ChunkPos,
+	target: GenerationStage,
+	loaded: IsLoaded,
+) {
+	loop {
+		let current = get_generation_stage(world, pos);
+		if current >= target {
+			return;
+		}
+		let Some(next) = current.next() else {
+			return;
+		};
+
+		for neighbour in pos.surrounding() {
+			generate_chunk_to_stage(world, neighbour, current, IsLoaded::NOT_LOADED);
+		}
+
+		match next {
+			GenerationStage::Nothing => (),
+			GenerationStage::Terrain => generate_terrain(world, pos, loaded),
+			GenerationStage::Structures => generate_structures(world, pos),
+			GenerationStage::Trees => generate_trees(world, pos),
+		}
+
+		if let Some(chunk) = world.chunks.get_mut(&pos) {
+			chunk.generation_state = next;
+		}
+	}
+}
+
+fn get_generation_stage(world: &GameWorld, pos: ChunkPos) -> GenerationStage {
+	world
+		.chunks
+		.get(&pos)
+		.map(|chunk| chunk.generation_state)
+		.unwrap_or(GenerationStage::Nothing)
+}
+
+fn generate_terrain(world: &mut GameWorld, pos: ChunkPos, loaded: IsLoaded) {
+	let loaded = world.chunks.get(&pos).map(|c| c.loaded).unwrap_or(loaded);
+	let mut chunk =
+		generate_chunk_terrain(pos, world.seed, &world.worldgen_settings.terrain, loaded);
+	world.pending_edits.apply(pos, &mut chunk);
 	world.chunks.insert(pos, chunk);
-	generate_trees(world, pos);
 }
 
-/// will perform all neccessary generation steps needed to get the
-/// chunk to [`GenerationStage::COMPLETE`], and will say that it is
-/// not loaded if a new chunk is created via this function.
-pub fn continue_generation_of_chunk(world: &mut GameWorld, pos: ChunkPos) {
-	let Some(chunk) = world.chunks.get(&pos) else {
-		fully_generate_chunk(world, pos, IsLoaded::NOT_LOADED);
-		return;
-	};
-
-	match chunk.generation_state {
-		GenerationStage::Nothing => fully_generate_chunk(world, pos, IsLoaded::NOT_LOADED),
-		GenerationStage::Terrain => generate_trees(world, pos),
-		GenerationStage::COMPLETE => (),
+/// places a block from inside a generation step.<br>
+/// if the terrain of the chunk the block is in doesnt exist yet,
+/// the block will be placed once it does.
+pub fn place_generated_block(world: &mut GameWorld, pos: BlockPos, block: Block) {
+	let chunk_pos = pos.to_chunk_pos();
+	if get_generation_stage(world, chunk_pos) >= GenerationStage::Terrain {
+		if let Some(old) = world.get_block_at_mut(pos) {
+			*old = block;
+		}
+		// trees can grow into chunks that are already complete, and might have been sent to players
+		if get_generation_stage(world, chunk_pos) >= GenerationStage::COMPLETE {
+			world.changed_blocks.push(pos);
+		}
+	} else {
+		world.pending_edits.push(pos, block);
 	}
 }
 
-pub fn get_height_at_with_seed(x: i32, z: i32, seed: Seed) -> i32 {
-	let perlin = Perlin::new(seed);
-	get_height_at(x, z, &perlin)
+/// gets the y coordinate of the highest block that the terrain
+/// generation would place at the given x and z coordinates
+pub fn get_height_at_with_seed(x: i32, z: i32, seed: Seed, settings: &WorldGenSettings) -> i32 {
+	match &settings.terrain {
+		TerrainSettings::Noise(noise) => {
+			let perlin = Perlin::new(seed);
+			get_height_at(x, z, &perlin, noise)
+		}
+		TerrainSettings::Superflat(superflat) => superflat.top_y().unwrap_or(0),
+		// the spawn platform
+		TerrainSettings::Void => 0,
+	}
 }
@@ -0,0 +1,34 @@
+use crate::{
+	block::Block,
+	game_world::chunk::Chunk,
+	pos::{BlockPos, ChunkPos},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// blocks that a generation step wanted to place in a chunk whose terrain didnt exist yet.<br>
+/// they are placed as soon as the terrain of that chunk is generated,
+/// so that the terrain doesnt overwrite them.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PendingEdits {
+	edits: HashMap<ChunkPos, Vec<(BlockPos, Block)>>,
+}
+
+impl PendingEdits {
+	pub fn push(&mut self, pos: BlockPos, block: Block) {
+		self.edits
+			.entry(pos.to_chunk_pos())
+			.or_default()
+			.push((pos, block));
+	}
+
+	/// places all pending edits of the chunk at `chunk_pos` into `chunk` and forgets about them
+	pub fn apply(&mut self, chunk_pos: ChunkPos, chunk: &mut Chunk) {
+		let Some(edits) = self.edits.remove(&chunk_pos) else {
+			return;
+		};
+		for (pos, block) in edits {
+			chunk.blocks[pos.to_block_in_chunk_pos()] = block;
+		}
+	}
+}
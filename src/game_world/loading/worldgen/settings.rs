@@ -0,0 +1,247 @@
+//! the settings that decide how the terrain of a world is generated.<br>
+//! these are chosen once when creating a world and are then stored in the [`GameWorld`](crate::game_world::GameWorld)
+
+use crate::{
+	block::{prelude::*, BlockId},
+	savedata,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{error::Error, fmt, fs};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldGenSettings {
+	/// the basic shape of the terrain
+	pub terrain: TerrainSettings,
+	/// whether trees should be placed on grass blocks
+	pub trees: bool,
+	/// whether structures like villages and dungeons should be placed
+	pub structures: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TerrainSettings {
+	/// hills and caves generated with perlin noise
+	Noise(NoiseSettings),
+	/// the same layers of blocks everywhere
+	Superflat(SuperflatSettings),
+	/// nothing but air
+	Void,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoiseSettings {
+	/// how far apart the big hills are horizontally
+	pub horizontal_stretch_0: f64,
+	/// how tall the big hills are
+	pub vertical_stretch_0: f64,
+	/// how far apart the small hills are horizontally
+	pub horizontal_stretch_1: f64,
+	/// how tall the small hills are
+	pub vertical_stretch_1: f64,
+	/// how much the noise for caves is stretched along each axis<br>
+	/// smaller values lead to bigger caves
+	pub cave_stretch: [f64; 3],
+	/// the noise value above which a block becomes cave air<br>
+	/// higher values lead to less caves
+	pub cave_threshold: f64,
+	/// how many blocks of dirt are below the grass block
+	pub dirt_depth: u32,
+	/// the height up to which water is placed above the terrain, or `None` for no water
+	pub sea_level: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuperflatSettings {
+	/// the y coordinate of the lowest layer
+	pub bottom_y: i32,
+	/// the layers ordered from bottom to top
+	pub layers: Vec<SuperflatLayer>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuperflatLayer {
+	#[serde(
+		serialize_with = "serialize_block",
+		deserialize_with = "deserialize_block"
+	)]
+	pub block: Block,
+	/// how many blocks high this layer is
+	pub thickness: u32,
+}
+
+/// a way to get [`WorldGenSettings`] when creating a new world
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorldGenPreset {
+	Default,
+	Superflat,
+	Amplified,
+	Void,
+	/// settings that are loaded from a RON file in the `worldgen_presets` folder
+	Custom(String),
+}
+
+impl Default for WorldGenSettings {
+	fn default() -> Self {
+		Self {
+			terrain: TerrainSettings::Noise(NoiseSettings::default()),
+			trees: true,
+			structures: true,
+		}
+	}
+}
+
+impl Default for NoiseSettings {
+	fn default() -> Self {
+		Self {
+			horizontal_stretch_0: 74.379,
+			vertical_stretch_0: 23.748,
+			horizontal_stretch_1: 21.174,
+			vertical_stretch_1: 4.849,
+			cave_stretch: [0.058, 0.053, 0.050],
+			cave_threshold: 0.5,
+			dirt_depth: 3,
+			sea_level: Some(-6),
+		}
+	}
+}
+
+impl Default for SuperflatSettings {
+	fn default() -> Self {
+		Self {
+			bottom_y: -4,
+			layers: vec![
+				SuperflatLayer {
+					block: Stone::BLOCK,
+					thickness: 1,
+				},
+				SuperflatLayer {
+					block: Dirt::BLOCK,
+					thickness: 2,
+				},
+				SuperflatLayer {
+					block: GrassBlock::BLOCK,
+					thickness: 1,
+				},
+			],
+		}
+	}
+}
+
+impl WorldGenSettings {
+	pub fn superflat(settings: SuperflatSettings) -> Self {
+		Self {
+			terrain: TerrainSettings::Superflat(settings),
+			trees: false,
+			structures: false,
+		}
+	}
+
+	pub fn amplified() -> Self {
+		let noise = NoiseSettings {
+			horizontal_stretch_0: 112.837,
+			vertical_stretch_0: 94.992,
+			vertical_stretch_1: 9.698,
+			..NoiseSettings::default()
+		};
+		Self {
+			terrain: TerrainSettings::Noise(noise),
+			trees: true,
+			structures: true,
+		}
+	}
+
+	pub fn void() -> Self {
+		Self {
+			terrain: TerrainSettings::Void,
+			trees: false,
+			structures: false,
+		}
+	}
+
+	/// loads custom settings from `worldgen_presets/<name>.ron` in the savedata folder
+	pub fn load_custom(name: &str) -> Result<Self, Box<dyn Error>> {
+		let path = custom_presets_path().join(format!("{}.ron", name));
+		let string = fs::read_to_string(path)?;
+		let settings = ron::from_str(&string)?;
+		Ok(settings)
+	}
+}
+
+impl SuperflatSettings {
+	/// gets the block at the given height, which is the same for every x and z
+	pub fn get_block_at(&self, y: i32) -> Block {
+		let mut layer_bottom = self.bottom_y;
+		for layer in &self.layers {
+			let layer_top = layer_bottom + layer.thickness as i32;
+			if (layer_bottom..layer_top).contains(&y) {
+				return layer.block;
+			}
+			layer_bottom = layer_top;
+		}
+		Air::BLOCK
+	}
+
+	/// the y coordinate of the highest block, or `None` if there are no layers
+	pub fn top_y(&self) -> Option<i32> {
+		let thickness = self.layers.iter().map(|l| l.thickness as i32).sum::<i32>();
+		(thickness > 0).then(|| self.bottom_y + thickness - 1)
+	}
+}
+
+impl WorldGenPreset {
+	/// gets all built in presets, followed by all custom presets that could be found
+	pub fn all() -> Vec<Self> {
+		let mut presets = vec![Self::Default, Self::Superflat, Self::Amplified, Self::Void];
+		let Ok(in_folder) = fs::read_dir(custom_presets_path()) else {
+			return presets;
+		};
+		for file in in_folder.flatten() {
+			let path = file.path();
+			if path.extension().is_some_and(|ext| ext == "ron") {
+				if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+					presets.push(Self::Custom(name.to_owned()));
+				}
+			}
+		}
+		presets
+	}
+
+	pub fn to_settings(&self) -> Result<WorldGenSettings, Box<dyn Error>> {
+		Ok(match self {
+			Self::Default => WorldGenSettings::default(),
+			Self::Superflat => WorldGenSettings::superflat(SuperflatSettings::default()),
+			Self::Amplified => WorldGenSettings::amplified(),
+			Self::Void => WorldGenSettings::void(),
+			Self::Custom(name) => WorldGenSettings::load_custom(name)?,
+		})
+	}
+}
+
+impl fmt::Display for WorldGenPreset {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Default => write!(f, "Default"),
+			Self::Superflat => write!(f, "Superflat"),
+			Self::Amplified => write!(f, "Amplified"),
+			Self::Void => write!(f, "Void"),
+			Self::Custom(name) => write!(f, "{}", name),
+		}
+	}
+}
+
+fn custom_presets_path() -> std::path::PathBuf {
+	savedata::get_savedata_path().join("worldgen_presets")
+}
+
+// blocks are stored by name, so that the RON files are readable and dont depend on raw ids
+
+fn serialize_block<S: Serializer>(block: &Block, serializer: S) -> Result<S::Ok, S::Error> {
+	block.id.to_debug_name().serialize(serializer)
+}
+
+fn deserialize_block<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Block, D::Error> {
+	let name = String::deserialize(deserializer)?;
+	let id = BlockId::from_debug_name(&name)
+		.ok_or_else(|| serde::de::Error::custom(format!("uknown block name: {}", name)))?;
+	Ok(Block::from_id(id))
+}
@@ -0,0 +1,144 @@
+use super::template::{rotate_pos, unrotate_pos, ConnectorFacing, StructureTemplate};
+use crate::block::Block;
+use bevy::math::{IVec3, Vec3Swizzles};
+use rand::{prelude::StdRng, seq::SliceRandom};
+use std::collections::VecDeque;
+
+/// a template that has been placed somewhere in the world
+#[derive(Debug, Clone)]
+pub struct Piece {
+	pub template: &'static StructureTemplate,
+	/// how many quarter turns the template is rotated around the y axis
+	pub rotation: u8,
+	/// the lowest corner of the piece in block space
+	pub min: IVec3,
+}
+
+/// a connector of a placed [`Piece`], in block space
+struct OpenConnector {
+	pos: IVec3,
+	facing: ConnectorFacing,
+	pool: &'static str,
+}
+
+/// decides how pieces are placed relative to the terrain
+pub enum Elevation<'a> {
+	/// every piece is placed on the surface, which is given by the function for any x and z
+	Surface(&'a dyn Fn(i32, i32) -> i32),
+	/// every piece is placed so that its connector is at the same height as the one it attaches to
+	Fixed,
+}
+
+impl Piece {
+	pub fn size(&self) -> IVec3 {
+		self.template.rotated_size(self.rotation)
+	}
+
+	/// the highest corner of the piece, exclusive
+	pub fn max(&self) -> IVec3 {
+		self.min + self.size()
+	}
+
+	/// whether this piece overlaps the box from `min` to `max` (exclusive)
+	pub fn intersects(&self, min: IVec3, max: IVec3) -> bool {
+		self.min.cmplt(max).all() && min.cmplt(self.max()).all()
+	}
+
+	/// the block that this piece places at the given position in block space
+	pub fn get_block(&self, pos: IVec3) -> Option<Block> {
+		let local = unrotate_pos(pos - self.min, self.size(), self.rotation);
+		self.template.get_block(local)
+	}
+
+	fn connectors(&self) -> impl Iterator<Item = OpenConnector> + '_ {
+		self.template.connectors.iter().map(|c| OpenConnector {
+			pos: self.min + rotate_pos(IVec3::from_array(c.pos), self.template.size, self.rotation),
+			facing: c.facing.rotated(self.rotation),
+			pool: &c.pool,
+		})
+	}
+}
+
+/// attaches random pieces to the open connectors, starting at `start`, until
+/// `max_depth` pieces are chained together or no more pieces fit.<br>
+/// no piece will reach further than `max_extent` blocks horizontally from where `start` is.
+pub fn assemble(
+	start: Piece,
+	max_depth: u32,
+	max_extent: i32,
+	elevation: &Elevation,
+	rng: &mut StdRng,
+) -> Vec<Piece> {
+	let origin = start.min;
+	let mut open = start.connectors().map(|c| (c, 1)).collect::<VecDeque<_>>();
+	let mut pieces = vec![start];
+
+	while let Some((connector, depth)) = open.pop_front() {
+		if depth > max_depth {
+			continue;
+		}
+		let Some(piece) =
+			find_fitting_piece(&connector, &pieces, origin, max_extent, elevation, rng)
+		else {
+			continue;
+		};
+		let attached_at = connector.pos + connector.facing.normal();
+		open.extend(
+			piece
+				.connectors()
+				.filter(|c| c.pos != attached_at)
+				.map(|c| (c, depth + 1)),
+		);
+		pieces.push(piece);
+	}
+
+	pieces
+}
+
+fn find_fitting_piece(
+	connector: &OpenConnector,
+	pieces: &[Piece],
+	origin: IVec3,
+	max_extent: i32,
+	elevation: &Elevation,
+	rng: &mut StdRng,
+) -> Option<Piece> {
+	let target = connector.pos + connector.facing.normal();
+	let mut templates = StructureTemplate::in_pool(connector.pool).collect::<Vec<_>>();
+	templates.shuffle(rng);
+	let mut rotations = [0, 1, 2, 3];
+
+	for template in templates {
+		rotations.shuffle(rng);
+		for rotation in rotations {
+			for other in &template.connectors {
+				if other.facing.rotated(rotation) != connector.facing.opposite() {
+					continue;
+				}
+				let other_pos = rotate_pos(IVec3::from_array(other.pos), template.size, rotation);
+				let mut min = target - other_pos;
+				if let Elevation::Surface(surface) = elevation {
+					let center = min + template.rotated_size(rotation) / 2;
+					min.y = surface(center.x, center.z) + 1 - template.ground;
+				}
+				let piece = Piece {
+					template,
+					rotation,
+					min,
+				};
+				if is_in_extent(&piece, origin, max_extent)
+					&& !pieces.iter().any(|p| p.intersects(piece.min, piece.max()))
+				{
+					return Some(piece);
+				}
+			}
+		}
+	}
+	None
+}
+
+fn is_in_extent(piece: &Piece, origin: IVec3, max_extent: i32) -> bool {
+	let min = piece.min.xz() - origin.xz();
+	let max = piece.max().xz() - origin.xz();
+	min.min_element() >= -max_extent && max.max_element() <= max_extent
+}
@@ -0,0 +1,305 @@
+//! structures are groups of blocks built from templates, like villages or dungeons.<br>
+//! the world is split into a grid of regions for each kind of structure,
+//! and every region may contain a single structure at a random position.
+//! because the layout of a structure only depends on the seed and its region,
+//! every chunk can place its own part of a structure without looking at other chunks.
+
+mod jigsaw;
+mod template;
+
+use self::{
+	jigsaw::{Elevation, Piece},
+	template::StructureTemplate,
+};
+use super::{get_height_at_with_seed, settings::TerrainSettings, Seed, WorldGenSettings};
+use crate::{
+	game_world::{chunk::CHUNK_LENGTH, GameWorld},
+	pos::{BlockPos, ChunkPos},
+};
+use bevy::{
+	math::{IVec2, IVec3, Vec3Swizzles},
+	reflect::Reflect,
+};
+use rand::{prelude::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use std::{collections::HashMap, fmt};
+
+/// how far a structure can reach from its start horizontally, in blocks
+const MAX_EXTENT: i32 = 3 * CHUNK_LENGTH as i32;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum StructureKind {
+	#[default]
+	Village,
+	Dungeon,
+	Ruins,
+}
+
+/// remembers the layout of structures that have already been assembled,
+/// so that they dont have to be assembled again for every chunk they are in
+#[derive(Debug, Default, Clone)]
+pub struct StructureCache {
+	layouts: HashMap<(StructureKind, IVec2), Option<Vec<Piece>>>,
+}
+
+impl StructureKind {
+	pub const ALL: [Self; 3] = [Self::Village, Self::Dungeon, Self::Ruins];
+
+	/// the size of a region in chunks
+	fn spacing(self) -> i32 {
+		match self {
+			Self::Village => 12,
+			Self::Dungeon => 6,
+			Self::Ruins => 8,
+		}
+	}
+
+	/// the minimum distance in chunks between structures of neighbouring regions
+	fn separation(self) -> i32 {
+		match self {
+			Self::Village => 4,
+			Self::Dungeon => 2,
+			Self::Ruins => 3,
+		}
+	}
+
+	fn salt(self) -> u64 {
+		match self {
+			Self::Village => 2947104837,
+			Self::Dungeon => 8301746291,
+			Self::Ruins => 5610382946,
+		}
+	}
+
+	/// the pool the first piece is chosen from
+	fn start_pool(self) -> &'static str {
+		match self {
+			Self::Village => "village_center",
+			Self::Dungeon => "dungeon_rooms",
+			Self::Ruins => "ruins",
+		}
+	}
+
+	/// how many pieces can be chained together from the first piece
+	fn max_depth(self) -> u32 {
+		match self {
+			Self::Village => 4,
+			Self::Dungeon => 4,
+			Self::Ruins => 0,
+		}
+	}
+
+	/// how many blocks below the surface this structure is, or `None` if it is on the surface
+	fn depth(self) -> Option<i32> {
+		match self {
+			Self::Village | Self::Ruins => None,
+			Self::Dungeon => Some(24),
+		}
+	}
+
+	/// the chance for each block of the structure to actually be placed
+	fn integrity(self) -> f32 {
+		match self {
+			Self::Village | Self::Dungeon => 1.0,
+			Self::Ruins => 0.7,
+		}
+	}
+}
+
+impl fmt::Display for StructureKind {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Village => write!(f, "Village"),
+			Self::Dungeon => write!(f, "Dungeon"),
+			Self::Ruins => write!(f, "Ruins"),
+		}
+	}
+}
+
+/// places the parts of all structures that are inside of the chunk
+pub fn generate_structures(world: &mut GameWorld, chunk_pos: ChunkPos) {
+	if !world.worldgen_settings.structures {
+		return;
+	}
+	let chunk_min = IVec3::from(chunk_pos.to_block_pos());
+	let chunk_max = chunk_min + CHUNK_LENGTH as i32;
+	let Some(chunk) = world.chunks.get_mut(&chunk_pos) else {
+		bevy::log::error!(
+			"trying to generate structures in a chunk that doesnt exist (at {})",
+			chunk_pos
+		);
+		return;
+	};
+
+	for kind in StructureKind::ALL {
+		for region in regions_near(kind, chunk_pos) {
+			let layout = world
+				.structure_cache
+				.layouts
+				.entry((kind, region))
+				.or_insert_with(|| {
+					assemble_structure(world.seed, &world.worldgen_settings, kind, region)
+				});
+			let Some(pieces) = layout else {
+				continue;
+			};
+			for piece in pieces.iter().filter(|p| p.intersects(chunk_min, chunk_max)) {
+				let min = piece.min.max(chunk_min);
+				let max = piece.max().min(chunk_max);
+				for x in min.x..max.x {
+					for y in min.y..max.y {
+						for z in min.z..max.z {
+							let pos = IVec3::new(x, y, z);
+							let Some(block) = piece.get_block(pos) else {
+								continue;
+							};
+							if !is_intact(kind, pos) {
+								continue;
+							}
+							chunk.blocks[BlockPos::from(pos).to_block_in_chunk_pos()] = block;
+						}
+					}
+				}
+			}
+		}
+	}
+}
+
+/// finds the start of the closest structure of the given kind.<br>
+/// only looks a limited distance away, so this may return `None` even if there is a structure.
+pub fn locate_structure(
+	seed: Seed,
+	settings: &WorldGenSettings,
+	kind: StructureKind,
+	from: BlockPos,
+) -> Option<BlockPos> {
+	const MAX_RING: i32 = 32;
+
+	let spacing_in_blocks = kind.spacing() * CHUNK_LENGTH as i32;
+	let center = IVec2::new(from.x, from.z).div_euclid(IVec2::splat(spacing_in_blocks));
+	let mut closest: Option<(i64, IVec3)> = None;
+
+	for ring in 0..=MAX_RING {
+		if let Some((distance_squared, _)) = closest {
+			// every region in this ring is further away than the closest structure
+			let ring_distance = ((ring - 1) * spacing_in_blocks) as i64;
+			if ring_distance * ring_distance > distance_squared {
+				break;
+			}
+		}
+		for x in -ring..=ring {
+			for z in -ring..=ring {
+				if x.abs() != ring && z.abs() != ring {
+					continue;
+				}
+				let Some(start) = find_start(seed, settings, kind, center + IVec2::new(x, z))
+				else {
+					continue;
+				};
+				let offset = (start.xz() - IVec2::new(from.x, from.z)).as_i64vec2();
+				let distance_squared = offset.length_squared();
+				if closest.is_none_or(|(closest, _)| distance_squared < closest) {
+					closest = Some((distance_squared, start));
+				}
+			}
+		}
+	}
+
+	closest.map(|(_, start)| BlockPos::from(start))
+}
+
+/// gets the regions that might contain a structure reaching into the chunk
+fn regions_near(kind: StructureKind, chunk_pos: ChunkPos) -> impl Iterator<Item = IVec2> {
+	let reach = MAX_EXTENT / CHUNK_LENGTH as i32 + 1;
+	let spacing = IVec2::splat(kind.spacing());
+	let chunk = IVec2::new(chunk_pos.x, chunk_pos.z);
+	let min = (chunk - reach).div_euclid(spacing);
+	let max = (chunk + reach).div_euclid(spacing);
+	(min.x..=max.x).flat_map(move |x| (min.y..=max.y).map(move |z| IVec2::new(x, z)))
+}
+
+/// gets the position of the center of the structure in the region, if there is one
+fn find_start(
+	seed: Seed,
+	settings: &WorldGenSettings,
+	kind: StructureKind,
+	region: IVec2,
+) -> Option<IVec3> {
+	if !settings.structures || matches!(settings.terrain, TerrainSettings::Void) {
+		return None;
+	}
+	let mut rng = get_region_random(seed, kind, region);
+	let range = 0..kind.spacing() - kind.separation();
+	let chunk =
+		region * kind.spacing() + IVec2::new(rng.gen_range(range.clone()), rng.gen_range(range));
+	let xz = chunk * CHUNK_LENGTH as i32 + CHUNK_LENGTH as i32 / 2;
+	let surface = get_height_at_with_seed(xz.x, xz.y, seed, settings);
+
+	match kind.depth() {
+		Some(depth) => Some(IVec3::new(xz.x, surface - depth, xz.y)),
+		None if is_below_sea_level(settings, surface) => None,
+		None => Some(IVec3::new(xz.x, surface + 1, xz.y)),
+	}
+}
+
+fn assemble_structure(
+	seed: Seed,
+	settings: &WorldGenSettings,
+	kind: StructureKind,
+	region: IVec2,
+) -> Option<Vec<Piece>> {
+	let start = find_start(seed, settings, kind, region)?;
+	let mut rng = get_region_random(seed, kind, region);
+
+	let templates = StructureTemplate::in_pool(kind.start_pool()).collect::<Vec<_>>();
+	let template = *templates.choose(&mut rng)?;
+	let rotation = rng.gen_range(0..4);
+	let size = template.rotated_size(rotation);
+	let mut min = start - size / 2;
+	min.y = start.y - template.ground;
+
+	let surface = |x, z| get_height_at_with_seed(x, z, seed, settings);
+	let elevation = match kind.depth() {
+		Some(_) => Elevation::Fixed,
+		None => Elevation::Surface(&surface),
+	};
+	let start = Piece {
+		template,
+		rotation,
+		min,
+	};
+	Some(jigsaw::assemble(
+		start,
+		kind.max_depth(),
+		MAX_EXTENT,
+		&elevation,
+		&mut rng,
+	))
+}
+
+fn is_below_sea_level(settings: &WorldGenSettings, y: i32) -> bool {
+	match &settings.terrain {
+		TerrainSettings::Noise(noise) => noise.sea_level.is_some_and(|sea| y < sea),
+		_ => false,
+	}
+}
+
+/// decides randomly, but always the same way for the same position, whether a block is placed
+fn is_intact(kind: StructureKind, pos: IVec3) -> bool {
+	let integrity = kind.integrity();
+	if integrity >= 1.0 {
+		return true;
+	}
+	let [x, y, z] = pos.as_i64vec3().to_array().map(|v| v as u64);
+	let mut hash = x.wrapping_mul(0x9E3779B97F4A7C15) ^ y.wrapping_mul(0xC2B2AE3D27D4EB4F);
+	hash ^= z.wrapping_mul(0x165667B19E3779F9) ^ kind.salt();
+	hash ^= hash >> 29;
+	hash = hash.wrapping_mul(0xBF58476D1CE4E5B9);
+	hash ^= hash >> 32;
+	(hash % 1000) as f32 / 1000.0 < integrity
+}
+
+fn get_region_random(seed: Seed, kind: StructureKind, region: IVec2) -> StdRng {
+	let [x, z] = [region.x as u64, region.y as u64];
+	let mixed = x.wrapping_mul(341873128712) ^ z.wrapping_mul(132897987541);
+	StdRng::seed_from_u64(mixed ^ (seed as u64) ^ kind.salt())
+}
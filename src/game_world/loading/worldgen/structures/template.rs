@@ -0,0 +1,221 @@
+use crate::block::{Block, BlockId};
+use bevy::math::IVec3;
+use serde::Deserialize;
+use std::{collections::HashMap, error::Error, sync::LazyLock};
+
+/// the templates that are built into the game, as (name, RON source).<br>
+/// these are included at compile time, so world generation
+/// does not have to wait for the asset server.
+const BUILT_IN_TEMPLATES: [(&str, &str); 8] = [
+	(
+		"village/well",
+		include_str!("../../../../../assets/structures/village/well.ron"),
+	),
+	(
+		"village/street",
+		include_str!("../../../../../assets/structures/village/street.ron"),
+	),
+	(
+		"village/house",
+		include_str!("../../../../../assets/structures/village/house.ron"),
+	),
+	(
+		"village/farm",
+		include_str!("../../../../../assets/structures/village/farm.ron"),
+	),
+	(
+		"dungeon/room",
+		include_str!("../../../../../assets/structures/dungeon/room.ron"),
+	),
+	(
+		"dungeon/corridor",
+		include_str!("../../../../../assets/structures/dungeon/corridor.ron"),
+	),
+	(
+		"ruins/tower",
+		include_str!("../../../../../assets/structures/ruins/tower.ron"),
+	),
+	(
+		"ruins/wall",
+		include_str!("../../../../../assets/structures/ruins/wall.ron"),
+	),
+];
+
+static TEMPLATES: LazyLock<Vec<StructureTemplate>> = LazyLock::new(|| {
+	BUILT_IN_TEMPLATES
+		.iter()
+		.map(|(name, source)| {
+			StructureTemplate::from_ron(source)
+				.unwrap_or_else(|e| panic!("the structure template {} is invalid: {}", name, e))
+		})
+		.collect()
+});
+
+/// a piece of a structure, that can be connected to other pieces with its [`Connector`]s
+#[derive(Debug)]
+pub struct StructureTemplate {
+	/// the name of the group of templates this belongs to.
+	/// connectors choose a random template from a pool.
+	pub pool: String,
+	/// how many layers of this are below the surface, if it is placed on the surface
+	pub ground: i32,
+	pub size: IVec3,
+	/// `None` means that the block that was already there is kept
+	blocks: Vec<Option<Block>>,
+	pub connectors: Vec<Connector>,
+}
+
+/// a point on the side of a template, where another template can be attached
+#[derive(Debug, Clone, Deserialize)]
+pub struct Connector {
+	/// the position of the connector relative to the template
+	pub pos: [i32; 3],
+	/// the direction in which the attached template will be
+	pub facing: ConnectorFacing,
+	/// the pool that the attached template is chosen from
+	pub pool: String,
+}
+
+/// a horizontal direction, named the same way as [`Face`](crate::face::Face)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum ConnectorFacing {
+	Right,
+	Left,
+	Back,
+	Forward,
+}
+
+#[derive(Deserialize)]
+struct TemplateFile {
+	pool: String,
+	#[serde(default)]
+	ground: i32,
+	palette: HashMap<char, String>,
+	layers: Vec<Vec<String>>,
+	#[serde(default)]
+	connectors: Vec<Connector>,
+}
+
+impl StructureTemplate {
+	fn from_ron(source: &str) -> Result<Self, Box<dyn Error>> {
+		let file = ron::from_str::<TemplateFile>(source)?;
+
+		let size_y = file.layers.len();
+		let size_z = file.layers.first().map(|l| l.len()).unwrap_or(0);
+		let size_x = file
+			.layers
+			.first()
+			.and_then(|l| l.first())
+			.map(|r| r.chars().count())
+			.unwrap_or(0);
+		let mut blocks = Vec::with_capacity(size_x * size_y * size_z);
+		for layer in &file.layers {
+			if layer.len() != size_z {
+				return Err("all layers need to have the same amount of rows".into());
+			}
+			for row in layer {
+				if row.chars().count() != size_x {
+					return Err("all rows need to have the same length".into());
+				}
+				for c in row.chars() {
+					if c == '.' {
+						blocks.push(None);
+						continue;
+					}
+					let block_name = file
+						.palette
+						.get(&c)
+						.ok_or_else(|| format!("the character '{}' is not in the palette", c))?;
+					let id = BlockId::from_debug_name(block_name)
+						.ok_or_else(|| format!("uknown block name: {}", block_name))?;
+					blocks.push(Some(Block::from_id(id)));
+				}
+			}
+		}
+
+		Ok(Self {
+			pool: file.pool,
+			ground: file.ground,
+			size: IVec3::new(size_x as i32, size_y as i32, size_z as i32),
+			blocks,
+			connectors: file.connectors,
+		})
+	}
+
+	pub fn all() -> &'static [Self] {
+		&TEMPLATES
+	}
+
+	pub fn in_pool(pool: &str) -> impl Iterator<Item = &'static Self> + '_ {
+		Self::all().iter().filter(move |t| t.pool == pool)
+	}
+
+	/// gets the block at a position relative to the (unrotated) template
+	pub fn get_block(&self, pos: IVec3) -> Option<Block> {
+		if pos.cmplt(IVec3::ZERO).any() || pos.cmpge(self.size).any() {
+			return None;
+		}
+		let index = pos.x + pos.z * self.size.x + pos.y * self.size.x * self.size.z;
+		self.blocks[index as usize]
+	}
+
+	pub fn rotated_size(&self, quarter_turns: u8) -> IVec3 {
+		if quarter_turns % 2 == 0 {
+			self.size
+		} else {
+			IVec3::new(self.size.z, self.size.y, self.size.x)
+		}
+	}
+}
+
+impl ConnectorFacing {
+	pub fn normal(self) -> IVec3 {
+		match self {
+			Self::Right => IVec3::X,
+			Self::Left => -IVec3::X,
+			Self::Back => IVec3::Z,
+			Self::Forward => -IVec3::Z,
+		}
+	}
+
+	pub fn opposite(self) -> Self {
+		match self {
+			Self::Right => Self::Left,
+			Self::Left => Self::Right,
+			Self::Back => Self::Forward,
+			Self::Forward => Self::Back,
+		}
+	}
+
+	/// rotates this the same way that [`rotate_pos`] rotates positions
+	pub fn rotated(self, quarter_turns: u8) -> Self {
+		let mut facing = self;
+		for _ in 0..quarter_turns % 4 {
+			facing = match facing {
+				Self::Right => Self::Back,
+				Self::Back => Self::Left,
+				Self::Left => Self::Forward,
+				Self::Forward => Self::Right,
+			};
+		}
+		facing
+	}
+}
+
+/// rotates a position inside of a template of the given (unrotated) size around the y axis
+pub fn rotate_pos(mut pos: IVec3, mut size: IVec3, quarter_turns: u8) -> IVec3 {
+	for _ in 0..quarter_turns % 4 {
+		pos = IVec3::new(size.z - 1 - pos.z, pos.y, pos.x);
+		size = IVec3::new(size.z, size.y, size.x);
+	}
+	pos
+}
+
+/// the inverse of [`rotate_pos`], but takes the rotated size
+pub fn unrotate_pos(mut pos: IVec3, mut rotated_size: IVec3, quarter_turns: u8) -> IVec3 {
+	for _ in 0..quarter_turns % 4 {
+		rotated_size = IVec3::new(rotated_size.z, rotated_size.y, rotated_size.x);
+		pos = IVec3::new(pos.z, pos.y, rotated_size.z - 1 - pos.x);
+	}
+	pos
+}
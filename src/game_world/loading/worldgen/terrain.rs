@@ -1,13 +1,21 @@
-use super::Seed;
+use super::{
+	settings::{NoiseSettings, SuperflatSettings, TerrainSettings},
+	Seed,
+};
 use crate::{
-	block::prelude::*,
+	block::{fluid::FluidKind, prelude::*},
 	game_world::chunk::{BlockArray, Chunk, GenerationStage, IsLoaded, CHUNK_LENGTH},
-	pos::{BlockInChunkPos, ChunkPos},
+	pos::{BlockInChunkPos, BlockPos, ChunkPos},
 };
 use noise::{NoiseFn, Perlin};
 
 /// will create a new chunk with the [`Terrain`](GenerationStage::Terrain) GenerationStage.
-pub fn generate_chunk_terrain(chunk_pos: ChunkPos, seed: Seed, loaded: IsLoaded) -> Chunk {
+pub fn generate_chunk_terrain(
+	chunk_pos: ChunkPos,
+	seed: Seed,
+	settings: &TerrainSettings,
+	loaded: IsLoaded,
+) -> Chunk {
 	let mut chunk = Chunk {
 		blocks: Box::new(BlockArray::ALL_AIR),
 		loaded,
@@ -15,28 +23,50 @@ pub fn generate_chunk_terrain(chunk_pos: ChunkPos, seed: Seed, loaded: IsLoaded)
 		generation_state: GenerationStage::Terrain,
 	};
 
+	match settings {
+		TerrainSettings::Noise(noise) => generate_noise_terrain(&mut chunk, chunk_pos, seed, noise),
+		TerrainSettings::Superflat(superflat) => {
+			generate_superflat_terrain(&mut chunk, chunk_pos, superflat)
+		}
+		TerrainSettings::Void => generate_spawn_platform(&mut chunk, chunk_pos),
+	}
+
+	chunk
+}
+
+fn generate_noise_terrain(
+	chunk: &mut Chunk,
+	chunk_pos: ChunkPos,
+	seed: Seed,
+	settings: &NoiseSettings,
+) {
 	let perlin = Perlin::new(seed);
+	let dirt_depth = settings.dirt_depth as i32;
 
 	for x in 0..CHUNK_LENGTH as u8 {
 		for z in 0..CHUNK_LENGTH as u8 {
 			let world_pos = chunk_pos.to_block_pos();
 			let x_block = x as i32 + world_pos.x;
 			let z_block = z as i32 + world_pos.z;
-			let y_block = get_height_at(x_block, z_block, &perlin);
+			let y_block = get_height_at(x_block, z_block, &perlin, settings);
 			let y_in_chunk = y_block - world_pos.y;
 			let clamped = (y_in_chunk + 1).clamp(0, CHUNK_LENGTH as i32) as u8;
+			let is_underwater = settings.sea_level.is_some_and(|sea| y_block < sea);
 
 			for y in 0..clamped {
 				let diff = y as i32 - y_in_chunk;
 				let mut block = match diff {
-					..-3 => Stone::BLOCK,
-					-3..0 => Dirt::BLOCK,
+					1.. => {
+						// should be unreachable because y doesnt go this high
+						continue;
+					}
+					0 if is_underwater => Dirt::BLOCK,
 					0 => GrassBlock::BLOCK,
-					// should be unreachable because y doesnt go this high
-					1.. => continue,
+					diff if diff >= -dirt_depth => Dirt::BLOCK,
+					_ => Stone::BLOCK,
 				};
 				let block_pos = [x_block, y as i32 + world_pos.y, z_block];
-				if is_cave_air(block_pos, &perlin) {
+				if is_cave_air(block_pos, &perlin, settings) {
 					block = Air::BLOCK;
 				} else if block == Stone::BLOCK && is_random_cobblestone(block_pos, &perlin) {
 					block = Cobblestone::BLOCK;
@@ -44,24 +74,59 @@ pub fn generate_chunk_terrain(chunk_pos: ChunkPos, seed: Seed, loaded: IsLoaded)
 				let pos = BlockInChunkPos::new(x, y, z);
 				chunk.blocks[pos] = block;
 			}
+
+			if let Some(sea_level) = settings.sea_level {
+				let sea_in_chunk = sea_level - world_pos.y;
+				let water_top = (sea_in_chunk + 1).clamp(0, CHUNK_LENGTH as i32) as u8;
+				for y in clamped..water_top {
+					let pos = BlockInChunkPos::new(x, y, z);
+					chunk.blocks[pos] = FluidKind::Water.source_block();
+				}
+			}
 		}
 	}
+}
 
-	chunk
+fn generate_superflat_terrain(
+	chunk: &mut Chunk,
+	chunk_pos: ChunkPos,
+	settings: &SuperflatSettings,
+) {
+	let world_pos = chunk_pos.to_block_pos();
+	for y in 0..CHUNK_LENGTH as u8 {
+		let block = settings.get_block_at(y as i32 + world_pos.y);
+		if block == Air::BLOCK {
+			continue;
+		}
+		for x in 0..CHUNK_LENGTH as u8 {
+			for z in 0..CHUNK_LENGTH as u8 {
+				chunk.blocks[BlockInChunkPos::new(x, y, z)] = block;
+			}
+		}
+	}
 }
 
-pub fn get_height_at(x: i32, z: i32, perlin: &Perlin) -> i32 {
-	const HORIZONTAL_STRETCH_0: f64 = 74.379;
-	const VERTICAL_STRETCH_0: f64 = 23.748;
-	const HORIZONTAL_STRETCH_1: f64 = 21.174;
-	const VERTICAL_STRETCH_1: f64 = 4.849;
+/// places a small stone platform at y 0 around the spawn column,
+/// so that players in a void world don't fall forever
+fn generate_spawn_platform(chunk: &mut Chunk, chunk_pos: ChunkPos) {
+	const RADIUS: i32 = 2;
+	for x in 16 - RADIUS..=16 + RADIUS {
+		for z in 16 - RADIUS..=16 + RADIUS {
+			let pos = BlockPos::new(x, 0, z);
+			if pos.to_chunk_pos() == chunk_pos {
+				chunk.blocks[pos.to_block_in_chunk_pos()] = Stone::BLOCK;
+			}
+		}
+	}
+}
 
-	let x0 = x as f64 / HORIZONTAL_STRETCH_0;
-	let z0 = z as f64 / HORIZONTAL_STRETCH_0;
-	let y0 = perlin.get([x0, z0]) * VERTICAL_STRETCH_0;
-	let x1 = x as f64 / HORIZONTAL_STRETCH_1;
-	let z1 = z as f64 / HORIZONTAL_STRETCH_1;
-	let y1 = perlin.get([x1, z1]) * VERTICAL_STRETCH_1;
+pub fn get_height_at(x: i32, z: i32, perlin: &Perlin, settings: &NoiseSettings) -> i32 {
+	let x0 = x as f64 / settings.horizontal_stretch_0;
+	let z0 = z as f64 / settings.horizontal_stretch_0;
+	let y0 = perlin.get([x0, z0]) * settings.vertical_stretch_0;
+	let x1 = x as f64 / settings.horizontal_stretch_1;
+	let z1 = z as f64 / settings.horizontal_stretch_1;
+	let y1 = perlin.get([x1, z1]) * settings.vertical_stretch_1;
 	(y0 + y1) as i32
 }
 
@@ -72,9 +137,8 @@ fn is_random_cobblestone([x, y, z]: [i32; 3], perlin: &Perlin) -> bool {
 	perlin.get([x * STRETCH[0], y * STRETCH[1], z * STRETCH[2]]) > THRESHOLD
 }
 
-fn is_cave_air([x, y, z]: [i32; 3], perlin: &Perlin) -> bool {
-	const STRETCH: [f64; 3] = [0.058, 0.053, 0.050];
-	const THRESHOLD: f64 = 0.5;
+fn is_cave_air([x, y, z]: [i32; 3], perlin: &Perlin, settings: &NoiseSettings) -> bool {
+	let stretch = settings.cave_stretch;
 	let [x, y, z] = [x as f64, y as f64, z as f64];
-	perlin.get([x * STRETCH[0], y * STRETCH[1], z * STRETCH[2]]) > THRESHOLD
+	perlin.get([x * stretch[0], y * stretch[1], z * stretch[2]]) > settings.cave_threshold
 }
@@ -1,7 +1,8 @@
+use super::place_generated_block;
 use crate::{
 	block::prelude::*,
 	game_world::{
-		chunk::{Chunk, GenerationStage, IsLoaded, CHUNK_LENGTH},
+		chunk::{Chunk, CHUNK_LENGTH},
 		GameWorld,
 	},
 	pos::{BlockInChunkPos, BlockPos, ChunkPos},
@@ -9,9 +10,15 @@ use crate::{
 use bevy::math::IVec3;
 use rand::{prelude::StdRng, Rng, SeedableRng};
 
+/// places a tree on a grass block of the chunk. the chunk needs to have its terrain generated.<br>
+/// the leaves may reach into neighbouring chunks.
 pub fn generate_trees(world: &mut GameWorld, chunk_pos: ChunkPos) {
 	// TODO generate multiple trees instead of just one
 
+	if !world.worldgen_settings.trees {
+		return;
+	}
+
 	let x = get_random(chunk_pos.to_block_pos(), 7832957017391).gen_range(0..CHUNK_LENGTH as u8);
 	let z = get_random(chunk_pos.to_block_pos(), 9870402726984).gen_range(0..CHUNK_LENGTH as u8);
 	let Some(chunk) = world.chunks.get_mut(&chunk_pos) else {
@@ -25,14 +32,13 @@ pub fn generate_trees(world: &mut GameWorld, chunk_pos: ChunkPos) {
 		// this will happen if this chunk is in a cave or just the sky
 		return;
 	};
-	chunk.generation_state = GenerationStage::Trees;
 
 	let block_pos = chunk_pos.to_block_pos() + IVec3::new(x as i32, y as i32, z as i32);
-	place_block_at(world, block_pos, Dirt::BLOCK);
+	place_generated_block(world, block_pos, Dirt::BLOCK);
 	let height = get_random(block_pos, 8749103747).gen_range(4..7);
 	for i in 1..=height {
 		let pos = block_pos + IVec3::new(0, i, 0);
-		place_block_at(world, pos, Log::BLOCK);
+		place_generated_block(world, pos, Log::BLOCK);
 	}
 	let highest_pos = block_pos + IVec3::new(0, height, 0);
 
@@ -49,26 +55,7 @@ pub fn generate_trees(world: &mut GameWorld, chunk_pos: ChunkPos) {
 	];
 	for offset in leaf_offsets {
 		let pos = highest_pos + IVec3::from_array(offset);
-		place_block_at(world, pos, Leaves::BLOCK);
-	}
-}
-
-fn place_block_at(world: &mut GameWorld, block_pos: BlockPos, new_block: Block) {
-	if let Some(block) = world.get_block_at_mut(block_pos) {
-		*block = new_block;
-	} else {
-		let chunk_pos = block_pos.to_chunk_pos();
-		let loaded = IsLoaded::NOT_LOADED;
-		let chunk = super::generate_chunk_terrain(chunk_pos, world.seed, loaded);
-		world.chunks.insert(chunk_pos, chunk);
-		let Some(block) = world.get_block_at_mut(block_pos) else {
-			bevy::log::error!(
-				"somehow, generating a chunk at {} didnt allow placing a block at {}; skipping block placement",
-				chunk_pos, block_pos
-			);
-			return;
-		};
-		*block = new_block;
+		place_generated_block(world, pos, Leaves::BLOCK);
 	}
 }
 
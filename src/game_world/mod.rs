@@ -1,37 +1,64 @@
+mod block_tick;
 pub mod chunk;
+mod edit_history;
+mod fluid;
 mod loading;
+mod time;
 
-use self::{chunk::Chunk, loading::worldgen};
+use self::{block_tick::ScheduledTicks, chunk::Chunk, loading::worldgen};
 use crate::{
 	block::Block,
+	entity::player::GameMode,
+	item::Inventory,
 	pos::{BlockPos, ChunkPos, Vec3Utils},
 	savedata, GlobalState,
 };
-use bevy::{prelude::*, utils::HashMap};
+use bevy::{
+	prelude::*,
+	utils::{HashMap, HashSet},
+};
 use serde::{Deserialize, Serialize};
 
-pub use self::loading::worldgen::get_height_at_with_seed;
+pub use self::loading::worldgen::{
+	fully_generate_chunk, get_height_at_with_seed, locate_structure, StructureKind, WorldGenPreset,
+	WorldGenSettings,
+};
+pub use self::{
+	edit_history::EditHistory,
+	loading::{chunk_pos_in_render_distance, ChunkLoader, UpdateChunkIsLoadedEvent},
+	time::{FreezeTimeEvent, SetTimeEvent, WorldTime, TICKS_PER_DAY},
+};
 
 pub struct GameWorldPlugin;
 
 impl Plugin for GameWorldPlugin {
 	fn build(&self, app: &mut App) {
-		app.add_plugins((chunk::ChunkPlugin, loading::LoadingPlugin))
-			.add_event::<NewWorldEvent>()
-			.add_event::<JoinWorldEvent>()
-			.add_event::<LeaveWorldEvent>()
-			.add_systems(
-				Update,
-				(
-					(save_game_world, leave_game_world).run_if(in_state(GlobalState::InWorld)),
-					(new_game_world, join_game_world).run_if(in_state(GlobalState::MainMenu)),
-				),
-			);
+		app.add_plugins((
+			chunk::ChunkPlugin,
+			loading::LoadingPlugin,
+			block_tick::BlockTickPlugin,
+			time::WorldTimePlugin,
+			edit_history::EditHistoryPlugin,
+		))
+		.add_event::<NewWorldEvent>()
+		.add_event::<JoinWorldEvent>()
+		.add_event::<LeaveWorldEvent>()
+		.add_systems(
+			Update,
+			(
+				(save_game_world, leave_game_world).run_if(in_state(GlobalState::InWorld)),
+				(new_game_world, join_game_world).run_if(in_state(GlobalState::MainMenu)),
+			),
+		);
 	}
 }
 
 #[derive(Event)]
-pub struct NewWorldEvent;
+pub struct NewWorldEvent {
+	/// how the terrain of the new world should be generated
+	pub worldgen_settings: WorldGenSettings,
+	pub game_mode: GameMode,
+}
 
 #[derive(Event)]
 pub struct JoinWorldEvent;
@@ -45,11 +72,42 @@ pub struct GameWorld {
 	pub chunks: HashMap<ChunkPos, Chunk>,
 	/// a value used to generate new chunks
 	pub seed: worldgen::Seed,
+	/// decides what new chunks look like. is chosen when creating the world
+	pub worldgen_settings: WorldGenSettings,
+	/// blocks that will be updated in the future, like flowing fluids
+	pub scheduled_ticks: ScheduledTicks,
+	/// blocks that world generation will place in chunks that dont have their terrain yet
+	pub pending_edits: worldgen::PendingEdits,
+	/// structures that have already been assembled. can always be created again from the seed
+	#[serde(skip)]
+	pub structure_cache: worldgen::StructureCache,
+	/// the inventory of the player, which is copied from the player whenever it changes
+	pub player_inventory: Inventory,
+	/// the game mode that new players start with
+	pub game_mode: GameMode,
+	/// the game mode of the player, which is copied from the player whenever it changes
+	pub player_game_mode: GameMode,
+	/// the time of day, which decides where the sun is and how bright the sky is
+	pub time: WorldTime,
+	/// the changes to blocks that can be undone
+	#[serde(skip)]
+	pub history: EditHistory,
+	/// chunks that have to be redrawn because a block in them changed
+	#[serde(skip)]
+	pub changed_chunks: HashSet<ChunkPos>,
+	/// the blocks that were changed by this game during the current frame,
+	/// so that the changes can be sent to other players
+	#[serde(skip)]
+	pub changed_blocks: Vec<BlockPos>,
+	/// whether this is a copy of a world on a server.<br>
+	/// remote worlds are not generated, simulated or saved by this game.
+	#[serde(skip)]
+	pub is_remote: bool,
 	// TODO store entities and other stuff here
 }
 
 fn save_game_world(input: Res<ButtonInput<KeyCode>>, game_world: Res<GameWorld>) {
-	if input.just_pressed(KeyCode::KeyO) {
+	if input.just_pressed(KeyCode::KeyO) && !game_world.is_remote {
 		savedata::save_game_world("debug_world", &game_world).unwrap();
 	}
 }
@@ -59,8 +117,14 @@ fn new_game_world(
 	mut commands: Commands,
 	mut global_state: ResMut<NextState<GlobalState>>,
 ) {
-	for _ in events.read() {
-		commands.insert_resource(GameWorld::default());
+	for event in events.read() {
+		commands.insert_resource(GameWorld {
+			worldgen_settings: event.worldgen_settings.clone(),
+			player_inventory: Inventory::with_starting_items(),
+			game_mode: event.game_mode,
+			player_game_mode: event.game_mode,
+			..default()
+		});
 		global_state.set(GlobalState::InWorld);
 	}
 }
@@ -71,8 +135,13 @@ fn join_game_world(
 	mut global_state: ResMut<NextState<GlobalState>>,
 ) {
 	for _ in events.read() {
-		commands.insert_resource(savedata::load_game_world("debug_world").unwrap());
-		global_state.set(GlobalState::InWorld);
+		match savedata::load_game_world("debug_world") {
+			Ok(game_world) => {
+				commands.insert_resource(game_world);
+				global_state.set(GlobalState::InWorld);
+			}
+			Err(err) => error!("couldn't load the world: {err}"),
+		}
 	}
 }
 
@@ -83,13 +152,27 @@ fn leave_game_world(
 	game_world: Res<GameWorld>,
 ) {
 	for _ in events.read() {
-		savedata::save_game_world("debug_world", &game_world).unwrap();
+		if !game_world.is_remote {
+			savedata::save_game_world("debug_world", &game_world).unwrap();
+		}
 		global_state.set(GlobalState::MainMenu);
 		commands.remove_resource::<GameWorld>();
 	}
 }
 
+/// a run condition for systems that generate or simulate the world,
+/// which only happens if the world is not on a server
+pub fn is_local_world(game_world: Option<Res<GameWorld>>) -> bool {
+	game_world.is_some_and(|game_world| !game_world.is_remote)
+}
+
 impl GameWorld {
+	/// the position where players appear when joining the world or respawning
+	pub fn spawn_point(&self) -> Vec3 {
+		let height = get_height_at_with_seed(16, 16, self.seed, &self.worldgen_settings);
+		Vec3::new(16.5, height as f32 + 4., 16.5)
+	}
+
 	#[allow(dead_code)]
 	pub fn get_chunk_at_world_pos(&self, pos: Vec3) -> Option<&Chunk> {
 		self.chunks.get(&pos.to_chunk_pos())
@@ -115,3 +198,22 @@ impl GameWorld {
 		Some(&mut chunk.blocks[pos])
 	}
 }
+
+#[cfg(test)]
+impl GameWorld {
+	/// a world for tests, where the given chunks are loaded and only contain air
+	pub fn with_air_chunks(chunks: impl IntoIterator<Item = ChunkPos>) -> Self {
+		use self::chunk::{BlockArray, GenerationStage, IsLoaded};
+
+		let mut game_world = Self::default();
+		for pos in chunks {
+			let chunk = Chunk {
+				blocks: Box::new(BlockArray::ALL_AIR),
+				loaded: IsLoaded::SIMPLE_LOADED,
+				generation_state: GenerationStage::COMPLETE,
+			};
+			game_world.chunks.insert(pos, chunk);
+		}
+		game_world
+	}
+}
@@ -0,0 +1,177 @@
+//! the time of day in a world, which advances every tick and decides where the sun is
+
+use super::{block_tick::TICKS_PER_SECOND, GameWorld};
+use crate::{
+	console::{AddCommand, Args, Command, Param, ParamKind},
+	GlobalState,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{error::Error, f32::consts::TAU};
+
+pub struct WorldTimePlugin;
+
+impl Plugin for WorldTimePlugin {
+	fn build(&self, app: &mut App) {
+		app.add_event::<SetTimeEvent>()
+			.add_event::<FreezeTimeEvent>()
+			.add_command(TimeCommand)
+			.add_systems(
+				Update,
+				(advance_time, set_time, freeze_time).run_if(in_state(GlobalState::InWorld)),
+			);
+	}
+}
+
+/// how many ticks a full day and night take
+pub const TICKS_PER_DAY: u64 = 24000;
+
+/// how bright the sky is in the middle of the night, compared to the day
+const MIN_SKY_LIGHT: f32 = 0.2;
+
+/// the time that has passed in a world.<br>
+/// a day starts at sunrise, has noon after a quarter, sunset after half and midnight after three quarters.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorldTime {
+	/// how many ticks have passed since the world was created
+	ticks: u64,
+	/// whether the time stays the same
+	frozen: bool,
+}
+
+/// sets the time of the current day, in ticks since sunrise
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SetTimeEvent(pub u64);
+
+/// stops or resumes the time
+#[derive(Event, Debug, Clone, Copy)]
+pub struct FreezeTimeEvent(pub bool);
+
+impl WorldTime {
+	pub fn ticks(self) -> u64 {
+		self.ticks
+	}
+
+	pub fn is_frozen(self) -> bool {
+		self.frozen
+	}
+
+	/// how many days have passed
+	pub fn day(self) -> u64 {
+		self.ticks / TICKS_PER_DAY
+	}
+
+	/// how far the current day has progressed, from 0 at sunrise to 1 at the next sunrise
+	pub fn time_of_day(self) -> f32 {
+		(self.ticks % TICKS_PER_DAY) as f32 / TICKS_PER_DAY as f32
+	}
+
+	/// the direction from the player to the sun.<br>
+	/// the sun rises in the east (+x) and sets in the west (-x).
+	pub fn sun_direction(self) -> Vec3 {
+		let angle = self.time_of_day() * TAU;
+		Vec3::new(angle.cos(), angle.sin(), 0.0)
+	}
+
+	/// how much it is day, from 0 at night to 1 at day.<br>
+	/// this fades while the sun is close to the horizon.
+	pub fn daylight(self) -> f32 {
+		let sun_height = self.sun_direction().y;
+		((sun_height + 0.2) / 0.4).clamp(0.0, 1.0)
+	}
+
+	/// how bright the sky is, from [`MIN_SKY_LIGHT`] at night to 1 at day
+	pub fn sky_light(self) -> f32 {
+		MIN_SKY_LIGHT + (1.0 - MIN_SKY_LIGHT) * self.daylight()
+	}
+}
+
+fn advance_time(
+	mut game_world: ResMut<GameWorld>,
+	mut time_since_tick: Local<f32>,
+	time: Res<Time>,
+) {
+	*time_since_tick += time.delta_secs();
+	let ticks = (*time_since_tick * TICKS_PER_SECOND).floor();
+	*time_since_tick -= ticks / TICKS_PER_SECOND;
+	if !game_world.time.frozen && ticks > 0.0 {
+		game_world.time.ticks += ticks as u64;
+	}
+}
+
+fn set_time(mut events: EventReader<SetTimeEvent>, mut game_world: ResMut<GameWorld>) {
+	for SetTimeEvent(time_of_day) in events.read() {
+		let day = game_world.time.day();
+		game_world.time.ticks = day * TICKS_PER_DAY + time_of_day % TICKS_PER_DAY;
+	}
+}
+
+fn freeze_time(mut events: EventReader<FreezeTimeEvent>, mut game_world: ResMut<GameWorld>) {
+	for FreezeTimeEvent(frozen) in events.read() {
+		game_world.time.frozen = *frozen;
+	}
+}
+
+struct TimeCommand;
+
+impl Command for TimeCommand {
+	fn name(&self) -> &'static str {
+		"time"
+	}
+
+	fn description(&self) -> &'static str {
+		"shows, sets, freezes or unfreezes the time of day"
+	}
+
+	fn signatures(&self) -> &'static [&'static [Param]] {
+		const SIGNATURES: &[&[Param]] = &[
+			&[],
+			&[
+				Param::literal(&["set"]),
+				Param::new("ticks", ParamKind::Int),
+			],
+			&[
+				Param::literal(&["set"]),
+				Param::new(
+					"time",
+					ParamKind::OneOf(&["sunrise", "noon", "sunset", "midnight"]),
+				),
+			],
+			&[Param::literal(&["freeze"])],
+			&[Param::literal(&["unfreeze"])],
+		];
+		SIGNATURES
+	}
+
+	fn run(&self, args: &Args, world: &mut World) -> Result<String, Box<dyn Error>> {
+		if args.is_empty() {
+			let time = world.resource::<GameWorld>().time;
+			return Ok(format!(
+				"day {}, {} ticks since sunrise",
+				time.day(),
+				time.ticks() % TICKS_PER_DAY
+			));
+		}
+		match args.word(0) {
+			"freeze" => {
+				world.send_event(FreezeTimeEvent(true));
+				Ok("froze the time".to_owned())
+			}
+			"unfreeze" => {
+				world.send_event(FreezeTimeEvent(false));
+				Ok("unfroze the time".to_owned())
+			}
+			_ => {
+				let ticks = match args.get_word(1) {
+					Some("sunrise") => 0,
+					Some("noon") => TICKS_PER_DAY / 4,
+					Some("sunset") => TICKS_PER_DAY / 2,
+					Some(_) => TICKS_PER_DAY * 3 / 4,
+					None => u64::try_from(args.int(1)).map_err(|_| "the time can't be negative")?,
+				};
+				world.send_event(SetTimeEvent(ticks));
+				Ok(format!("set the time to {}", ticks % TICKS_PER_DAY))
+			}
+		}
+	}
+}
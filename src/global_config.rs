@@ -1,4 +1,4 @@
-use crate::savedata;
+use crate::{network::DEFAULT_PORT, savedata};
 use bevy::prelude::*;
 // `bevy_framepace` is not on bevy version 0.15 yet
 // use bevy_framepace::{FramepacePlugin, FramepaceSettings, Limiter};
@@ -19,6 +19,8 @@ impl Plugin for GlobalConfigPlugin {
 
 #[derive(Resource, Debug, Clone, Serialize, Deserialize, Reflect)]
 #[reflect(Resource)]
+// missing values are taken from the default, so that old configs keep working
+#[serde(default)]
 pub struct Config {
 	/// the radius of how many chunks to load around the player horizontally
 	pub horizontal_render_distance: u32,
@@ -26,8 +28,16 @@ pub struct Config {
 	pub vertical_render_distance: u32,
 	/// what to limit the fps to
 	pub fps_limit: Option<f64>,
+	/// how many times per second the physics of entities are updated
+	pub tick_rate: f64,
 	/// the field of view of the player camera
 	pub fov: f32,
+	/// how far the camera is away from the player's eyes in third person
+	pub third_person_distance: f32,
+	/// the name other players see when playing together
+	pub player_name: String,
+	/// the server that is joined with the join server button, like `127.0.0.1:25580`
+	pub server_address: String,
 }
 
 impl Default for Config {
@@ -36,7 +46,11 @@ impl Default for Config {
 			horizontal_render_distance: 3,
 			vertical_render_distance: 2,
 			fps_limit: Some(60.),
+			tick_rate: 60.,
 			fov: TAU / 8.,
+			third_person_distance: 4.,
+			player_name: "Player".to_owned(),
+			server_address: format!("127.0.0.1:{DEFAULT_PORT}"),
 		}
 	}
 }
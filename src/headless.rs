@@ -0,0 +1,159 @@
+//! runs a world without a window, so that players can join it on a server that has no GPU.<br>
+//! chunks are only loaded around the players that joined, and commands are typed into the terminal.
+
+use crate::{
+	console::{AddCommand, Args, Command, Param, StdinConsolePlugin},
+	game_world::{GameWorld, LeaveWorldEvent, WorldGenPreset},
+	item::Inventory,
+	network, savedata, GlobalState, SimulationPlugin,
+};
+use bevy::{app::ScheduleRunnerPlugin, log::LogPlugin, prelude::*, state::app::StatesPlugin};
+use std::{error::Error, time::Duration};
+
+/// how the server is started
+#[derive(Resource, Debug, Clone)]
+pub struct ServerOptions {
+	pub port: u16,
+	/// the world type of the world, if it doesn't exist yet
+	pub preset: String,
+	/// the seed of the world, if it doesn't exist yet
+	pub seed: u32,
+}
+
+impl Default for ServerOptions {
+	fn default() -> Self {
+		Self {
+			port: network::DEFAULT_PORT,
+			preset: "Default".to_owned(),
+			seed: 0,
+		}
+	}
+}
+
+/// how often the server updates every second
+const FRAMES_PER_SECOND: f64 = 60.;
+
+/// how many seconds pass between saving the world
+const AUTOSAVE_INTERVAL: f32 = 5. * 60.;
+
+/// the same world that the game uses
+const WORLD_NAME: &str = "debug_world";
+
+/// starts the server. this is called by the server binary
+pub fn run(options: ServerOptions) -> AppExit {
+	App::new()
+		.add_plugins((
+			MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(
+				1. / FRAMES_PER_SECOND,
+			))),
+			LogPlugin::default(),
+			StatesPlugin,
+			// never receives any input, but some systems check for keys
+			bevy::input::InputPlugin,
+			SimulationPlugin,
+			StdinConsolePlugin,
+		))
+		.insert_resource(options)
+		.insert_resource(AutosaveTimer(Timer::from_seconds(
+			AUTOSAVE_INTERVAL,
+			TimerMode::Repeating,
+		)))
+		.add_command(StopCommand)
+		.add_systems(OnEnter(GlobalState::Loading), open_world)
+		.add_systems(OnEnter(GlobalState::InWorld), host)
+		.add_systems(Update, autosave.run_if(in_state(GlobalState::InWorld)))
+		// the world is saved when leaving it, and there is no main menu to go back to
+		.add_systems(OnEnter(GlobalState::MainMenu), exit)
+		.run()
+}
+
+#[derive(Resource, Debug)]
+struct AutosaveTimer(Timer);
+
+/// loads the saved world, or creates a new one if there is none
+fn open_world(
+	mut commands: Commands,
+	mut global_state: ResMut<NextState<GlobalState>>,
+	options: Res<ServerOptions>,
+	mut exit_events: EventWriter<AppExit>,
+) {
+	let game_world = match savedata::load_game_world(WORLD_NAME) {
+		Ok(game_world) => {
+			info!("loaded the world {WORLD_NAME}");
+			game_world
+		}
+		Err(err) => {
+			info!("creating a new world, since {WORLD_NAME} couldn't be loaded: {err}");
+			match new_world(&options) {
+				Ok(game_world) => game_world,
+				Err(err) => {
+					error!("couldn't create the world: {err}");
+					exit_events.send(AppExit::error());
+					return;
+				}
+			}
+		}
+	};
+	commands.insert_resource(game_world);
+	global_state.set(GlobalState::InWorld);
+}
+
+fn new_world(options: &ServerOptions) -> Result<GameWorld, Box<dyn Error>> {
+	let preset = WorldGenPreset::all()
+		.into_iter()
+		.find(|preset| preset.to_string().eq_ignore_ascii_case(&options.preset))
+		.ok_or_else(|| format!("unknown world type: {}", options.preset))?;
+	Ok(GameWorld {
+		seed: options.seed,
+		worldgen_settings: preset.to_settings()?,
+		player_inventory: Inventory::with_starting_items(),
+		..default()
+	})
+}
+
+fn host(world: &mut World) {
+	let port = world.resource::<ServerOptions>().port;
+	match network::start_server(world, port) {
+		Ok(port) => info!("players can join on port {port}"),
+		Err(err) => {
+			error!("couldn't start the server on port {port}: {err}");
+			world.send_event(AppExit::error());
+		}
+	}
+}
+
+fn autosave(mut timer: ResMut<AutosaveTimer>, time: Res<Time>, game_world: Res<GameWorld>) {
+	if !timer.0.tick(time.delta()).just_finished() {
+		return;
+	}
+	match savedata::save_game_world(WORLD_NAME, &game_world) {
+		Ok(()) => info!("saved the world"),
+		Err(err) => error!("couldn't save the world: {err}"),
+	}
+}
+
+fn exit(mut exit_events: EventWriter<AppExit>) {
+	exit_events.send(AppExit::Success);
+}
+
+struct StopCommand;
+
+impl Command for StopCommand {
+	fn name(&self) -> &'static str {
+		"stop"
+	}
+
+	fn description(&self) -> &'static str {
+		"saves the world, disconnects all players and stops the server"
+	}
+
+	fn signatures(&self) -> &'static [&'static [Param]] {
+		const SIGNATURES: &[&[Param]] = &[&[]];
+		SIGNATURES
+	}
+
+	fn run(&self, _args: &Args, world: &mut World) -> Result<String, Box<dyn Error>> {
+		world.send_event(LeaveWorldEvent);
+		Ok("stopping the server".to_owned())
+	}
+}
@@ -1,6 +1,9 @@
 //! input handling for gamepad (controller / joystick)
 
-use super::{AttackInput, CrouchInput, InputSet, InteractInput, JumpInput, RotateInput, WalkInput};
+use super::{
+	AttackInput, CrouchInput, HotbarInput, InputSet, InteractInput, InventoryInput, JumpInput,
+	RotateInput, WalkInput,
+};
 use crate::savedata;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -20,6 +23,8 @@ impl Plugin for GamepadPlugin {
 					get_crouch_input,
 					get_attack_input,
 					get_interact_input,
+					get_hotbar_input,
+					get_inventory_input,
 				)
 					.in_set(InputSet::Get),),
 			);
@@ -57,6 +62,21 @@ fn get_rotate_input(
 	}
 }
 
+fn get_hotbar_input(
+	mut hotbar_input: ResMut<HotbarInput>,
+	controls: Res<Controls>,
+	gamepads: Query<&Gamepad>,
+) {
+	for gamepad in &gamepads {
+		if gamepad.just_pressed(controls.hotbar_left) {
+			hotbar_input.offset -= 1;
+		}
+		if gamepad.just_pressed(controls.hotbar_right) {
+			hotbar_input.offset += 1;
+		}
+	}
+}
+
 macro_rules! single_button {
 	($fn_name:ident, $input_res:ident, $control:ident) => {
 		fn $fn_name(
@@ -82,6 +102,7 @@ single_button! { get_jump_input, JumpInput, jump }
 single_button! { get_crouch_input, CrouchInput, crouch }
 single_button! { get_attack_input, AttackInput, attack }
 single_button! { get_interact_input, InteractInput, interact }
+single_button! { get_inventory_input, InventoryInput, inventory }
 
 #[derive(Resource, Debug, Clone, Serialize, Deserialize)]
 pub struct Controls {
@@ -89,6 +110,9 @@ pub struct Controls {
 	pub crouch: GamepadButton,
 	pub attack: GamepadButton,
 	pub interact: GamepadButton,
+	pub hotbar_left: GamepadButton,
+	pub hotbar_right: GamepadButton,
+	pub inventory: GamepadButton,
 	pub rotate_sensivity: f32,
 }
 
@@ -99,6 +123,9 @@ impl Default for Controls {
 			crouch: GamepadButton::LeftTrigger,
 			attack: GamepadButton::LeftTrigger2,
 			interact: GamepadButton::RightTrigger2,
+			hotbar_left: GamepadButton::DPadLeft,
+			hotbar_right: GamepadButton::DPadRight,
+			inventory: GamepadButton::North,
 			rotate_sensivity: 0.1,
 		}
 	}
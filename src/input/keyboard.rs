@@ -1,7 +1,9 @@
 //! input handling for keyboard
 
-use super::{CrouchInput, InputSet, JumpInput, WalkInput};
-use crate::savedata;
+use super::{
+	CrouchInput, HotbarInput, InputSet, InventoryInput, JumpInput, RedoInput, UndoInput, WalkInput,
+};
+use crate::{item::HOTBAR_SIZE, savedata};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::{error::Error, fs};
@@ -13,7 +15,15 @@ impl Plugin for KeyboardPlugin {
 		app.insert_resource(Controls::load().unwrap_or_default())
 			.add_systems(
 				Update,
-				(get_walk_input, get_jump_input, get_crouch_input).in_set(InputSet::Get),
+				(
+					get_walk_input,
+					get_jump_input,
+					get_crouch_input,
+					get_hotbar_input,
+					get_inventory_input,
+					get_edit_history_input,
+				)
+					.in_set(InputSet::Get),
 			);
 	}
 }
@@ -36,6 +46,42 @@ fn get_walk_input(
 	walk_input.vec = walk_input.vec.clamp_length_max(1.);
 }
 
+fn get_hotbar_input(
+	input: Res<ButtonInput<KeyCode>>,
+	mut hotbar_input: ResMut<HotbarInput>,
+	controls: Res<Controls>,
+) {
+	for (slot, key) in controls.hotbar.iter().enumerate() {
+		if input.just_pressed(*key) {
+			hotbar_input.slot = Some(slot);
+		}
+	}
+}
+
+/// undoing and redoing only happens while holding control, like in most programs
+fn get_edit_history_input(
+	input: Res<ButtonInput<KeyCode>>,
+	mut undo_input: ResMut<UndoInput>,
+	mut redo_input: ResMut<RedoInput>,
+	controls: Res<Controls>,
+) {
+	if !input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+		return;
+	}
+	if input.just_pressed(controls.undo) {
+		undo_input.started = true;
+	}
+	if input.pressed(controls.undo) {
+		undo_input.holding = true;
+	}
+	if input.just_pressed(controls.redo) {
+		redo_input.started = true;
+	}
+	if input.pressed(controls.redo) {
+		redo_input.holding = true;
+	}
+}
+
 macro_rules! single_button {
 	($fn_name:ident, $input_res:ident, $control:ident) => {
 		fn $fn_name(
@@ -57,6 +103,7 @@ macro_rules! single_button {
 
 single_button! { get_jump_input, JumpInput, jump }
 single_button! { get_crouch_input, CrouchInput, crouch }
+single_button! { get_inventory_input, InventoryInput, inventory }
 // single_button! { get_attack_input, AttackInput, attack }
 // single_button! { get_interact_input, InteractInputc, interact }
 
@@ -68,6 +115,13 @@ pub struct Controls {
 	pub left: KeyCode,
 	pub jump: KeyCode,
 	pub crouch: KeyCode,
+	/// the keys that select each slot of the hotbar
+	pub hotbar: [KeyCode; HOTBAR_SIZE],
+	pub inventory: KeyCode,
+	/// undoes the last change to the world, while holding control
+	pub undo: KeyCode,
+	/// redoes the last change that was undone, while holding control
+	pub redo: KeyCode,
 	// pub attack: KeyCode,
 	// pub interact: KeyCode,
 }
@@ -81,6 +135,20 @@ impl Default for Controls {
 			left: KeyCode::KeyA,
 			jump: KeyCode::Space,
 			crouch: KeyCode::ShiftLeft,
+			hotbar: [
+				KeyCode::Digit1,
+				KeyCode::Digit2,
+				KeyCode::Digit3,
+				KeyCode::Digit4,
+				KeyCode::Digit5,
+				KeyCode::Digit6,
+				KeyCode::Digit7,
+				KeyCode::Digit8,
+				KeyCode::Digit9,
+			],
+			inventory: KeyCode::Tab,
+			undo: KeyCode::KeyZ,
+			redo: KeyCode::KeyY,
 			// attack: KeyCode::???,
 			// interact: KeyCode::???,
 		}
@@ -19,10 +19,14 @@ impl Plugin for InputPlugin {
 		.insert_resource(WalkInput::default())
 		.insert_resource(RotateInput::default())
 		.insert_resource(ScrollInput::default())
+		.insert_resource(HotbarInput::default())
 		.insert_resource(JumpInput::default())
 		.insert_resource(CrouchInput::default())
 		.insert_resource(AttackInput::default())
 		.insert_resource(InteractInput::default())
+		.insert_resource(InventoryInput::default())
+		.insert_resource(UndoInput::default())
+		.insert_resource(RedoInput::default())
 		.add_systems(Update, cleanup_input_resources.in_set(InputSet::CleanUp))
 		.configure_sets(
 			Update,
@@ -77,6 +81,24 @@ impl ScrollInput {
 	}
 }
 
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct HotbarInput {
+	/// how many slots the selection should move, where positive values move it to the right
+	offset: i32,
+	/// a slot that was chosen directly, like with the number keys
+	slot: Option<usize>,
+}
+
+impl HotbarInput {
+	pub fn offset(&self) -> i32 {
+		self.offset
+	}
+
+	pub fn slot(&self) -> Option<usize> {
+		self.slot
+	}
+}
+
 macro_rules! single_button {
 	($name:ident, $doc_text:expr) => {
 		#[derive(Resource, Debug, Clone, Copy, Default)]
@@ -95,21 +117,33 @@ single_button! { JumpInput, "jump" }
 single_button! { CrouchInput, "crouch" }
 single_button! { AttackInput, "attack" }
 single_button! { InteractInput, "interact" }
+single_button! { InventoryInput, "open or close the inventory" }
+single_button! { UndoInput, "undo the last change to the world" }
+single_button! { RedoInput, "redo the last change that was undone" }
 
+#[allow(clippy::too_many_arguments)]
 fn cleanup_input_resources(
 	mut walk_input: ResMut<WalkInput>,
 	mut rotate_input: ResMut<RotateInput>,
 	mut scroll_input: ResMut<ScrollInput>,
+	mut hotbar_input: ResMut<HotbarInput>,
 	mut jump_input: ResMut<JumpInput>,
 	mut crouch_input: ResMut<CrouchInput>,
 	mut attack_input: ResMut<AttackInput>,
 	mut interact_input: ResMut<InteractInput>,
+	mut inventory_input: ResMut<InventoryInput>,
+	mut undo_input: ResMut<UndoInput>,
+	mut redo_input: ResMut<RedoInput>,
 ) {
 	*walk_input = WalkInput::default();
 	*rotate_input = RotateInput::default();
 	*scroll_input = ScrollInput::default();
+	*hotbar_input = HotbarInput::default();
 	*jump_input = JumpInput::default();
 	*crouch_input = CrouchInput::default();
 	*attack_input = AttackInput::default();
 	*interact_input = InteractInput::default();
+	*inventory_input = InventoryInput::default();
+	*undo_input = UndoInput::default();
+	*redo_input = RedoInput::default();
 }
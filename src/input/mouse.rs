@@ -1,9 +1,9 @@
 //! input handling for mouse
 
-use super::{AttackInput, InputSet, InteractInput, RotateInput, ScrollInput};
+use super::{AttackInput, HotbarInput, InputSet, InteractInput, RotateInput, ScrollInput};
 use crate::savedata;
 use bevy::{
-	input::mouse::{MouseMotion, MouseWheel},
+	input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
 	prelude::*,
 };
 use serde::{Deserialize, Serialize};
@@ -41,16 +41,45 @@ fn get_rotate_input(
 	}
 }
 
+/// how many pixels a touchpad has to scroll to move the hotbar selection by one slot
+const PIXELS_PER_SLOT: f32 = 50.;
+
+/// scrolling that hasn't added up to a whole hotbar slot yet, for each [`MouseScrollUnit`]
+#[derive(Debug, Default)]
+struct PartialScroll {
+	lines: f32,
+	pixels: f32,
+}
+
 fn get_scroll_input(
 	mut scroll_input: ResMut<ScrollInput>,
+	mut hotbar_input: ResMut<HotbarInput>,
 	mut mouse_scroll_events: EventReader<MouseWheel>,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	controls: Res<Controls>,
+	mut partial: Local<PartialScroll>,
 ) {
 	// FIXME this currently breaks scrolling in bevy_inspector_egui
 	// ...or i guess not?!? (it works now)
 
-	// currently ignores the scroll unit
 	for event in mouse_scroll_events.read() {
-		scroll_input.delta += event.y;
+		// horizontal scrolling doesn't do anything
+		if event.y == 0. {
+			continue;
+		}
+		if keyboard_input.pressed(controls.scroll_modifier) {
+			scroll_input.delta += event.y;
+			continue;
+		}
+		let (scrolled, delta) = match event.unit {
+			MouseScrollUnit::Line => (&mut partial.lines, event.y),
+			MouseScrollUnit::Pixel => (&mut partial.pixels, event.y / PIXELS_PER_SLOT),
+		};
+		*scrolled += delta;
+		let slots = scrolled.trunc();
+		*scrolled -= slots;
+		// scrolling down moves the selection to the right
+		hotbar_input.offset -= slots as i32;
 	}
 }
 
@@ -83,6 +112,8 @@ pub struct Controls {
 	pub sensitivity: f32,
 	pub attack: MouseButton,
 	pub interact: MouseButton,
+	/// while this is held, scrolling changes the flying speed instead of the selected hotbar slot
+	pub scroll_modifier: KeyCode,
 }
 
 impl Default for Controls {
@@ -91,6 +122,7 @@ impl Default for Controls {
 			sensitivity: 0.003,
 			attack: MouseButton::Left,
 			interact: MouseButton::Right,
+			scroll_modifier: KeyCode::ControlLeft,
 		}
 	}
 }
@@ -0,0 +1,125 @@
+use super::{Item, ItemStack};
+use crate::block::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// how many slots can be selected directly
+pub const HOTBAR_SIZE: usize = 9;
+/// how many slots there are in total, including the hotbar
+pub const INVENTORY_SIZE: usize = 4 * HOTBAR_SIZE;
+
+/// the items something is carrying.<br>
+/// the first [`HOTBAR_SIZE`] slots are the hotbar, and one of them is always selected.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Inventory {
+	slots: Vec<Option<ItemStack>>,
+	/// the index of the selected slot in the hotbar
+	selected: usize,
+}
+
+impl Default for Inventory {
+	fn default() -> Self {
+		Self {
+			slots: vec![None; INVENTORY_SIZE],
+			selected: 0,
+		}
+	}
+}
+
+impl Inventory {
+	/// the items that the player has when joining a new world
+	pub fn with_starting_items() -> Self {
+		let mut inventory = Self::default();
+		let blocks = [
+			Stone::BLOCK_ID,
+			GrassBlock::BLOCK_ID,
+			Dirt::BLOCK_ID,
+			Cobblestone::BLOCK_ID,
+			DebugBlock::BLOCK_ID,
+			DebugSlab::BLOCK_ID,
+			Log::BLOCK_ID,
+			Planks::BLOCK_ID,
+			Leaves::BLOCK_ID,
+		];
+		for id in blocks {
+			let item = Item::Block(id);
+			inventory.add(ItemStack::new(item, item.max_stack_size()));
+		}
+		inventory
+	}
+
+	/// all slots, where the first [`HOTBAR_SIZE`] slots are the hotbar
+	pub fn slots(&self) -> &[Option<ItemStack>] {
+		&self.slots
+	}
+
+	pub fn slots_mut(&mut self) -> &mut [Option<ItemStack>] {
+		&mut self.slots
+	}
+
+	pub fn hotbar(&self) -> &[Option<ItemStack>] {
+		&self.slots[..HOTBAR_SIZE]
+	}
+
+	pub fn selected_slot(&self) -> usize {
+		self.selected
+	}
+
+	pub fn select_slot(&mut self, slot: usize) {
+		self.selected = slot.min(HOTBAR_SIZE - 1);
+	}
+
+	/// moves the selected slot by `offset`, wrapping around at the ends of the hotbar
+	pub fn scroll(&mut self, offset: i32) {
+		self.selected = (self.selected as i32 + offset).rem_euclid(HOTBAR_SIZE as i32) as usize;
+	}
+
+	pub fn selected_stack(&self) -> Option<ItemStack> {
+		self.slots[self.selected]
+	}
+
+	/// removes a single item from the selected stack
+	pub fn take_selected(&mut self) -> Option<Item> {
+		let slot = &mut self.slots[self.selected];
+		let stack = slot.as_mut()?;
+		let item = stack.item;
+		stack.count -= 1;
+		if stack.count == 0 {
+			*slot = None;
+		}
+		Some(item)
+	}
+
+	/// adds as many of the items as possible, filling up stacks of the same item first.<br>
+	/// returns how many items did not fit
+	pub fn add(&mut self, stack: ItemStack) -> u32 {
+		let max = stack.item.max_stack_size();
+		let mut remaining = stack.count;
+
+		for existing in self.slots.iter_mut().flatten() {
+			if remaining == 0 {
+				break;
+			}
+			if existing.item != stack.item {
+				continue;
+			}
+			let moved = remaining.min(max.saturating_sub(existing.count));
+			existing.count += moved;
+			remaining -= moved;
+		}
+
+		for slot in &mut self.slots {
+			if remaining == 0 {
+				break;
+			}
+			if slot.is_some() {
+				continue;
+			}
+			let moved = remaining.min(max);
+			*slot = Some(ItemStack::new(stack.item, moved));
+			remaining -= moved;
+		}
+
+		remaining
+	}
+}
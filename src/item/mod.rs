@@ -0,0 +1,72 @@
+//! items are the things that can be carried around in an [`Inventory`]
+
+mod inventory;
+mod recipe;
+
+use crate::block::{prelude::*, BlockId};
+use serde::{Deserialize, Serialize};
+
+pub use self::{
+	inventory::{Inventory, HOTBAR_SIZE, INVENTORY_SIZE},
+	recipe::{load_recipes, CraftingGrid, Recipe},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Item {
+	/// a block that can be placed in the world
+	Block(BlockId),
+}
+
+/// multiple items of the same kind in a single inventory slot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemStack {
+	pub item: Item,
+	/// is always at least 1 and at most [`Item::max_stack_size`]
+	pub count: u32,
+}
+
+impl Item {
+	pub fn max_stack_size(self) -> u32 {
+		64
+	}
+
+	/// the block that is placed when using this item, if it can be placed
+	pub fn to_block(self) -> Option<Block> {
+		match self {
+			Self::Block(id) => Some(Block::from_id(id)),
+		}
+	}
+
+	/// how many times faster than by hand this item breaks the block.<br>
+	/// there are no tools yet, so every item is as fast as a hand
+	pub fn mining_speed(self, _block: Block) -> f32 {
+		match self {
+			Self::Block(_) => 1.0,
+		}
+	}
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Block(id) => id.to_debug_name(),
+		}
+	}
+
+	/// every item that exists. air is not an item
+	pub fn all() -> impl Iterator<Item = Self> {
+		BlockId::ALL
+			.into_iter()
+			.filter(|&id| id != Air::BLOCK_ID)
+			.map(Self::Block)
+	}
+
+	/// the inverse of [`Item::name`]
+	pub fn from_name(name: &str) -> Option<Self> {
+		BlockId::from_debug_name(name).map(Self::Block)
+	}
+}
+
+impl ItemStack {
+	pub fn new(item: Item, count: u32) -> Self {
+		Self { item, count }
+	}
+}
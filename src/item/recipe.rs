@@ -0,0 +1,421 @@
+//! crafting recipes, and finding the recipe that matches the items in a [`CraftingGrid`].<br>
+//! recipes are loaded from the RON files in `assets/recipes`.
+
+use super::{Item, ItemStack};
+use serde::Deserialize;
+use std::{collections::HashMap, error::Error, fs};
+
+/// turns the items in a crafting grid into a new item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recipe {
+	/// the items need to be arranged in a specific pattern,
+	/// but the pattern can be anywhere in the grid and may be mirrored
+	Shaped {
+		width: usize,
+		height: usize,
+		/// the rows of the pattern from top to bottom, where `None` has to be an empty slot
+		pattern: Vec<Option<Item>>,
+		result: ItemStack,
+	},
+	/// the items can be anywhere in the grid
+	Shapeless {
+		ingredients: Vec<Item>,
+		result: ItemStack,
+	},
+}
+
+/// a square grid of slots, that items are put into for crafting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CraftingGrid {
+	size: usize,
+	/// the rows of the grid from top to bottom
+	slots: Vec<Option<ItemStack>>,
+}
+
+#[derive(Deserialize)]
+enum RecipeFile {
+	Shaped {
+		/// each string is a row, and each character is a slot. a space is an empty slot.
+		pattern: Vec<String>,
+		key: HashMap<char, String>,
+		result: ResultFile,
+	},
+	Shapeless {
+		ingredients: Vec<String>,
+		result: ResultFile,
+	},
+}
+
+#[derive(Deserialize)]
+struct ResultFile {
+	item: String,
+	#[serde(default = "default_count")]
+	count: u32,
+}
+
+fn default_count() -> u32 {
+	1
+}
+
+impl Recipe {
+	pub fn from_ron(source: &str) -> Result<Self, Box<dyn Error>> {
+		let file = ron::from_str::<RecipeFile>(source)?;
+		match file {
+			RecipeFile::Shaped {
+				pattern,
+				key,
+				result,
+			} => {
+				let height = pattern.len();
+				let width = pattern.first().map(|row| row.chars().count()).unwrap_or(0);
+				if width == 0 || height == 0 {
+					return Err("the pattern can't be empty".into());
+				}
+				let mut slots = Vec::with_capacity(width * height);
+				for row in &pattern {
+					if row.chars().count() != width {
+						return Err("all rows of the pattern need to have the same length".into());
+					}
+					for c in row.chars() {
+						if c == ' ' {
+							slots.push(None);
+							continue;
+						}
+						let name = key
+							.get(&c)
+							.ok_or_else(|| format!("the character '{}' is not in the key", c))?;
+						slots.push(Some(parse_item(name)?));
+					}
+				}
+				Ok(Self::Shaped {
+					width,
+					height,
+					pattern: slots,
+					result: parse_result(result)?,
+				})
+			}
+			RecipeFile::Shapeless {
+				ingredients,
+				result,
+			} => {
+				if ingredients.is_empty() {
+					return Err("a shapeless recipe needs at least one ingredient".into());
+				}
+				let ingredients = ingredients
+					.iter()
+					.map(|name| parse_item(name))
+					.collect::<Result<Vec<_>, _>>()?;
+				Ok(Self::Shapeless {
+					ingredients,
+					result: parse_result(result)?,
+				})
+			}
+		}
+	}
+
+	pub fn result(&self) -> ItemStack {
+		match self {
+			Self::Shaped { result, .. } | Self::Shapeless { result, .. } => *result,
+		}
+	}
+
+	/// whether the items in the grid can be crafted with this recipe
+	pub fn matches(&self, grid: &CraftingGrid) -> bool {
+		match self {
+			Self::Shaped {
+				width,
+				height,
+				pattern,
+				..
+			} => {
+				let Some((min_x, min_y, max_x, max_y)) = grid.bounds() else {
+					return false;
+				};
+				if max_x - min_x + 1 != *width || max_y - min_y + 1 != *height {
+					return false;
+				}
+				let matches_pattern = |mirrored: bool| {
+					(0..*height).all(|y| {
+						(0..*width).all(|x| {
+							let pattern_x = if mirrored { width - 1 - x } else { x };
+							pattern[pattern_x + y * width] == grid.item_at(min_x + x, min_y + y)
+						})
+					})
+				};
+				matches_pattern(false) || matches_pattern(true)
+			}
+			Self::Shapeless { ingredients, .. } => {
+				let mut remaining = ingredients.clone();
+				for item in grid.slots.iter().flatten().map(|stack| stack.item) {
+					let Some(index) = remaining.iter().position(|&i| i == item) else {
+						return false;
+					};
+					remaining.swap_remove(index);
+				}
+				remaining.is_empty()
+			}
+		}
+	}
+}
+
+impl CraftingGrid {
+	/// creates an empty grid with `size * size` slots
+	pub fn new(size: usize) -> Self {
+		Self {
+			size,
+			slots: vec![None; size * size],
+		}
+	}
+
+	pub fn slots(&self) -> &[Option<ItemStack>] {
+		&self.slots
+	}
+
+	pub fn slots_mut(&mut self) -> &mut [Option<ItemStack>] {
+		&mut self.slots
+	}
+
+	fn item_at(&self, x: usize, y: usize) -> Option<Item> {
+		self.slots[x + y * self.size].map(|stack| stack.item)
+	}
+
+	/// the smallest rectangle containing all items, as `(min_x, min_y, max_x, max_y)`
+	fn bounds(&self) -> Option<(usize, usize, usize, usize)> {
+		let mut bounds: Option<(usize, usize, usize, usize)> = None;
+		for (i, slot) in self.slots.iter().enumerate() {
+			if slot.is_none() {
+				continue;
+			}
+			let [x, y] = [i % self.size, i / self.size];
+			bounds = Some(match bounds {
+				None => (x, y, x, y),
+				Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+			});
+		}
+		bounds
+	}
+
+	/// finds the first recipe that matches the items in this grid
+	pub fn find_recipe<'a>(&self, recipes: &'a [Recipe]) -> Option<&'a Recipe> {
+		recipes.iter().find(|recipe| recipe.matches(self))
+	}
+
+	/// removes a single item from every slot, after the result of a recipe was taken
+	pub fn consume_ingredients(&mut self) {
+		for slot in &mut self.slots {
+			if let Some(stack) = slot {
+				stack.count -= 1;
+				if stack.count == 0 {
+					*slot = None;
+				}
+			}
+		}
+	}
+
+	/// removes all items from the grid
+	pub fn take_all(&mut self) -> Vec<ItemStack> {
+		self.slots.iter_mut().filter_map(Option::take).collect()
+	}
+}
+
+/// loads all recipes in `assets/recipes`
+pub fn load_recipes() -> Result<Vec<Recipe>, Box<dyn Error>> {
+	let mut paths = fs::read_dir("assets/recipes")?
+		.map(|entry| entry.map(|entry| entry.path()))
+		.collect::<Result<Vec<_>, _>>()?;
+	// the first matching recipe is used, so the order should always be the same
+	paths.sort();
+
+	let mut recipes = Vec::new();
+	for path in paths.into_iter().filter(|path| path.is_file()) {
+		let source = fs::read_to_string(&path)?;
+		let recipe = Recipe::from_ron(&source)
+			.map_err(|e| format!("the recipe {} is invalid: {}", path.display(), e))?;
+		recipes.push(recipe);
+	}
+	Ok(recipes)
+}
+
+fn parse_item(name: &str) -> Result<Item, Box<dyn Error>> {
+	Item::from_name(name).ok_or_else(|| format!("unknown item name: {}", name).into())
+}
+
+fn parse_result(result: ResultFile) -> Result<ItemStack, Box<dyn Error>> {
+	let item = parse_item(&result.item)?;
+	if result.count == 0 || result.count > item.max_stack_size() {
+		return Err(format!("invalid result count: {}", result.count).into());
+	}
+	Ok(ItemStack::new(item, result.count))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn item(name: &str) -> Item {
+		Item::from_name(name).unwrap()
+	}
+
+	/// creates a grid from rows of item names, where `""` is an empty slot
+	fn grid(rows: &[&[&str]]) -> CraftingGrid {
+		let mut grid = CraftingGrid::new(rows.len());
+		for (y, row) in rows.iter().enumerate() {
+			for (x, name) in row.iter().enumerate() {
+				if !name.is_empty() {
+					grid.slots_mut()[x + y * rows.len()] = Some(ItemStack::new(item(name), 1));
+				}
+			}
+		}
+		grid
+	}
+
+	fn planks_recipe() -> Recipe {
+		Recipe::from_ron(
+			r###"Shapeless(ingredients: ["Log"], result: (item: "Planks", count: 4))"###,
+		)
+		.unwrap()
+	}
+
+	/// an L shape, which is different when mirrored
+	fn shaped_recipe() -> Recipe {
+		Recipe::from_ron(
+			r###"Shaped(
+				pattern: [
+					"# ",
+					"##",
+				],
+				key: {'#': "Cobblestone"},
+				result: (item: "Stone"),
+			)"###,
+		)
+		.unwrap()
+	}
+
+	#[test]
+	fn parses_shapeless_recipe() {
+		assert_eq!(
+			planks_recipe(),
+			Recipe::Shapeless {
+				ingredients: vec![item("Log")],
+				result: ItemStack::new(item("Planks"), 4),
+			}
+		);
+	}
+
+	#[test]
+	fn parses_shaped_recipe() {
+		let c = Some(item("Cobblestone"));
+		assert_eq!(
+			shaped_recipe(),
+			Recipe::Shaped {
+				width: 2,
+				height: 2,
+				pattern: vec![c, None, c, c],
+				result: ItemStack::new(item("Stone"), 1),
+			}
+		);
+	}
+
+	#[test]
+	fn rejects_invalid_recipes() {
+		let unknown_key =
+			r###"Shaped(pattern: ["#x"], key: {'#': "Log"}, result: (item: "Planks"))"###;
+		assert!(Recipe::from_ron(unknown_key).is_err());
+		let uneven_rows =
+			r###"Shaped(pattern: ["##", "#"], key: {'#': "Log"}, result: (item: "Planks"))"###;
+		assert!(Recipe::from_ron(uneven_rows).is_err());
+		let unknown_item = r###"Shapeless(ingredients: ["Diamond"], result: (item: "Planks"))"###;
+		assert!(Recipe::from_ron(unknown_item).is_err());
+		let no_ingredients = r###"Shapeless(ingredients: [], result: (item: "Planks"))"###;
+		assert!(Recipe::from_ron(no_ingredients).is_err());
+		let zero_count =
+			r###"Shapeless(ingredients: ["Log"], result: (item: "Planks", count: 0))"###;
+		assert!(Recipe::from_ron(zero_count).is_err());
+	}
+
+	#[test]
+	fn shapeless_matches_anywhere() {
+		let recipe = planks_recipe();
+		assert!(recipe.matches(&grid(&[&["Log", ""], &["", ""]])));
+		assert!(recipe.matches(&grid(&[&["", "", ""], &["", "", ""], &["", "", "Log"]])));
+	}
+
+	#[test]
+	fn shapeless_needs_exact_ingredients() {
+		let recipe = planks_recipe();
+		assert!(!recipe.matches(&grid(&[&["", ""], &["", ""]])));
+		assert!(!recipe.matches(&grid(&[&["Log", "Log"], &["", ""]])));
+		assert!(!recipe.matches(&grid(&[&["Log", "Dirt"], &["", ""]])));
+		assert!(!recipe.matches(&grid(&[&["Stone", ""], &["", ""]])));
+	}
+
+	#[test]
+	fn shaped_matches_at_any_offset() {
+		let recipe = shaped_recipe();
+		let c = "Cobblestone";
+		assert!(recipe.matches(&grid(&[&[c, ""], &[c, c]])));
+		assert!(recipe.matches(&grid(&[&["", "", ""], &["", c, ""], &["", c, c]])));
+		assert!(recipe.matches(&grid(&[&[c, "", ""], &[c, c, ""], &["", "", ""]])));
+	}
+
+	#[test]
+	fn shaped_matches_mirrored() {
+		let recipe = shaped_recipe();
+		let c = "Cobblestone";
+		assert!(recipe.matches(&grid(&[&["", c], &[c, c]])));
+		// upside down is not the same as mirrored
+		assert!(!recipe.matches(&grid(&[&[c, c], &[c, ""]])));
+	}
+
+	#[test]
+	fn shaped_rejects_wrong_items() {
+		let recipe = shaped_recipe();
+		let c = "Cobblestone";
+		assert!(!recipe.matches(&grid(&[&[c, ""], &[c, "Dirt"]])));
+		assert!(!recipe.matches(&grid(&[&[c, c], &[c, c]])));
+		assert!(!recipe.matches(&grid(&[&[c, "", ""], &[c, c, ""], &["", "", c]])));
+		assert!(!recipe.matches(&grid(&[&["", ""], &["", ""]])));
+	}
+
+	#[test]
+	fn shaped_does_not_fit_into_smaller_grid() {
+		let recipe = Recipe::from_ron(
+			r####"Shaped(pattern: ["###"], key: {'#': "Planks"}, result: (item: "Log"))"####,
+		)
+		.unwrap();
+		let p = "Planks";
+		assert!(!recipe.matches(&grid(&[&[p, p], &["", ""]])));
+		assert!(recipe.matches(&grid(&[&["", "", ""], &[p, p, p], &["", "", ""]])));
+	}
+
+	#[test]
+	fn finds_first_matching_recipe() {
+		let recipes = [shaped_recipe(), planks_recipe()];
+		let found = grid(&[&["", ""], &["Log", ""]]).find_recipe(&recipes);
+		assert_eq!(found, Some(&recipes[1]));
+		assert_eq!(
+			grid(&[&["", ""], &["Dirt", ""]]).find_recipe(&recipes),
+			None
+		);
+	}
+
+	#[test]
+	fn consumes_one_item_per_slot() {
+		let mut grid = grid(&[&["Log", ""], &["", "Log"]]);
+		grid.slots_mut()[0] = Some(ItemStack::new(item("Log"), 3));
+		grid.consume_ingredients();
+		assert_eq!(grid.slots()[0], Some(ItemStack::new(item("Log"), 2)));
+		assert_eq!(grid.slots()[3], None);
+	}
+
+	#[test]
+	fn built_in_recipes_are_valid() {
+		let recipes = load_recipes().unwrap();
+		assert!(!recipes.is_empty());
+		let planks = grid(&[&["Log", ""], &["", ""]]).find_recipe(&recipes);
+		assert_eq!(
+			planks.map(Recipe::result),
+			Some(ItemStack::new(item("Planks"), 4))
+		);
+	}
+}
@@ -17,6 +17,7 @@
 /// # Examples
 ///
 /// ```
+/// # use voxel_game::match_min;
 /// let x = match_min! {
 ///     3.48 => { 0 }
 ///     -1.2 => { 1 }
@@ -55,6 +56,7 @@ macro_rules! match_min {
 /// # Examples
 ///
 /// ```
+/// # use voxel_game::match_max;
 /// let x = match_max! {
 ///     3.48 => { 0 }
 ///     -1.2 => { 1 }
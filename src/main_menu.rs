@@ -1,5 +1,8 @@
 use crate::{
-	game_world::{JoinWorldEvent, NewWorldEvent},
+	entity::player::GameMode,
+	game_world::{JoinWorldEvent, NewWorldEvent, WorldGenPreset},
+	global_config::Config,
+	network::JoinServerEvent,
 	GlobalState,
 };
 use bevy::prelude::*;
@@ -12,7 +15,13 @@ impl Plugin for MainMenuPlugin {
 			.add_systems(OnExit(GlobalState::MainMenu), despawn)
 			.add_systems(
 				Update,
-				(click_new_world_button, click_start_button)
+				(
+					click_new_world_button,
+					click_start_button,
+					click_join_server_button,
+					click_world_type_button,
+					click_game_mode_button,
+				)
 					.run_if(in_state(GlobalState::MainMenu)),
 			);
 	}
@@ -30,9 +39,50 @@ struct StartButton;
 #[derive(Component)]
 struct NewWorldButton;
 
+#[derive(Component)]
+struct JoinServerButton;
+
+#[derive(Component)]
+struct WorldTypeButton;
+
+#[derive(Component)]
+struct WorldTypeText;
+
+#[derive(Component)]
+struct GameModeButton;
+
+#[derive(Component)]
+struct GameModeText;
+
+/// the game mode that is chosen for a new world
+#[derive(Resource, Debug, Default)]
+struct SelectedGameMode(GameMode);
+
+/// the presets that can be chosen for a new world, and which one is currently selected
+#[derive(Resource, Debug)]
+struct WorldGenPresets {
+	presets: Vec<WorldGenPreset>,
+	selected: usize,
+}
+
+impl WorldGenPresets {
+	fn selected(&self) -> &WorldGenPreset {
+		&self.presets[self.selected]
+	}
+}
+
 fn spawn(mut commands: Commands) {
 	commands.spawn((MainMenuCamera, Camera3d::default()));
 
+	let presets = WorldGenPresets {
+		presets: WorldGenPreset::all(),
+		selected: 0,
+	};
+	let world_type_text = format!("World Type: {}", presets.selected());
+	commands.insert_resource(presets);
+	let game_mode_text = format!("Game Mode: {}", GameMode::default());
+	commands.insert_resource(SelectedGameMode::default());
+
 	commands
 		.spawn((
 			MainMenuRoot,
@@ -67,6 +117,50 @@ fn spawn(mut commands: Commands) {
 						TextFont::from_font_size(20.),
 					));
 				});
+			parent
+				.spawn((
+					WorldTypeButton,
+					Button,
+					Node {
+						width: Val::VMin(30.),
+						height: Val::VMin(10.),
+						margin: UiRect::all(Val::VMin(1.)),
+						justify_content: JustifyContent::Center,
+						align_items: AlignItems::Center,
+						..default()
+					},
+					BackgroundColor::from(Color::srgb(0.15, 0.15, 0.15)),
+				))
+				.with_children(|parent| {
+					parent.spawn((
+						WorldTypeText,
+						Text::new(world_type_text),
+						TextColor::from(Color::WHITE),
+						TextFont::from_font_size(20.),
+					));
+				});
+			parent
+				.spawn((
+					GameModeButton,
+					Button,
+					Node {
+						width: Val::VMin(30.),
+						height: Val::VMin(10.),
+						margin: UiRect::all(Val::VMin(1.)),
+						justify_content: JustifyContent::Center,
+						align_items: AlignItems::Center,
+						..default()
+					},
+					BackgroundColor::from(Color::srgb(0.15, 0.15, 0.15)),
+				))
+				.with_children(|parent| {
+					parent.spawn((
+						GameModeText,
+						Text::new(game_mode_text),
+						TextColor::from(Color::WHITE),
+						TextFont::from_font_size(20.),
+					));
+				});
 			parent
 				.spawn((
 					StartButton,
@@ -88,6 +182,27 @@ fn spawn(mut commands: Commands) {
 						TextFont::from_font_size(20.),
 					));
 				});
+			parent
+				.spawn((
+					JoinServerButton,
+					Button,
+					Node {
+						width: Val::VMin(20.),
+						height: Val::VMin(10.),
+						margin: UiRect::all(Val::VMin(1.)),
+						justify_content: JustifyContent::Center,
+						align_items: AlignItems::Center,
+						..default()
+					},
+					BackgroundColor::from(Color::srgb(0.15, 0.15, 0.15)),
+				))
+				.with_children(|parent| {
+					parent.spawn((
+						Text::new("Join Server"),
+						TextColor::from(Color::WHITE),
+						TextFont::from_font_size(20.),
+					));
+				});
 		});
 }
 
@@ -102,15 +217,56 @@ fn despawn(
 	for cam in cams.iter() {
 		commands.entity(cam).despawn();
 	}
+	commands.remove_resource::<WorldGenPresets>();
+	commands.remove_resource::<SelectedGameMode>();
 }
 
 fn click_new_world_button(
 	mut join_event: EventWriter<NewWorldEvent>,
 	intercation_query: Query<&Interaction, (Changed<Interaction>, With<NewWorldButton>)>,
+	presets: Res<WorldGenPresets>,
+	game_mode: Res<SelectedGameMode>,
+) {
+	for interaction in intercation_query.iter() {
+		if interaction == &Interaction::Pressed {
+			let preset = presets.selected();
+			match preset.to_settings() {
+				Ok(worldgen_settings) => {
+					join_event.send(NewWorldEvent {
+						worldgen_settings,
+						game_mode: game_mode.0,
+					});
+				}
+				Err(err) => error!("couldn't load world type {}: {}", preset, err),
+			}
+		}
+	}
+}
+
+fn click_world_type_button(
+	intercation_query: Query<&Interaction, (Changed<Interaction>, With<WorldTypeButton>)>,
+	mut text: Query<&mut Text, With<WorldTypeText>>,
+	mut presets: ResMut<WorldGenPresets>,
 ) {
 	for interaction in intercation_query.iter() {
 		if interaction == &Interaction::Pressed {
-			join_event.send(NewWorldEvent);
+			presets.selected = (presets.selected + 1) % presets.presets.len();
+			let mut text = text.single_mut();
+			text.0 = format!("World Type: {}", presets.selected());
+		}
+	}
+}
+
+fn click_game_mode_button(
+	intercation_query: Query<&Interaction, (Changed<Interaction>, With<GameModeButton>)>,
+	mut text: Query<&mut Text, With<GameModeText>>,
+	mut game_mode: ResMut<SelectedGameMode>,
+) {
+	for interaction in intercation_query.iter() {
+		if interaction == &Interaction::Pressed {
+			game_mode.0 = game_mode.0.next();
+			let mut text = text.single_mut();
+			text.0 = format!("Game Mode: {}", game_mode.0);
 		}
 	}
 }
@@ -125,3 +281,18 @@ fn click_start_button(
 		}
 	}
 }
+
+/// joins the server from the config
+fn click_join_server_button(
+	mut join_event: EventWriter<JoinServerEvent>,
+	intercation_query: Query<&Interaction, (Changed<Interaction>, With<JoinServerButton>)>,
+	config: Res<Config>,
+) {
+	for interaction in intercation_query.iter() {
+		if interaction == &Interaction::Pressed {
+			join_event.send(JoinServerEvent {
+				address: config.server_address.clone(),
+			});
+		}
+	}
+}
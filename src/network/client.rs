@@ -0,0 +1,338 @@
+//! joining a world that is hosted by another game.<br>
+//! the world is only a copy of what the server sends, and changes are sent to the server.
+
+use super::{
+	connection::Connection,
+	interpolation::{Snapshot, Snapshots},
+	protocol::{self, ClientMessage, ServerMessage, PROTOCOL_VERSION},
+	remote_player_bundle, RemotePlayer, PLAYER_UPDATES_PER_SECOND,
+};
+use crate::{
+	entity::{player::Player, LookDirection},
+	game_world::{
+		chunk::{Chunk, ChunkUpdateEvent, GenerationStage, IsLoaded},
+		GameWorld, LeaveWorldEvent, UpdateChunkIsLoadedEvent,
+	},
+	global_config::Config,
+	item::Inventory,
+	GlobalState,
+};
+use bevy::{
+	prelude::*,
+	tasks::{block_on, IoTaskPool, Task},
+};
+use std::{
+	io::{self, ErrorKind},
+	net::{TcpStream, ToSocketAddrs},
+	time::Duration,
+};
+
+pub struct ClientPlugin;
+
+impl Plugin for ClientPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_event::<JoinServerEvent>()
+			.add_systems(
+				Update,
+				(
+					(connect_to_server, finish_connecting, receive_welcome)
+						.chain()
+						.run_if(in_state(GlobalState::MainMenu)),
+					receive_server_messages
+						.run_if(in_state(GlobalState::InWorld))
+						.run_if(resource_exists::<Client>),
+				),
+			)
+			.add_systems(
+				PostUpdate,
+				(send_block_changes, send_player_state, flush_client)
+					.chain()
+					.run_if(in_state(GlobalState::InWorld))
+					.run_if(resource_exists::<Client>),
+			)
+			.add_systems(OnExit(GlobalState::InWorld), leave_server);
+	}
+}
+
+/// connects to a server, and joins its world once the server answers
+#[derive(Event, Debug, Clone)]
+pub struct JoinServerEvent {
+	/// the address of the server, like `127.0.0.1:25580`
+	pub address: String,
+}
+
+/// how long to wait for a server to accept the connection
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Resource)]
+struct Client {
+	connection: Connection,
+	player_update_timer: Timer,
+}
+
+/// a connection to a server that is still being made.<br>
+/// looking up the address and connecting can take seconds, so it happens in a task
+#[derive(Resource)]
+struct Connecting {
+	address: String,
+	task: Task<io::Result<TcpStream>>,
+}
+
+fn connect(address: &str) -> io::Result<TcpStream> {
+	let address = address.to_socket_addrs()?.next().ok_or_else(|| {
+		io::Error::new(
+			ErrorKind::NotFound,
+			"the address doesn't belong to any server",
+		)
+	})?;
+	TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)
+}
+
+fn connect_to_server(mut events: EventReader<JoinServerEvent>, mut commands: Commands) {
+	for event in events.read() {
+		info!("connecting to {}", event.address);
+		let address = event.address.clone();
+		let task = IoTaskPool::get().spawn(async move { connect(&address) });
+		commands.insert_resource(Connecting {
+			address: event.address.clone(),
+			task,
+		});
+	}
+}
+
+/// says hello to the server once the connection is made
+fn finish_connecting(
+	mut commands: Commands,
+	connecting: Option<ResMut<Connecting>>,
+	config: Res<Config>,
+) {
+	let Some(mut connecting) = connecting else {
+		return;
+	};
+	if !connecting.task.is_finished() {
+		return;
+	}
+	commands.remove_resource::<Connecting>();
+	let connection = block_on(&mut connecting.task).and_then(Connection::new);
+	let mut connection = match connection {
+		Ok(connection) => connection,
+		Err(err) => {
+			error!("couldn't connect to {}: {err}", connecting.address);
+			return;
+		}
+	};
+	connection.send(&ClientMessage::Hello {
+		version: PROTOCOL_VERSION,
+		name: config.player_name.clone(),
+	});
+	commands.insert_resource(Client {
+		connection,
+		player_update_timer: Timer::from_seconds(
+			1. / PLAYER_UPDATES_PER_SECOND,
+			TimerMode::Repeating,
+		),
+	});
+}
+
+/// waits for the server to answer, and then joins its world
+fn receive_welcome(
+	mut commands: Commands,
+	client: Option<ResMut<Client>>,
+	mut global_state: ResMut<NextState<GlobalState>>,
+) {
+	let Some(mut client) = client else {
+		return;
+	};
+	// sends the hello
+	client.connection.flush();
+	match client.connection.receive() {
+		Some(ServerMessage::Welcome {
+			player_id,
+			seed,
+			worldgen_settings,
+			game_mode,
+			time,
+		}) => {
+			info!("joined the server as player {player_id}");
+			commands.insert_resource(GameWorld {
+				seed,
+				worldgen_settings,
+				player_inventory: Inventory::with_starting_items(),
+				game_mode,
+				player_game_mode: game_mode,
+				time,
+				is_remote: true,
+				..default()
+			});
+			global_state.set(GlobalState::InWorld);
+		}
+		Some(ServerMessage::Disconnect { reason }) => {
+			error!("the server refused to let you join: {reason}");
+			commands.remove_resource::<Client>();
+		}
+		Some(message) => {
+			error!("expected a welcome from the server, but got {message:?}");
+			commands.remove_resource::<Client>();
+		}
+		None => {
+			if let Some(reason) = client.connection.closed_reason() {
+				error!("lost the connection to the server: {reason}");
+				commands.remove_resource::<Client>();
+			}
+		}
+	}
+}
+
+#[allow(clippy::too_many_arguments)]
+fn receive_server_messages(
+	mut commands: Commands,
+	mut client: ResMut<Client>,
+	mut game_world: ResMut<GameWorld>,
+	mut remote_players: Query<(Entity, &RemotePlayer, &mut Snapshots)>,
+	mut loaded_events: EventWriter<UpdateChunkIsLoadedEvent>,
+	mut chunk_updates: EventWriter<ChunkUpdateEvent>,
+	mut leave_events: EventWriter<LeaveWorldEvent>,
+	time: Res<Time>,
+) {
+	while let Some(message) = client.connection.receive() {
+		match message {
+			ServerMessage::Welcome { .. } => warn!("the server sent a second welcome"),
+			ServerMessage::Disconnect { reason } => {
+				info!("the server disconnected you: {reason}");
+				leave_events.send(LeaveWorldEvent);
+				commands.remove_resource::<Client>();
+				return;
+			}
+			ServerMessage::Chunk { pos, blocks } => {
+				let blocks = match protocol::decompress_chunk(&blocks) {
+					Ok(blocks) => blocks,
+					Err(err) => {
+						warn!("the server sent a broken chunk at {pos}: {err}");
+						continue;
+					}
+				};
+				let old_is_loaded = game_world
+					.chunks
+					.get(&pos)
+					.map_or(IsLoaded::NOT_LOADED, |chunk| chunk.loaded);
+				let mut loaded = old_is_loaded;
+				loaded.set_simple_loaded(true);
+				game_world.chunks.insert(
+					pos,
+					Chunk {
+						blocks,
+						loaded,
+						generation_state: GenerationStage::COMPLETE,
+					},
+				);
+				if old_is_loaded.is_visible() {
+					chunk_updates.send(ChunkUpdateEvent { chunk_pos: pos });
+				} else {
+					loaded_events.send(UpdateChunkIsLoadedEvent {
+						pos,
+						old_is_loaded,
+						new_is_loaded: loaded,
+					});
+				}
+			}
+			ServerMessage::UnloadChunk { pos } => {
+				let Some(chunk) = game_world.chunks.get_mut(&pos) else {
+					continue;
+				};
+				let old_is_loaded = chunk.loaded;
+				chunk.loaded.set_simple_loaded(false);
+				let new_is_loaded = chunk.loaded;
+				loaded_events.send(UpdateChunkIsLoadedEvent {
+					pos,
+					old_is_loaded,
+					new_is_loaded,
+				});
+			}
+			ServerMessage::BlockChanged { pos, block } => {
+				game_world.replace_block(pos, block);
+			}
+			ServerMessage::PlayerJoined { id, name } => {
+				info!("{name} joined the server");
+				commands.spawn(remote_player_bundle(id, name, Vec3::ZERO));
+			}
+			ServerMessage::PlayerLeft { id } => {
+				for (entity, player, ..) in &remote_players {
+					if player.id == id {
+						info!("{} left the server", player.name);
+						commands.entity(entity).despawn_recursive();
+					}
+				}
+			}
+			ServerMessage::PlayerMoved {
+				id,
+				translation,
+				pitch,
+				yaw,
+			} => {
+				for (_, player, mut snapshots) in &mut remote_players {
+					if player.id == id {
+						snapshots.push(Snapshot {
+							time: time.elapsed_secs_f64(),
+							translation,
+							look_direction: LookDirection { pitch, yaw },
+						});
+					}
+				}
+			}
+			ServerMessage::Time(time) => game_world.time = time,
+		}
+	}
+}
+
+/// asks the server to make the same changes that the player made this frame
+fn send_block_changes(mut client: ResMut<Client>, game_world: Res<GameWorld>) {
+	for &pos in &game_world.changed_blocks {
+		if let Some(&block) = game_world.get_block_at(pos) {
+			client
+				.connection
+				.send(&ClientMessage::SetBlock { pos, block });
+		}
+	}
+}
+
+fn send_player_state(
+	mut client: ResMut<Client>,
+	time: Res<Time>,
+	player: Query<(&Transform, &LookDirection), With<Player>>,
+) {
+	if !client
+		.player_update_timer
+		.tick(time.delta())
+		.just_finished()
+	{
+		return;
+	}
+	let Ok((transform, look_direction)) = player.get_single() else {
+		return;
+	};
+	client.connection.send(&ClientMessage::PlayerState {
+		translation: transform.translation,
+		pitch: look_direction.pitch,
+		yaw: look_direction.yaw,
+	});
+}
+
+fn flush_client(
+	mut commands: Commands,
+	mut client: ResMut<Client>,
+	mut leave_events: EventWriter<LeaveWorldEvent>,
+) {
+	client.connection.flush();
+	if let Some(reason) = client.connection.closed_reason() {
+		error!("lost the connection to the server: {reason}");
+		leave_events.send(LeaveWorldEvent);
+		commands.remove_resource::<Client>();
+	}
+}
+
+fn leave_server(mut commands: Commands, client: Option<ResMut<Client>>) {
+	if let Some(mut client) = client {
+		client.connection.disconnect();
+		commands.remove_resource::<Client>();
+	}
+}
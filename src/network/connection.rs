@@ -0,0 +1,283 @@
+//! a tcp connection that never blocks, so that it can be used from systems every frame
+
+use super::protocol;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+	io::{self, ErrorKind, Read, Write},
+	mem,
+	net::{Shutdown, TcpStream},
+	thread,
+	time::Duration,
+};
+
+/// if more than this many bytes can't be sent, the other side is too slow and is disconnected
+const MAX_UNSENT_LEN: usize = 32 * 1024 * 1024;
+
+/// how long the bytes that are still queued when disconnecting can take to be sent
+const DISCONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct Connection {
+	stream: TcpStream,
+	/// bytes that were received, but don't form a complete message yet
+	received: Vec<u8>,
+	/// bytes that were written with [`Connection::send`], but couldn't be sent yet
+	unsent: Vec<u8>,
+	/// why the connection stopped working. nothing is sent after this is set, and only
+	/// the messages that arrived before the other side closed the connection are received
+	closed: Option<String>,
+}
+
+impl Connection {
+	pub fn new(stream: TcpStream) -> io::Result<Self> {
+		stream.set_nonblocking(true)?;
+		// messages are small and should arrive as soon as possible
+		stream.set_nodelay(true)?;
+		Ok(Self {
+			stream,
+			received: Vec::new(),
+			unsent: Vec::new(),
+			closed: None,
+		})
+	}
+
+	/// the reason why the connection was closed, if it was
+	pub fn closed_reason(&self) -> Option<&str> {
+		self.closed.as_deref()
+	}
+
+	/// how many bytes are still waiting to be sent
+	pub fn unsent_len(&self) -> usize {
+		self.unsent.len()
+	}
+
+	/// queues the message. it is sent with the next [`Connection::flush`]
+	pub fn send(&mut self, message: &impl Serialize) {
+		if self.closed.is_some() {
+			return;
+		}
+		if self.unsent.len() > MAX_UNSENT_LEN {
+			self.close("the other side is not receiving messages fast enough");
+			return;
+		}
+		if let Err(err) = protocol::encode(message, &mut self.unsent) {
+			self.close(&err.to_string());
+		}
+	}
+
+	/// sends as many of the queued bytes as possible without waiting
+	pub fn flush(&mut self) {
+		while self.closed.is_none() && !self.unsent.is_empty() {
+			match self.stream.write(&self.unsent) {
+				Ok(0) => self.close("the connection was closed"),
+				Ok(len) => {
+					self.unsent.drain(..len);
+				}
+				Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+				Err(err) if err.kind() == ErrorKind::Interrupted => {}
+				Err(err) => self.close(&err.to_string()),
+			}
+		}
+	}
+
+	/// the next message that was received, or `None` if there is no complete message yet
+	pub fn receive<M: DeserializeOwned>(&mut self) -> Option<M> {
+		match protocol::decode(&mut self.received) {
+			Ok(Some(message)) => return Some(message),
+			Ok(None) => {}
+			Err(err) => {
+				self.close(&err.to_string());
+				return None;
+			}
+		}
+		if self.closed.is_some() {
+			return None;
+		}
+		self.read_available();
+		match protocol::decode(&mut self.received) {
+			Ok(message) => message,
+			Err(err) => {
+				self.close(&err.to_string());
+				None
+			}
+		}
+	}
+
+	/// reads everything that arrived, without waiting for more
+	fn read_available(&mut self) {
+		let mut buffer = [0; 16 * 1024];
+		while self.closed.is_none() {
+			match self.stream.read(&mut buffer) {
+				// the messages that already arrived can still be received
+				Ok(0) => self.closed = Some("the connection was closed".to_owned()),
+				Ok(len) => self.received.extend_from_slice(&buffer[..len]),
+				Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+				Err(err) if err.kind() == ErrorKind::Interrupted => {}
+				Err(err) => self.close(&err.to_string()),
+			}
+		}
+	}
+
+	/// sends everything that is queued, and then closes the connection.<br>
+	/// what can't be sent right away is sent on another thread, so that the game doesn't wait
+	pub fn disconnect(&mut self) {
+		self.flush();
+		if self.closed.is_some() {
+			return;
+		}
+		// the last message should arrive even if the other side is slow
+		match self.stream.try_clone() {
+			Ok(mut stream) if !self.unsent.is_empty() => {
+				let unsent = mem::take(&mut self.unsent);
+				thread::spawn(move || {
+					if stream.set_nonblocking(false).is_ok()
+						&& stream.set_write_timeout(Some(DISCONNECT_TIMEOUT)).is_ok()
+					{
+						let _ = stream.write_all(&unsent);
+					}
+					let _ = stream.shutdown(Shutdown::Both);
+				});
+				// the stream is shut down by the thread once it is done
+				self.closed = Some("disconnected".to_owned());
+				self.received.clear();
+			}
+			_ => self.close("disconnected"),
+		}
+	}
+
+	fn close(&mut self, reason: &str) {
+		self.received.clear();
+		if self.closed.is_none() {
+			self.closed = Some(reason.to_owned());
+			let _ = self.stream.shutdown(Shutdown::Both);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		block::prelude::*,
+		game_world::chunk::BlockArray,
+		network::protocol::{ClientMessage, ServerMessage, PROTOCOL_VERSION},
+		pos::{BlockInChunkPos, BlockPos, ChunkPos},
+	};
+	use std::net::TcpListener;
+
+	/// a server and a client that are connected over localhost
+	fn connected_pair() -> (Connection, Connection) {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+		let (server, _) = listener.accept().unwrap();
+		(
+			Connection::new(server).unwrap(),
+			Connection::new(client).unwrap(),
+		)
+	}
+
+	/// waits until a message arrives, since nothing in a connection blocks
+	fn wait_for<M: DeserializeOwned>(connection: &mut Connection) -> M {
+		for _ in 0..1000 {
+			if let Some(message) = connection.receive() {
+				return message;
+			}
+			assert_eq!(connection.closed_reason(), None);
+			std::thread::sleep(Duration::from_millis(5));
+		}
+		panic!("no message arrived");
+	}
+
+	#[test]
+	fn messages_arrive_over_localhost() {
+		let (mut server, mut client) = connected_pair();
+		let hello = ClientMessage::Hello {
+			version: PROTOCOL_VERSION,
+			name: "test".to_owned(),
+		};
+		let edit = ClientMessage::SetBlock {
+			pos: BlockPos::new(1, -2, 3),
+			block: Planks::BLOCK,
+		};
+		client.send(&hello);
+		client.send(&edit);
+		client.flush();
+		assert_eq!(wait_for::<ClientMessage>(&mut server), hello);
+		assert_eq!(wait_for::<ClientMessage>(&mut server), edit);
+
+		// chunks are the largest messages, so they are split into several packets
+		let mut blocks = Box::new(BlockArray::ALL_AIR);
+		for x in 0..32 {
+			for y in 0..8 {
+				blocks[BlockInChunkPos::new(x, y, x / 2)] = Stone::BLOCK;
+			}
+		}
+		let chunk = ServerMessage::Chunk {
+			pos: ChunkPos::new(0, -1, 0),
+			blocks: protocol::compress_chunk(&blocks).unwrap(),
+		};
+		server.send(&chunk);
+		server.flush();
+		let ServerMessage::Chunk {
+			blocks: received, ..
+		} = wait_for(&mut client)
+		else {
+			panic!("expected a chunk");
+		};
+		assert_eq!(protocol::decompress_chunk(&received).unwrap(), blocks);
+	}
+
+	#[test]
+	fn closing_is_noticed() {
+		let (mut server, mut client) = connected_pair();
+		server.send(&ServerMessage::Disconnect {
+			reason: "stopping".to_owned(),
+		});
+		server.disconnect();
+		assert_eq!(
+			wait_for::<ServerMessage>(&mut client),
+			ServerMessage::Disconnect {
+				reason: "stopping".to_owned()
+			}
+		);
+		for _ in 0..1000 {
+			if client.receive::<ServerMessage>().is_none() && client.closed_reason().is_some() {
+				return;
+			}
+			std::thread::sleep(Duration::from_millis(5));
+		}
+		panic!("the closed connection was not noticed");
+	}
+
+	#[test]
+	fn disconnecting_does_not_wait_for_slow_receivers() {
+		let (mut server, mut client) = connected_pair();
+		// far more than fits into the buffers of the sockets
+		let message = ServerMessage::Disconnect {
+			reason: "a".repeat(1024 * 1024),
+		};
+		for _ in 0..16 {
+			server.send(&message);
+		}
+		// the client doesn't receive anything yet, so this would wait forever if it blocked
+		server.disconnect();
+		assert!(server.closed_reason().is_some());
+		for _ in 0..16 {
+			assert_eq!(wait_for::<ServerMessage>(&mut client), message);
+		}
+	}
+
+	#[test]
+	fn broken_messages_close_the_connection() {
+		let (mut server, mut client) = connected_pair();
+		// a length that is far too long
+		client.unsent.extend(u32::MAX.to_be_bytes());
+		client.flush();
+		for _ in 0..1000 {
+			if server.receive::<ClientMessage>().is_none() && server.closed_reason().is_some() {
+				return;
+			}
+			std::thread::sleep(Duration::from_millis(5));
+		}
+		panic!("the broken message was accepted");
+	}
+}
@@ -0,0 +1,136 @@
+//! smooths the movement of other players.<br>
+//! their positions only arrive a few times per second, so they are shown slightly in the past,
+//! between the two snapshots around that time.
+
+use crate::{entity::LookDirection, GlobalState};
+use bevy::prelude::*;
+use std::{collections::VecDeque, f32::consts::PI};
+
+pub struct InterpolationPlugin;
+
+impl Plugin for InterpolationPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(Update, interpolate.run_if(in_state(GlobalState::InWorld)));
+	}
+}
+
+/// how many seconds in the past other players are shown.<br>
+/// this is two updates, so that one late update doesn't make them stop
+const INTERPOLATION_DELAY: f64 = 2. / super::PLAYER_UPDATES_PER_SECOND as f64;
+
+/// snapshots that are older than this many seconds are dropped, even if no newer ones arrived
+const MAX_SNAPSHOT_AGE: f64 = 1.;
+
+/// where a player was at some point in time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Snapshot {
+	/// when the snapshot was received, in seconds since startup
+	pub time: f64,
+	pub translation: Vec3,
+	pub look_direction: LookDirection,
+}
+
+impl Snapshot {
+	fn lerp(self, other: Self, s: f32) -> (Vec3, LookDirection) {
+		let yaw_diff =
+			(other.look_direction.yaw - self.look_direction.yaw + PI).rem_euclid(2. * PI) - PI;
+		(
+			self.translation.lerp(other.translation, s),
+			LookDirection {
+				pitch: self
+					.look_direction
+					.pitch
+					.lerp(other.look_direction.pitch, s),
+				yaw: self.look_direction.yaw + yaw_diff * s,
+			},
+		)
+	}
+}
+
+/// the latest snapshots of a player, which move its [`Transform`] and [`LookDirection`]
+#[derive(Component, Debug, Default, Clone)]
+pub struct Snapshots(VecDeque<Snapshot>);
+
+impl Snapshots {
+	pub fn push(&mut self, snapshot: Snapshot) {
+		// snapshots arrive in order, so only the end has to be checked
+		while self.0.back().is_some_and(|last| last.time > snapshot.time) {
+			self.0.pop_back();
+		}
+		self.0.push_back(snapshot);
+	}
+
+	/// the newest snapshot, which is where the player actually is right now
+	pub fn latest(&self) -> Option<&Snapshot> {
+		self.0.back()
+	}
+
+	/// where the player is shown at the given time.<br>
+	/// this removes snapshots that aren't needed anymore
+	fn sample(&mut self, time: f64) -> Option<(Vec3, LookDirection)> {
+		let shown_time = time - INTERPOLATION_DELAY;
+		while self.0.len() > 1
+			&& (self.0[1].time <= shown_time || self.0[0].time < time - MAX_SNAPSHOT_AGE)
+		{
+			self.0.pop_front();
+		}
+		let &first = self.0.front()?;
+		let Some(&second) = self.0.get(1) else {
+			return Some((first.translation, first.look_direction));
+		};
+		if shown_time <= first.time {
+			return Some((first.translation, first.look_direction));
+		}
+		let s = (shown_time - first.time) / (second.time - first.time);
+		Some(first.lerp(second, s as f32))
+	}
+}
+
+fn interpolate(
+	time: Res<Time>,
+	mut players: Query<(&mut Snapshots, &mut Transform, &mut LookDirection)>,
+) {
+	let now = time.elapsed_secs_f64();
+	for (mut snapshots, mut transform, mut look_direction) in &mut players {
+		if let Some((translation, look)) = snapshots.sample(now) {
+			transform.translation = translation;
+			*look_direction = look;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn snapshot(time: f64, x: f32, yaw: f32) -> Snapshot {
+		Snapshot {
+			time,
+			translation: Vec3::new(x, 0., 0.),
+			look_direction: LookDirection { pitch: 0., yaw },
+		}
+	}
+
+	#[test]
+	fn players_are_shown_between_two_snapshots() {
+		let mut snapshots = Snapshots::default();
+		snapshots.push(snapshot(1., 0., 0.));
+		snapshots.push(snapshot(1.1, 10., 0.));
+		let (translation, _) = snapshots.sample(1.05 + INTERPOLATION_DELAY).unwrap();
+		assert!((translation.x - 5.).abs() < 1e-3);
+
+		// nothing is guessed after the latest snapshot
+		let (translation, _) = snapshots.sample(5.).unwrap();
+		assert_eq!(translation.x, 10.);
+		assert_eq!(snapshots.0.len(), 1);
+	}
+
+	#[test]
+	fn yaw_turns_the_short_way() {
+		let mut snapshots = Snapshots::default();
+		snapshots.push(snapshot(1., 0., PI - 0.1));
+		snapshots.push(snapshot(2., 0., -PI + 0.1));
+		let (_, look_direction) = snapshots.sample(1.5 + INTERPOLATION_DELAY).unwrap();
+		assert!((look_direction.yaw.rem_euclid(2. * PI) - PI).abs() < 1e-3);
+	}
+}
@@ -0,0 +1,73 @@
+//! playing together over the network.<br>
+//! one game hosts a server, which owns the world and sends it to the other players.
+//! the other players only send their position and the blocks they want to change.
+
+mod client;
+mod connection;
+mod interpolation;
+mod protocol;
+mod server;
+
+use crate::{
+	entity::{
+		collision::Solid,
+		movement::Crouching,
+		player::{self, Avatar, Nametag},
+		LookDirection,
+	},
+	GlobalState,
+};
+use bevy::prelude::*;
+
+use self::{interpolation::Snapshots, protocol::PlayerId};
+
+pub use self::{client::JoinServerEvent, server::start_server};
+
+pub struct NetworkPlugin;
+
+impl Plugin for NetworkPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_plugins((
+			server::ServerPlugin,
+			client::ClientPlugin,
+			interpolation::InterpolationPlugin,
+		))
+		.add_systems(OnExit(GlobalState::InWorld), despawn_remote_players);
+	}
+}
+
+/// the port that servers use if no other port is given
+pub const DEFAULT_PORT: u16 = 25580;
+
+/// how often the positions of players are sent, per second.<br>
+/// this is the same for the server and the clients
+const PLAYER_UPDATES_PER_SECOND: f32 = 20.;
+
+/// another player that is connected to the same server
+#[derive(Component, Debug, Clone)]
+pub struct RemotePlayer {
+	pub id: PlayerId,
+	pub name: String,
+}
+
+/// the components of an entity that shows where another player is
+fn remote_player_bundle(id: PlayerId, name: String, translation: Vec3) -> impl Bundle {
+	(
+		Name::new(format!("Remote Player {name}")),
+		Nametag(name.clone()),
+		RemotePlayer { id, name },
+		Avatar,
+		// other players can't be walked through, and blocks can't be placed in them
+		Solid,
+		player::collider(Crouching(false)),
+		Transform::from_translation(translation),
+		LookDirection::default(),
+		Snapshots::default(),
+	)
+}
+
+fn despawn_remote_players(mut commands: Commands, players: Query<Entity, With<RemotePlayer>>) {
+	for entity in &players {
+		commands.entity(entity).despawn_recursive();
+	}
+}
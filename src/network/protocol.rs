@@ -0,0 +1,127 @@
+//! the messages that the server and clients send to each other.<br>
+//! every message is sent as its length in bytes, followed by the message encoded with bincode.
+
+use crate::{
+	block::Block,
+	entity::player::GameMode,
+	game_world::{chunk::BlockArray, WorldGenSettings, WorldTime},
+	pos::{BlockPos, ChunkPos},
+};
+use bevy::math::Vec3;
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{error::Error, io::Read};
+
+/// has to be increased whenever a message changes,
+/// so that clients with an older version can't join
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// messages that are longer than this are rejected, so that a broken length can't use up all memory
+pub const MAX_MESSAGE_LEN: usize = 4 * 1024 * 1024;
+
+/// identifies a player on a server. the host always has the id `0`
+pub type PlayerId = u32;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ClientMessage {
+	/// has to be the first message, otherwise the server closes the connection
+	Hello { version: u32, name: String },
+	/// where the player is and where they are looking
+	PlayerState {
+		translation: Vec3,
+		pitch: f32,
+		yaw: f32,
+	},
+	/// asks the server to change a block. if it doesn't allow it,
+	/// it sends back the block that is actually there
+	SetBlock { pos: BlockPos, block: Block },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ServerMessage {
+	/// the answer to [`ClientMessage::Hello`], with everything needed to join the world
+	Welcome {
+		player_id: PlayerId,
+		seed: u32,
+		worldgen_settings: WorldGenSettings,
+		game_mode: GameMode,
+		time: WorldTime,
+	},
+	/// the reason why the server closes the connection
+	Disconnect {
+		reason: String,
+	},
+	/// all blocks of a chunk, compressed with [`compress_chunk`]
+	Chunk {
+		pos: ChunkPos,
+		blocks: Vec<u8>,
+	},
+	/// the chunk is too far away from the player, and will not receive any changes
+	UnloadChunk {
+		pos: ChunkPos,
+	},
+	BlockChanged {
+		pos: BlockPos,
+		block: Block,
+	},
+	PlayerJoined {
+		id: PlayerId,
+		name: String,
+	},
+	PlayerLeft {
+		id: PlayerId,
+	},
+	PlayerMoved {
+		id: PlayerId,
+		translation: Vec3,
+		pitch: f32,
+		yaw: f32,
+	},
+	/// is sent regularly, so that the time of clients doesn't drift away
+	Time(WorldTime),
+}
+
+/// adds the message with its length to the end of the buffer
+pub fn encode(message: &impl Serialize, buffer: &mut Vec<u8>) -> Result<(), Box<dyn Error>> {
+	let bytes = bincode::serialize(message)?;
+	if bytes.len() > MAX_MESSAGE_LEN {
+		return Err(format!("the message is too long ({} bytes)", bytes.len()).into());
+	}
+	buffer.extend((bytes.len() as u32).to_be_bytes());
+	buffer.extend(bytes);
+	Ok(())
+}
+
+/// takes the first complete message from the start of the buffer.<br>
+/// returns `None` if the rest of the message has not been received yet.
+pub fn decode<M: DeserializeOwned>(buffer: &mut Vec<u8>) -> Result<Option<M>, Box<dyn Error>> {
+	let Some(&len) = buffer.first_chunk::<4>() else {
+		return Ok(None);
+	};
+	let len = u32::from_be_bytes(len) as usize;
+	if len > MAX_MESSAGE_LEN {
+		return Err(format!("the message is too long ({len} bytes)").into());
+	}
+	if buffer.len() < 4 + len {
+		return Ok(None);
+	}
+	let message = bincode::deserialize(&buffer[4..4 + len])?;
+	buffer.drain(..4 + len);
+	Ok(Some(message))
+}
+
+/// most chunks are mostly air or stone, so they become a lot smaller when compressed
+pub fn compress_chunk(blocks: &BlockArray) -> Result<Vec<u8>, Box<dyn Error>> {
+	let mut encoder = DeflateEncoder::new(Vec::new(), Compression::fast());
+	bincode::serialize_into(&mut encoder, blocks)?;
+	Ok(encoder.finish()?)
+}
+
+pub fn decompress_chunk(bytes: &[u8]) -> Result<Box<BlockArray>, Box<dyn Error>> {
+	let mut decompressed = Vec::new();
+	DeflateDecoder::new(bytes)
+		// a chunk can never be bigger than this, even if a server sends something else
+		.take(MAX_MESSAGE_LEN as u64)
+		.read_to_end(&mut decompressed)?;
+	Ok(bincode::deserialize(&decompressed)?)
+}
@@ -0,0 +1,832 @@
+//! the server is started by a player with `/host`. it keeps running the world like normal,
+//! and sends it to everyone who joins
+
+use super::{
+	connection::Connection,
+	interpolation::{Snapshot, Snapshots},
+	protocol::{self, ClientMessage, PlayerId, ServerMessage, PROTOCOL_VERSION},
+	remote_player_bundle, RemotePlayer, DEFAULT_PORT, PLAYER_UPDATES_PER_SECOND,
+};
+use crate::{
+	block::Block,
+	console::{AddCommand, Args, Command, Param, ParamKind},
+	entity::{
+		collision::SolidEntities,
+		player::{GameMode, Player, REACH},
+		LookDirection,
+	},
+	game_world::{chunk::GenerationStage, chunk_pos_in_render_distance, ChunkLoader, GameWorld},
+	global_config::Config,
+	pos::{BlockPos, ChunkPos},
+	GlobalState,
+};
+use bevy::{
+	prelude::*,
+	utils::{HashMap, HashSet},
+};
+use std::{
+	error::Error,
+	io::ErrorKind,
+	net::{Ipv4Addr, TcpListener},
+};
+
+pub struct ServerPlugin;
+
+impl Plugin for ServerPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_command(HostCommand)
+			.add_systems(
+				Update,
+				(accept_clients, receive_client_messages, send_chunks)
+					.chain()
+					.run_if(in_state(GlobalState::InWorld))
+					.run_if(resource_exists::<Server>),
+			)
+			// the changed blocks are cleared in `Last`
+			.add_systems(
+				PostUpdate,
+				(
+					send_block_changes,
+					send_player_positions,
+					send_time,
+					flush_clients,
+				)
+					.chain()
+					.run_if(in_state(GlobalState::InWorld))
+					.run_if(resource_exists::<Server>),
+			)
+			.add_systems(OnExit(GlobalState::InWorld), stop_server);
+	}
+}
+
+/// the id of the player that hosts the server
+const HOST_ID: PlayerId = 0;
+
+/// how many chunks are sent to a client at most every frame
+const CHUNKS_PER_FRAME: usize = 8;
+
+/// no more chunks are sent while this many bytes are still waiting to be sent to a client
+const MAX_UNSENT_CHUNK_BYTES: usize = 1024 * 1024;
+
+/// how fast a client in survival mode can move, in m/s.<br>
+/// clients say where they are themselves, so they could teleport anywhere without this
+const MAX_SURVIVAL_SPEED: f32 = 100.;
+
+/// how far away from its last known position a client can change blocks in survival mode.<br>
+/// that position is at its feet and a bit in the past, so this is further than [`REACH`]
+const MAX_SURVIVAL_REACH: f32 = REACH + 3.;
+
+#[derive(Resource)]
+struct Server {
+	listener: TcpListener,
+	clients: Vec<ServerClient>,
+	/// the id of the next client that connects
+	next_id: PlayerId,
+	player_update_timer: Timer,
+	time_update_timer: Timer,
+}
+
+struct ServerClient {
+	id: PlayerId,
+	connection: Connection,
+	/// the player entity of this client, which exists once the client said hello
+	entity: Option<Entity>,
+	name: String,
+	/// the chunks the client has, which it receives changes for
+	sent_chunks: HashSet<ChunkPos>,
+}
+
+impl Server {
+	fn start(port: u16) -> Result<Self, Box<dyn Error>> {
+		let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, port))?;
+		listener.set_nonblocking(true)?;
+		Ok(Self {
+			listener,
+			clients: Vec::new(),
+			next_id: HOST_ID + 1,
+			player_update_timer: Timer::from_seconds(
+				1. / PLAYER_UPDATES_PER_SECOND,
+				TimerMode::Repeating,
+			),
+			time_update_timer: Timer::from_seconds(1., TimerMode::Repeating),
+		})
+	}
+
+	/// the clients that said hello and are in the world
+	fn joined_clients(&mut self) -> impl Iterator<Item = &mut ServerClient> {
+		self.clients
+			.iter_mut()
+			.filter(|client| client.entity.is_some())
+	}
+}
+
+fn disconnect_message(reason: impl Into<String>) -> ServerMessage {
+	ServerMessage::Disconnect {
+		reason: reason.into(),
+	}
+}
+
+fn accept_clients(mut server: ResMut<Server>) {
+	loop {
+		match server.listener.accept() {
+			Ok((stream, address)) => {
+				let connection = match Connection::new(stream) {
+					Ok(connection) => connection,
+					Err(err) => {
+						warn!("couldn't accept the connection from {address}: {err}");
+						continue;
+					}
+				};
+				info!("{address} is connecting");
+				let id = server.next_id;
+				server.next_id += 1;
+				server.clients.push(ServerClient {
+					id,
+					connection,
+					entity: None,
+					name: address.to_string(),
+					sent_chunks: HashSet::new(),
+				});
+			}
+			Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+			Err(err) => {
+				warn!("couldn't accept a connection: {err}");
+				break;
+			}
+		}
+	}
+}
+
+#[allow(clippy::too_many_arguments)]
+fn receive_client_messages(
+	mut commands: Commands,
+	mut server: ResMut<Server>,
+	mut game_world: ResMut<GameWorld>,
+	mut players: Query<(&Transform, &mut Snapshots, &GameMode), With<RemotePlayer>>,
+	host: Query<(), With<Player>>,
+	solids: SolidEntities,
+	config: Res<Config>,
+	time: Res<Time>,
+) {
+	let mut joined = Vec::new();
+	for client in &mut server.clients {
+		while let Some(message) = client.connection.receive() {
+			let Some(entity) = client.entity else {
+				if let ClientMessage::Hello { version, name } = message {
+					greet(client, version, name, &mut commands, &game_world);
+					joined.extend(client.entity.map(|_| client.id));
+				} else {
+					client
+						.connection
+						.send(&disconnect_message("the first message has to be a hello"));
+					client.connection.disconnect();
+				}
+				continue;
+			};
+			match message {
+				ClientMessage::Hello { .. } => {
+					client
+						.connection
+						.send(&disconnect_message("you already said hello"));
+					client.connection.disconnect();
+				}
+				ClientMessage::PlayerState {
+					translation,
+					pitch,
+					yaw,
+				} => {
+					if let Ok((transform, mut snapshots, &game_mode)) = players.get_mut(entity) {
+						let now = time.elapsed_secs_f64();
+						let (from, since) = last_position(transform, &snapshots, now);
+						let translation = if game_mode == GameMode::Creative {
+							translation
+						} else {
+							limit_movement(from, translation, (now - since) as f32, &game_world)
+						};
+						snapshots.push(Snapshot {
+							time: now,
+							translation,
+							look_direction: LookDirection { pitch, yaw },
+						});
+					}
+				}
+				ClientMessage::SetBlock { pos, block } => {
+					let now = time.elapsed_secs_f64();
+					let allowed =
+						players
+							.get(entity)
+							.is_ok_and(|(transform, snapshots, &game_mode)| {
+								let (player_pos, _) = last_position(transform, snapshots, now);
+								may_set_block(
+									client,
+									player_pos,
+									game_mode,
+									pos,
+									block,
+									&game_world,
+									&solids,
+								)
+							});
+					// changes of other players are not in the history of the host
+					if allowed && game_world.replace_block(pos, block).is_some() {
+						game_world.changed_blocks.push(pos);
+					} else if let Some(&block) = game_world.get_block_at(pos) {
+						client
+							.connection
+							.send(&ServerMessage::BlockChanged { pos, block });
+					}
+				}
+			}
+		}
+	}
+
+	// tells everyone about the new players, and the new players about everyone
+	for id in joined {
+		// a server without a window has no host that plays on it
+		let host = (!host.is_empty()).then(|| (HOST_ID, config.player_name.clone()));
+		let names = server
+			.joined_clients()
+			.map(|client| (client.id, client.name.clone()))
+			.chain(host)
+			.collect::<HashMap<_, _>>();
+		for client in server.joined_clients() {
+			if client.id == id {
+				for (&other_id, name) in names.iter().filter(|(&other_id, _)| other_id != id) {
+					client.connection.send(&ServerMessage::PlayerJoined {
+						id: other_id,
+						name: name.clone(),
+					});
+				}
+			} else {
+				client.connection.send(&ServerMessage::PlayerJoined {
+					id,
+					name: names[&id].clone(),
+				});
+			}
+		}
+	}
+}
+
+/// where the player of a client was last, and when it got there.<br>
+/// before the client sends its position, that is where the player was spawned
+fn last_position(transform: &Transform, snapshots: &Snapshots, now: f64) -> (Vec3, f64) {
+	snapshots.latest().map_or(
+		(
+			transform.translation,
+			now - 1. / PLAYER_UPDATES_PER_SECOND as f64,
+		),
+		|snapshot| (snapshot.translation, snapshot.time),
+	)
+}
+
+/// where a client in survival mode can be, if it says that it moved from `from` to `to`
+/// in the given number of seconds.<br>
+/// the only way to get further than [`MAX_SURVIVAL_SPEED`] allows is respawning
+fn limit_movement(from: Vec3, to: Vec3, secs: f32, game_world: &GameWorld) -> Vec3 {
+	if !to.is_finite() {
+		return from;
+	}
+	let max_distance = MAX_SURVIVAL_SPEED * secs.max(0.);
+	if from.distance(to) <= max_distance || to == game_world.spawn_point() {
+		to
+	} else {
+		from + (to - from).clamp_length_max(max_distance)
+	}
+}
+
+/// whether a client at the given position is allowed to change the block at `pos` to `block`
+#[allow(clippy::too_many_arguments)]
+fn may_set_block(
+	client: &ServerClient,
+	player_pos: Vec3,
+	game_mode: GameMode,
+	pos: BlockPos,
+	block: Block,
+	game_world: &GameWorld,
+	solids: &SolidEntities,
+) -> bool {
+	// blocks with unknown ids or invalid data can't be handled by the server
+	if Block::from_raw_data(block.id, block.raw_data()).is_none() {
+		return false;
+	}
+	// clients can only change blocks they can see
+	let chunk_pos = pos.to_chunk_pos();
+	let is_loaded = game_world
+		.chunks
+		.get(&chunk_pos)
+		.is_some_and(|chunk| chunk.loaded.is_simple_loaded());
+	if !is_loaded || !client.sent_chunks.contains(&chunk_pos) {
+		return false;
+	}
+	// like for the host, blocks can't be placed inside of players or other entities.
+	// the client already checked its own player, which the server only knows from a moment ago
+	if solids.is_in_the_way(block, pos, client.entity) {
+		return false;
+	}
+	// commands and world edit can change blocks that are far away, but only in creative mode
+	let center = pos.to_world_pos() + Vec3::splat(0.5);
+	game_mode == GameMode::Creative || center.distance(player_pos) <= MAX_SURVIVAL_REACH
+}
+
+/// answers the hello of a client, and lets it into the world
+fn greet(
+	client: &mut ServerClient,
+	version: u32,
+	name: String,
+	commands: &mut Commands,
+	game_world: &GameWorld,
+) {
+	if version != PROTOCOL_VERSION {
+		client.connection.send(&disconnect_message(format!(
+			"the server uses version {PROTOCOL_VERSION} of the protocol, but you use version {version}"
+		)));
+		client.connection.disconnect();
+		return;
+	}
+	info!("{name} joined from {}", client.name);
+	client.connection.send(&ServerMessage::Welcome {
+		player_id: client.id,
+		seed: game_world.seed,
+		worldgen_settings: game_world.worldgen_settings.clone(),
+		game_mode: game_world.game_mode,
+		time: game_world.time,
+	});
+	let entity = commands
+		.spawn((
+			remote_player_bundle(client.id, name.clone(), game_world.spawn_point()),
+			// new players start in the default game mode, and only the server can change it
+			game_world.game_mode,
+			// chunks around other players have to be loaded, so that they can be sent to them
+			ChunkLoader,
+		))
+		.id();
+	client.entity = Some(entity);
+	client.name = name;
+}
+
+/// sends the chunks close to each player, with the closest ones first
+fn send_chunks(
+	mut server: ResMut<Server>,
+	game_world: Res<GameWorld>,
+	players: Query<&Transform, With<RemotePlayer>>,
+	config: Res<Config>,
+) {
+	let render_distance = (
+		config.horizontal_render_distance,
+		config.vertical_render_distance,
+	);
+	for client in server.joined_clients() {
+		let Some(Ok(transform)) = client.entity.map(|entity| players.get(entity)) else {
+			continue;
+		};
+		let in_render_distance =
+			chunk_pos_in_render_distance(transform.translation, render_distance);
+
+		let mut sent = 0;
+		for &pos in &in_render_distance {
+			if sent >= CHUNKS_PER_FRAME || client.connection.unsent_len() > MAX_UNSENT_CHUNK_BYTES {
+				break;
+			}
+			if client.sent_chunks.contains(&pos) {
+				continue;
+			}
+			let Some(chunk) = game_world.chunks.get(&pos) else {
+				continue;
+			};
+			if !chunk.loaded.is_simple_loaded()
+				|| chunk.generation_state < GenerationStage::COMPLETE
+			{
+				continue;
+			}
+			match protocol::compress_chunk(&chunk.blocks) {
+				Ok(blocks) => client
+					.connection
+					.send(&ServerMessage::Chunk { pos, blocks }),
+				Err(err) => {
+					error!("couldn't compress the chunk at {pos}: {err}");
+					continue;
+				}
+			}
+			client.sent_chunks.insert(pos);
+			sent += 1;
+		}
+
+		let in_render_distance = in_render_distance.into_iter().collect::<HashSet<_>>();
+		let too_far = client
+			.sent_chunks
+			.iter()
+			.filter(|pos| !in_render_distance.contains(*pos))
+			.copied()
+			.collect::<Vec<_>>();
+		for pos in too_far {
+			client.sent_chunks.remove(&pos);
+			client.connection.send(&ServerMessage::UnloadChunk { pos });
+		}
+	}
+}
+
+/// sends the blocks that changed this frame to everyone who has their chunk
+fn send_block_changes(mut server: ResMut<Server>, game_world: Res<GameWorld>) {
+	let changed = game_world.changed_blocks.iter().collect::<HashSet<_>>();
+	for &pos in changed {
+		let Some(&block) = game_world.get_block_at(pos) else {
+			continue;
+		};
+		for client in server.joined_clients() {
+			if client.sent_chunks.contains(&pos.to_chunk_pos()) {
+				client
+					.connection
+					.send(&ServerMessage::BlockChanged { pos, block });
+			}
+		}
+	}
+}
+
+fn send_player_positions(
+	mut server: ResMut<Server>,
+	time: Res<Time>,
+	host: Query<(&Transform, &LookDirection), With<Player>>,
+	remote_players: Query<&Snapshots, With<RemotePlayer>>,
+) {
+	if !server
+		.player_update_timer
+		.tick(time.delta())
+		.just_finished()
+	{
+		return;
+	}
+	let host = host
+		.get_single()
+		.ok()
+		.map(|(transform, &look_direction)| (HOST_ID, transform.translation, look_direction));
+	// the latest snapshots are sent, since the interpolated ones are already in the past
+	let remote_players = server.clients.iter().filter_map(|client| {
+		let snapshot = remote_players.get(client.entity?).ok()?.latest()?;
+		Some((client.id, snapshot.translation, snapshot.look_direction))
+	});
+	let moved = host
+		.into_iter()
+		.chain(remote_players)
+		.map(
+			|(id, translation, look_direction)| ServerMessage::PlayerMoved {
+				id,
+				translation,
+				pitch: look_direction.pitch,
+				yaw: look_direction.yaw,
+			},
+		)
+		.collect::<Vec<_>>();
+	for client in server.joined_clients() {
+		for message in &moved {
+			// clients know where they are themselves
+			if !matches!(message, ServerMessage::PlayerMoved { id, .. } if *id == client.id) {
+				client.connection.send(message);
+			}
+		}
+	}
+}
+
+/// the time of clients advances on its own, but it is corrected regularly
+fn send_time(mut server: ResMut<Server>, time: Res<Time>, game_world: Res<GameWorld>) {
+	if !server.time_update_timer.tick(time.delta()).just_finished() {
+		return;
+	}
+	for client in server.joined_clients() {
+		client
+			.connection
+			.send(&ServerMessage::Time(game_world.time));
+	}
+}
+
+/// sends everything that was queued this frame, and removes clients that disconnected
+fn flush_clients(mut commands: Commands, mut server: ResMut<Server>) {
+	let mut left = Vec::new();
+	server.clients.retain_mut(|client| {
+		client.connection.flush();
+		let Some(reason) = client.connection.closed_reason() else {
+			return true;
+		};
+		info!("{} left: {reason}", client.name);
+		if let Some(entity) = client.entity {
+			commands.entity(entity).despawn_recursive();
+			left.push(client.id);
+		}
+		false
+	});
+	for id in left {
+		for client in server.joined_clients() {
+			client.connection.send(&ServerMessage::PlayerLeft { id });
+		}
+	}
+}
+
+/// lets other players join the world on the given port, or any free port if it is `0`.<br>
+/// returns the port the server is running on.
+pub fn start_server(world: &mut World, port: u16) -> Result<u16, Box<dyn Error>> {
+	if world.contains_resource::<Server>() {
+		return Err("the server is already running".into());
+	}
+	let server = Server::start(port)?;
+	let port = server.listener.local_addr()?.port();
+	world.insert_resource(server);
+	Ok(port)
+}
+
+fn stop_server(mut commands: Commands, server: Option<ResMut<Server>>) {
+	let Some(mut server) = server else {
+		return;
+	};
+	for client in &mut server.clients {
+		client
+			.connection
+			.send(&disconnect_message("the server was stopped"));
+		client.connection.disconnect();
+	}
+	commands.remove_resource::<Server>();
+	info!("stopped the server");
+}
+
+struct HostCommand;
+
+impl Command for HostCommand {
+	fn name(&self) -> &'static str {
+		"host"
+	}
+
+	fn description(&self) -> &'static str {
+		"starts a server, so that other players can join this world"
+	}
+
+	fn signatures(&self) -> &'static [&'static [Param]] {
+		const SIGNATURES: &[&[Param]] = &[&[], &[Param::new("port", ParamKind::Int)]];
+		SIGNATURES
+	}
+
+	fn run(&self, args: &Args, world: &mut World) -> Result<String, Box<dyn Error>> {
+		if world.resource::<GameWorld>().is_remote {
+			return Err("only the server can host the world".into());
+		}
+		let port = if args.is_empty() {
+			DEFAULT_PORT
+		} else {
+			u16::try_from(args.int(0)).map_err(|_| "the port has to be between 0 and 65535")?
+		};
+		let port = start_server(world, port)?;
+		Ok(format!("started a server on port {port}"))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		block::prelude::*,
+		entity::{
+			collision::{Solid, SpatialHash},
+			movement::Crouching,
+			player,
+		},
+		game_world::chunk::BlockArray,
+	};
+	use bevy::ecs::system::RunSystemOnce;
+	use std::{net::TcpStream, time::Duration};
+
+	/// a world with a single loaded chunk at the origin, and a server for it that the player hosts.<br>
+	/// the host stands at the origin
+	fn hosted_world() -> World {
+		let mut world = World::new();
+		world.spawn((
+			Player,
+			Solid,
+			player::collider(Crouching(false)),
+			Transform::default(),
+			LookDirection::default(),
+		));
+		world.init_resource::<SpatialHash>();
+		let game_world = GameWorld::with_air_chunks([ChunkPos::new(0, 0, 0)]);
+		world.insert_resource(game_world);
+		world.insert_resource(Config::default());
+		world.insert_resource(Time::<()>::default());
+		world.insert_resource(Server::start(0).unwrap());
+		world
+	}
+
+	fn connect(world: &World) -> Connection {
+		let port = world
+			.resource::<Server>()
+			.listener
+			.local_addr()
+			.unwrap()
+			.port();
+		Connection::new(TcpStream::connect(("127.0.0.1", port)).unwrap()).unwrap()
+	}
+
+	/// runs the server until the client receives a message
+	fn next_message(world: &mut World, client: &mut Connection) -> ServerMessage {
+		for _ in 0..1000 {
+			client.flush();
+			world.run_system_once(accept_clients).unwrap();
+			world.run_system_once(receive_client_messages).unwrap();
+			world.run_system_once(send_chunks).unwrap();
+			world.run_system_once(send_block_changes).unwrap();
+			world.run_system_once(flush_clients).unwrap();
+			world.resource_mut::<GameWorld>().changed_blocks.clear();
+			if let Some(message) = client.receive() {
+				return message;
+			}
+			assert_eq!(client.closed_reason(), None);
+			std::thread::sleep(Duration::from_millis(5));
+		}
+		panic!("the server didn't send anything");
+	}
+
+	fn hello(version: u32) -> ClientMessage {
+		ClientMessage::Hello {
+			version,
+			name: "test".to_owned(),
+		}
+	}
+
+	/// lets the client join, and waits for the welcome, the host joining and the chunk
+	fn join(world: &mut World, client: &mut Connection) {
+		client.send(&hello(PROTOCOL_VERSION));
+		for _ in 0..3 {
+			next_message(world, client);
+		}
+	}
+
+	/// where the server thinks the player of the only client is
+	fn client_translation(world: &mut World) -> Vec3 {
+		let mut snapshots = world.query::<&Snapshots>();
+		snapshots.single(world).latest().unwrap().translation
+	}
+
+	/// puts the player of the only client at the translation, as if it had walked there
+	fn move_client(world: &mut World, translation: Vec3) {
+		let mut snapshots = world.query::<&mut Snapshots>();
+		snapshots.single_mut(world).push(Snapshot {
+			time: 0.,
+			translation,
+			look_direction: LookDirection::default(),
+		});
+	}
+
+	#[test]
+	fn clients_receive_chunks_and_can_change_blocks() {
+		let mut world = hosted_world();
+		let mut client = connect(&world);
+		client.send(&hello(PROTOCOL_VERSION));
+		assert!(matches!(
+			next_message(&mut world, &mut client),
+			ServerMessage::Welcome { player_id: 1, .. }
+		));
+		assert!(matches!(
+			next_message(&mut world, &mut client),
+			ServerMessage::PlayerJoined { id: HOST_ID, .. }
+		));
+
+		move_client(&mut world, Vec3::new(2., 0., 2.));
+		let ServerMessage::Chunk { pos, blocks } = next_message(&mut world, &mut client) else {
+			panic!("expected a chunk");
+		};
+		assert_eq!(pos, ChunkPos::new(0, 0, 0));
+		assert_eq!(
+			protocol::decompress_chunk(&blocks).unwrap(),
+			Box::new(BlockArray::ALL_AIR)
+		);
+
+		let pos = BlockPos::new(1, 2, 3);
+		client.send(&ClientMessage::SetBlock {
+			pos,
+			block: Stone::BLOCK,
+		});
+		assert_eq!(
+			next_message(&mut world, &mut client),
+			ServerMessage::BlockChanged {
+				pos,
+				block: Stone::BLOCK
+			}
+		);
+		let mut game_world = world.resource_mut::<GameWorld>();
+		assert_eq!(game_world.get_block_at(pos), Some(&Stone::BLOCK));
+		// the host can only undo its own changes
+		assert_eq!(game_world.undo(), None);
+	}
+
+	#[test]
+	fn invalid_block_changes_are_reverted() {
+		let mut world = hosted_world();
+		let mut client = connect(&world);
+		join(&mut world, &mut client);
+		move_client(&mut world, Vec3::ZERO);
+		SpatialHash::update(&mut world);
+
+		let stone_id = bincode::serialize(&Stone::BLOCK).unwrap()[0];
+		let unknown_block = bincode::deserialize::<Block>(&[u8::MAX, 0]).unwrap();
+		let invalid_data = bincode::deserialize::<Block>(&[stone_id, 1]).unwrap();
+		let changes = [
+			(BlockPos::new(1, 0, 0), unknown_block),
+			(BlockPos::new(2, 0, 0), invalid_data),
+			(BlockPos::new(15, 15, 15), Stone::BLOCK),
+			// inside of the host
+			(BlockPos::new(0, 1, 0), Stone::BLOCK),
+		];
+		for (pos, block) in changes {
+			client.send(&ClientMessage::SetBlock { pos, block });
+			assert_eq!(
+				next_message(&mut world, &mut client),
+				ServerMessage::BlockChanged {
+					pos,
+					block: Air::BLOCK
+				}
+			);
+			assert_eq!(
+				world.resource::<GameWorld>().get_block_at(pos),
+				Some(&Air::BLOCK)
+			);
+		}
+	}
+
+	#[test]
+	fn clients_can_place_blocks_where_the_server_last_saw_them() {
+		let mut world = hosted_world();
+		let mut client = connect(&world);
+		join(&mut world, &mut client);
+		// the player of the client is shown a moment in the past,
+		// but it has already moved away, for example by jumping
+		let translation = Vec3::new(5.5, 0., 5.5);
+		move_client(&mut world, translation);
+		let mut avatar = world.query_filtered::<&mut Transform, With<RemotePlayer>>();
+		avatar.single_mut(&mut world).translation = translation;
+		SpatialHash::update(&mut world);
+
+		let pos = BlockPos::new(5, 0, 5);
+		client.send(&ClientMessage::SetBlock {
+			pos,
+			block: Stone::BLOCK,
+		});
+		assert_eq!(
+			next_message(&mut world, &mut client),
+			ServerMessage::BlockChanged {
+				pos,
+				block: Stone::BLOCK
+			}
+		);
+	}
+
+	#[test]
+	fn survival_clients_cant_teleport() {
+		let mut world = hosted_world();
+		let mut client = connect(&world);
+		join(&mut world, &mut client);
+		let spawn_point = world.resource::<GameWorld>().spawn_point();
+		let teleport = ClientMessage::PlayerState {
+			translation: Vec3::new(1., 0., 0.),
+			pitch: 0.,
+			yaw: 0.,
+		};
+		let pos = BlockPos::new(1, 0, 0);
+		let set_block = ClientMessage::SetBlock {
+			pos,
+			block: Stone::BLOCK,
+		};
+
+		client.send(&teleport);
+		client.send(&set_block);
+		assert_eq!(
+			next_message(&mut world, &mut client),
+			ServerMessage::BlockChanged {
+				pos,
+				block: Air::BLOCK
+			}
+		);
+		let max_distance = MAX_SURVIVAL_SPEED / PLAYER_UPDATES_PER_SECOND;
+		assert!(client_translation(&mut world).distance(spawn_point) <= max_distance + 1e-3);
+
+		// creative players can fly as fast as they want
+		let mut game_mode = world.query_filtered::<&mut GameMode, With<RemotePlayer>>();
+		*game_mode.single_mut(&mut world) = GameMode::Creative;
+		client.send(&teleport);
+		client.send(&set_block);
+		assert_eq!(
+			next_message(&mut world, &mut client),
+			ServerMessage::BlockChanged {
+				pos,
+				block: Stone::BLOCK
+			}
+		);
+		assert_eq!(client_translation(&mut world), Vec3::new(1., 0., 0.));
+	}
+
+	#[test]
+	fn clients_with_other_versions_are_rejected() {
+		let mut world = hosted_world();
+		let mut client = connect(&world);
+		client.send(&hello(PROTOCOL_VERSION + 1));
+		assert!(matches!(
+			next_message(&mut world, &mut client),
+			ServerMessage::Disconnect { .. }
+		));
+		assert!(world.resource::<Server>().clients.is_empty());
+	}
+}
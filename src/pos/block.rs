@@ -2,7 +2,7 @@ use super::{BlockInChunkPos, ChunkPos};
 use crate::{face::Face, game_world::chunk::CHUNK_LENGTH};
 use bevy::math::{IVec3, Vec3};
 use serde::{Deserialize, Serialize};
-use std::ops::Add;
+use std::ops::{Add, Sub};
 
 #[derive(Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct BlockPos {
@@ -67,3 +67,15 @@ impl Add<IVec3> for BlockPos {
 		}
 	}
 }
+
+impl Sub<IVec3> for BlockPos {
+	type Output = Self;
+
+	fn sub(self, rhs: IVec3) -> Self::Output {
+		Self {
+			x: self.x - rhs.x,
+			y: self.y - rhs.y,
+			z: self.z - rhs.z,
+		}
+	}
+}
@@ -37,6 +37,14 @@ impl ChunkPos {
 		Face::all().map(move |face| self + face.normal())
 	}
 
+	/// gets all 26 chunk positions around this chunk, including diagonals
+	pub fn surrounding(self) -> impl Iterator<Item = Self> {
+		(-1..=1)
+			.flat_map(|x| (-1..=1).flat_map(move |y| (-1..=1).map(move |z| IVec3::new(x, y, z))))
+			.filter(|offset| *offset != IVec3::ZERO)
+			.map(move |offset| self + offset)
+	}
+
 	pub fn distance_squared(self, rhs: Self) -> u32 {
 		((self.x - rhs.x).pow(2) + (self.y - rhs.y).pow(2) + (self.z - rhs.z).pow(2)) as u32
 	}
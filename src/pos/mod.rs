@@ -1,11 +1,13 @@
 mod block;
 mod block_in_chunk;
 mod chunk;
+mod region;
 mod world;
 
 pub use self::{
 	block::BlockPos,
 	block_in_chunk::BlockInChunkPos,
 	chunk::ChunkPos,
+	region::BlockRegion,
 	world::{IVec3Utils, Vec3Utils},
 };
@@ -0,0 +1,80 @@
+use super::BlockPos;
+use bevy::math::{I64Vec3, IVec3};
+use serde::{Deserialize, Serialize};
+
+/// a box of blocks, which contains both of its corners
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BlockRegion {
+	min: BlockPos,
+	max: BlockPos,
+}
+
+impl BlockRegion {
+	/// creates the smallest region that contains both corners
+	pub fn from_corners(a: BlockPos, b: BlockPos) -> Self {
+		let (a, b) = (IVec3::from(a), IVec3::from(b));
+		Self {
+			min: a.min(b).into(),
+			max: a.max(b).into(),
+		}
+	}
+
+	pub fn min(self) -> BlockPos {
+		self.min
+	}
+
+	pub fn max(self) -> BlockPos {
+		self.max
+	}
+
+	/// how many blocks the region is long along each axis.<br>
+	/// this doesn't fit into an [`IVec3`] for regions that span most of the world
+	pub fn size(self) -> I64Vec3 {
+		IVec3::from(self.max).as_i64vec3() - IVec3::from(self.min).as_i64vec3() + I64Vec3::ONE
+	}
+
+	/// how many blocks are in the region, or [`u64::MAX`] if there are even more
+	pub fn volume(self) -> u64 {
+		let size = self.size().as_u64vec3();
+		size.x.saturating_mul(size.y).saturating_mul(size.z)
+	}
+
+	pub fn contains(self, pos: BlockPos) -> bool {
+		let pos = IVec3::from(pos);
+		pos.cmpge(self.min.into()).all() && pos.cmple(self.max.into()).all()
+	}
+
+	/// whether the position is on the outer layer of the region
+	pub fn is_on_edge(self, pos: BlockPos) -> bool {
+		let pos = IVec3::from(pos);
+		self.contains(pos.into())
+			&& (pos.cmpeq(self.min.into()).any() || pos.cmpeq(self.max.into()).any())
+	}
+
+	/// all positions in the region, where x changes the slowest and z the fastest
+	pub fn iter(self) -> impl Iterator<Item = BlockPos> {
+		let (min, max) = (self.min, self.max);
+		(min.x..=max.x).flat_map(move |x| {
+			(min.y..=max.y).flat_map(move |y| (min.z..=max.z).map(move |z| BlockPos::new(x, y, z)))
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn huge_regions_dont_overflow() {
+		let region =
+			BlockRegion::from_corners(BlockPos::new(i32::MIN, 0, 0), BlockPos::new(i32::MAX, 0, 0));
+		assert_eq!(region.size(), I64Vec3::new(1 << 32, 1, 1));
+		assert_eq!(region.volume(), 1 << 32);
+
+		let region = BlockRegion::from_corners(
+			BlockPos::new(i32::MIN, i32::MIN, i32::MIN),
+			BlockPos::new(i32::MAX, i32::MAX, i32::MAX),
+		);
+		assert_eq!(region.volume(), u64::MAX);
+	}
+}
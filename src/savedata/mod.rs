@@ -0,0 +1,149 @@
+mod nbt;
+mod schematic;
+mod sponge;
+
+use crate::game_world::GameWorld;
+use bevy::prelude::info;
+use std::{env, error::Error, fs, path::PathBuf};
+
+pub use self::schematic::Schematic;
+
+/// is written in front of every saved world, so that worlds from before it existed are recognized
+const SAVE_HEADER: &[u8] = b"voxel-game-world";
+/// has to be increased whenever the saved [`GameWorld`] changes.<br>
+/// worlds with another version can't be loaded, instead of being read as garbage
+const SAVE_VERSION: u32 = 1;
+
+pub fn save_game_world(world_name: &str, game_world: &GameWorld) -> Result<(), Box<dyn Error>> {
+	info!("Saving game world {}...", world_name);
+	let binary = game_world_to_bytes(game_world)?;
+	let path = get_savedata_path().join("worlds");
+	fs::create_dir_all(&path)?;
+	// TODO split up the world into multiple files
+	fs::write(path.join(format!("{}.bin", world_name)), binary)?;
+	info!("Saved game world {}", world_name);
+	Ok(())
+}
+
+pub fn load_game_world(world_name: &str) -> Result<GameWorld, Box<dyn Error>> {
+	info!("Loading game world {}...", world_name);
+	let path = get_savedata_path().join(format!("worlds/{}.bin", world_name));
+	let binary = fs::read(path)?;
+	let game_world = game_world_from_bytes(&binary)?;
+	info!("Loaded game world {}", world_name);
+	Ok(game_world)
+}
+
+fn game_world_to_bytes(game_world: &GameWorld) -> Result<Vec<u8>, Box<dyn Error>> {
+	let mut binary = SAVE_HEADER.to_vec();
+	binary.extend_from_slice(&SAVE_VERSION.to_le_bytes());
+	bincode::serialize_into(&mut binary, game_world)?;
+	Ok(binary)
+}
+
+fn game_world_from_bytes(binary: &[u8]) -> Result<GameWorld, Box<dyn Error>> {
+	let Some((version, binary)) = binary
+		.strip_prefix(SAVE_HEADER)
+		.and_then(|binary| binary.split_first_chunk())
+	else {
+		return Err("the world was saved by an older version of the game".into());
+	};
+	let version = u32::from_le_bytes(*version);
+	if version != SAVE_VERSION {
+		return Err(format!(
+			"the world was saved with version {version}, but only version {SAVE_VERSION} can be loaded"
+		)
+		.into());
+	}
+	Ok(bincode::deserialize(binary)?)
+}
+
+/// saves a schematic into the schematics folder, so that it can be imported into any world
+pub fn export_schematic(name: &str, schematic: &Schematic) -> Result<(), Box<dyn Error>> {
+	let path = get_savedata_path().join("schematics");
+	fs::create_dir_all(&path)?;
+	fs::write(
+		path.join(format!("{}.bin", schematic_file_name(name)?)),
+		schematic.to_bytes()?,
+	)?;
+	info!("Exported schematic {}", name);
+	Ok(())
+}
+
+/// loads a schematic from the schematics folder.<br>
+/// if there is no schematic with that name, a minecraft `.schem` file with that name is imported.
+pub fn import_schematic(name: &str) -> Result<Schematic, Box<dyn Error>> {
+	let path = get_savedata_path()
+		.join("schematics")
+		.join(schematic_file_name(name)?);
+	let bin_path = path.with_extension("bin");
+	if bin_path.exists() {
+		return Schematic::from_bytes(&fs::read(bin_path)?);
+	}
+	let schem_path = path.with_extension("schem");
+	if schem_path.exists() {
+		return sponge::from_sponge(&fs::read(schem_path)?, &sponge::BlockTable::load()?);
+	}
+	Err(format!("there is no schematic called {}", name).into())
+}
+
+/// makes sure that the name can't be used to access files outside of the schematics folder
+fn schematic_file_name(name: &str) -> Result<&str, Box<dyn Error>> {
+	let is_valid = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-';
+	if name.is_empty() || !name.chars().all(is_valid) {
+		return Err("schematic names can only contain letters, digits, '_' and '-'".into());
+	}
+	Ok(name)
+}
+
+/// gets the folder where all savedata is stored, which depends on the operating system
+pub fn get_savedata_path() -> PathBuf {
+	data_dir().join("BlueSheep3/Voxel Game")
+}
+
+#[cfg(target_os = "windows")]
+fn data_dir() -> PathBuf {
+	let path = env::var("APPDATA").expect("APPDATA not found");
+	let path = PathBuf::from(path);
+	path.parent().unwrap().join("LocalLow")
+}
+
+#[cfg(target_os = "macos")]
+fn data_dir() -> PathBuf {
+	let home = env::var("HOME").expect("HOME not found");
+	PathBuf::from(home).join("Library/Application Support")
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn data_dir() -> PathBuf {
+	if let Some(path) = env::var_os("XDG_DATA_HOME").filter(|p| !p.is_empty()) {
+		return PathBuf::from(path);
+	}
+	let home = env::var("HOME").expect("HOME not found");
+	PathBuf::from(home).join(".local/share")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn saved_worlds_can_be_loaded() {
+		let game_world = GameWorld {
+			seed: 1234,
+			..Default::default()
+		};
+		let binary = game_world_to_bytes(&game_world).unwrap();
+		assert_eq!(game_world_from_bytes(&binary).unwrap().seed, 1234);
+	}
+
+	#[test]
+	fn worlds_from_other_versions_are_rejected() {
+		let old_world = bincode::serialize(&GameWorld::default()).unwrap();
+		assert!(game_world_from_bytes(&old_world).is_err());
+
+		let mut newer_world = game_world_to_bytes(&GameWorld::default()).unwrap();
+		newer_world[SAVE_HEADER.len()] += 1;
+		assert!(game_world_from_bytes(&newer_world).is_err());
+	}
+}
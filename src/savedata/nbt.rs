@@ -0,0 +1,285 @@
+//! a reader for the binary NBT format that minecraft uses for its files.<br>
+//! only reading is needed, since schematics are only imported from minecraft.
+
+use flate2::read::GzDecoder;
+use std::{collections::HashMap, error::Error, io::Read};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tag {
+	Byte(i8),
+	Short(i16),
+	Int(i32),
+	Long(i64),
+	Float(f32),
+	Double(f64),
+	ByteArray(Vec<u8>),
+	String(String),
+	List(Vec<Self>),
+	Compound(HashMap<String, Self>),
+	IntArray(Vec<i32>),
+	LongArray(Vec<i64>),
+}
+
+impl Tag {
+	pub fn get(&self, name: &str) -> Option<&Self> {
+		match self {
+			Self::Compound(tags) => tags.get(name),
+			_ => None,
+		}
+	}
+
+	/// gets any whole number, since different programs save the same values with different types
+	pub fn as_int(&self) -> Option<i64> {
+		match *self {
+			Self::Byte(value) => Some(value.into()),
+			Self::Short(value) => Some(value.into()),
+			Self::Int(value) => Some(value.into()),
+			Self::Long(value) => Some(value),
+			_ => None,
+		}
+	}
+}
+
+/// reads the root tag of a file, which might be compressed with gzip
+pub fn read(bytes: &[u8]) -> Result<Tag, Box<dyn Error>> {
+	const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+	if bytes.starts_with(&GZIP_MAGIC) {
+		return read(&decompress(bytes, MAX_DECOMPRESSED_LEN)?);
+	}
+	let mut reader = Reader { bytes };
+	let tag_type = reader.u8()?;
+	// the name of the root tag is not used for anything
+	reader.string()?;
+	reader.payload(tag_type, 0)
+}
+
+/// files that decompress into more bytes than this are rejected,
+/// so that a small file can't fill up the memory
+const MAX_DECOMPRESSED_LEN: u64 = 64 * 1024 * 1024;
+
+fn decompress(bytes: &[u8], max_len: u64) -> Result<Vec<u8>, Box<dyn Error>> {
+	let mut decompressed = Vec::new();
+	// reads one byte more than allowed, to notice when there is too much
+	GzDecoder::new(bytes)
+		.take(max_len + 1)
+		.read_to_end(&mut decompressed)?;
+	if decompressed.len() as u64 > max_len {
+		return Err(format!("the file is bigger than {max_len} bytes when decompressed").into());
+	}
+	Ok(decompressed)
+}
+
+/// nested lists and compounds deeper than this are rejected, so that the stack can't overflow
+const MAX_DEPTH: u32 = 512;
+
+struct Reader<'a> {
+	bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+	fn take<const N: usize>(&mut self) -> Result<[u8; N], Box<dyn Error>> {
+		let Some((first, rest)) = self.bytes.split_first_chunk::<N>() else {
+			return Err("the file ended unexpectedly".into());
+		};
+		self.bytes = rest;
+		Ok(*first)
+	}
+
+	fn u8(&mut self) -> Result<u8, Box<dyn Error>> {
+		Ok(self.take::<1>()?[0])
+	}
+
+	fn u16(&mut self) -> Result<u16, Box<dyn Error>> {
+		Ok(u16::from_be_bytes(self.take()?))
+	}
+
+	fn i32(&mut self) -> Result<i32, Box<dyn Error>> {
+		Ok(i32::from_be_bytes(self.take()?))
+	}
+
+	fn i64(&mut self) -> Result<i64, Box<dyn Error>> {
+		Ok(i64::from_be_bytes(self.take()?))
+	}
+
+	/// reads the length of an array. negative lengths are treated as empty
+	fn len(&mut self) -> Result<usize, Box<dyn Error>> {
+		let len = self.i32()?.max(0) as usize;
+		// every element needs at least one byte, so this avoids huge allocations for broken files
+		if len > self.bytes.len() {
+			return Err("an array is longer than the file".into());
+		}
+		Ok(len)
+	}
+
+	fn string(&mut self) -> Result<String, Box<dyn Error>> {
+		let len = self.u16()? as usize;
+		if len > self.bytes.len() {
+			return Err("the file ended unexpectedly".into());
+		}
+		let (string, rest) = self.bytes.split_at(len);
+		self.bytes = rest;
+		// minecraft uses a modified version of UTF-8, which is the same for normal text
+		Ok(String::from_utf8_lossy(string).into_owned())
+	}
+
+	fn payload(&mut self, tag_type: u8, depth: u32) -> Result<Tag, Box<dyn Error>> {
+		if depth > MAX_DEPTH {
+			return Err("the tags are nested too deeply".into());
+		}
+		let tag = match tag_type {
+			1 => Tag::Byte(self.u8()? as i8),
+			2 => Tag::Short(self.u16()? as i16),
+			3 => Tag::Int(self.i32()?),
+			4 => Tag::Long(self.i64()?),
+			5 => Tag::Float(f32::from_bits(self.i32()? as u32)),
+			6 => Tag::Double(f64::from_bits(self.i64()? as u64)),
+			7 => {
+				let len = self.len()?;
+				let (array, rest) = self.bytes.split_at(len);
+				self.bytes = rest;
+				Tag::ByteArray(array.to_vec())
+			}
+			8 => Tag::String(self.string()?),
+			9 => {
+				let element_type = self.u8()?;
+				let len = self.len()?;
+				let elements = (0..len)
+					.map(|_| self.payload(element_type, depth + 1))
+					.collect::<Result<_, _>>()?;
+				Tag::List(elements)
+			}
+			10 => {
+				let mut tags = HashMap::new();
+				loop {
+					let tag_type = self.u8()?;
+					if tag_type == 0 {
+						break;
+					}
+					let name = self.string()?;
+					tags.insert(name, self.payload(tag_type, depth + 1)?);
+				}
+				Tag::Compound(tags)
+			}
+			11 => {
+				let len = self.len()?;
+				Tag::IntArray((0..len).map(|_| self.i32()).collect::<Result<_, _>>()?)
+			}
+			12 => {
+				let len = self.len()?;
+				Tag::LongArray((0..len).map(|_| self.i64()).collect::<Result<_, _>>()?)
+			}
+			_ => return Err(format!("unknown tag type {tag_type}").into()),
+		};
+		Ok(tag)
+	}
+}
+
+/// writes a root tag without compressing it, so that tests can create files
+#[cfg(test)]
+pub fn write(tag: &Tag) -> Vec<u8> {
+	fn type_id(tag: &Tag) -> u8 {
+		match tag {
+			Tag::Byte(_) => 1,
+			Tag::Short(_) => 2,
+			Tag::Int(_) => 3,
+			Tag::Long(_) => 4,
+			Tag::Float(_) => 5,
+			Tag::Double(_) => 6,
+			Tag::ByteArray(_) => 7,
+			Tag::String(_) => 8,
+			Tag::List(_) => 9,
+			Tag::Compound(_) => 10,
+			Tag::IntArray(_) => 11,
+			Tag::LongArray(_) => 12,
+		}
+	}
+
+	fn string(bytes: &mut Vec<u8>, string: &str) {
+		bytes.extend((string.len() as u16).to_be_bytes());
+		bytes.extend(string.as_bytes());
+	}
+
+	fn payload(bytes: &mut Vec<u8>, tag: &Tag) {
+		match tag {
+			Tag::Byte(value) => bytes.extend(value.to_be_bytes()),
+			Tag::Short(value) => bytes.extend(value.to_be_bytes()),
+			Tag::Int(value) => bytes.extend(value.to_be_bytes()),
+			Tag::Long(value) => bytes.extend(value.to_be_bytes()),
+			Tag::Float(value) => bytes.extend(value.to_be_bytes()),
+			Tag::Double(value) => bytes.extend(value.to_be_bytes()),
+			Tag::ByteArray(array) => {
+				bytes.extend((array.len() as i32).to_be_bytes());
+				bytes.extend(array);
+			}
+			Tag::String(value) => string(bytes, value),
+			Tag::List(elements) => {
+				bytes.push(elements.first().map_or(0, type_id));
+				bytes.extend((elements.len() as i32).to_be_bytes());
+				for element in elements {
+					payload(bytes, element);
+				}
+			}
+			Tag::Compound(tags) => {
+				for (name, tag) in tags {
+					bytes.push(type_id(tag));
+					string(bytes, name);
+					payload(bytes, tag);
+				}
+				bytes.push(0);
+			}
+			Tag::IntArray(array) => {
+				bytes.extend((array.len() as i32).to_be_bytes());
+				bytes.extend(array.iter().flat_map(|value| value.to_be_bytes()));
+			}
+			Tag::LongArray(array) => {
+				bytes.extend((array.len() as i32).to_be_bytes());
+				bytes.extend(array.iter().flat_map(|value| value.to_be_bytes()));
+			}
+		}
+	}
+
+	let mut bytes = vec![type_id(tag)];
+	string(&mut bytes, "");
+	payload(&mut bytes, tag);
+	bytes
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use flate2::{write::GzEncoder, Compression};
+	use std::io::Write;
+
+	#[test]
+	fn round_trip() {
+		let tag = Tag::Compound(HashMap::from([
+			("byte".to_owned(), Tag::Byte(-3)),
+			("double".to_owned(), Tag::Double(0.25)),
+			("text".to_owned(), Tag::String("hello".to_owned())),
+			(
+				"list".to_owned(),
+				Tag::List(vec![Tag::LongArray(vec![1, -2]), Tag::LongArray(vec![])]),
+			),
+			("ints".to_owned(), Tag::IntArray(vec![i32::MIN, 7])),
+		]));
+		assert_eq!(read(&write(&tag)).unwrap(), tag);
+	}
+
+	#[test]
+	fn rejects_deep_nesting() {
+		let mut tag = Tag::List(Vec::new());
+		for _ in 0..MAX_DEPTH + 2 {
+			tag = Tag::List(vec![tag]);
+		}
+		assert!(read(&write(&tag)).is_err());
+	}
+
+	#[test]
+	fn rejects_huge_decompressed_files() {
+		let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+		encoder.write_all(&[0; 1025]).unwrap();
+		let compressed = encoder.finish().unwrap();
+		assert_eq!(decompress(&compressed, 1025).unwrap().len(), 1025);
+		assert!(decompress(&compressed, 1024).is_err());
+	}
+}
@@ -0,0 +1,262 @@
+//! a file format for saving a box of blocks, so that it can be placed into another world.<br>
+//! blocks are saved with their names instead of their ids, so that files keep working
+//! when blocks are added or reordered.
+
+use crate::block::{Block, BlockId};
+use bevy::math::IVec3;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, error::Error};
+
+/// has to be changed whenever the format changes, so that old files are not read wrongly
+const VERSION: u32 = 1;
+
+/// the most blocks a schematic can contain, so that broken files can't allocate too much memory
+const MAX_VOLUME: usize = 256 * 256 * 256;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Schematic {
+	version: u32,
+	/// how many blocks the schematic is long along each axis
+	size: [u32; 3],
+	/// where the smallest corner is placed, relative to the position the schematic is placed at
+	offset: [i32; 3],
+	/// the names of the blocks, which are referred to by their index in this list
+	palette: Vec<String>,
+	/// runs of the same block as `(length, palette index)`, in the order of [`BlockRegion::iter`]
+	blocks: Vec<(u32, u16)>,
+	/// the data of blocks that don't have the default data, as `(index, data)`
+	data: Vec<(u32, u8)>,
+}
+
+impl Schematic {
+	/// creates a schematic from blocks in the order of [`BlockRegion::iter`]
+	pub fn from_blocks(
+		size: IVec3,
+		offset: IVec3,
+		blocks: impl IntoIterator<Item = Block>,
+	) -> Result<Self, Box<dyn Error>> {
+		let mut schematic = Self {
+			version: VERSION,
+			size: size.as_uvec3().to_array(),
+			offset: offset.to_array(),
+			palette: Vec::new(),
+			blocks: Vec::new(),
+			data: Vec::new(),
+		};
+		let mut palette_indices = HashMap::new();
+		let mut count = 0;
+		for (index, block) in blocks.into_iter().enumerate() {
+			let palette_index = *palette_indices.entry(block.id).or_insert_with(|| {
+				schematic.palette.push(block.id.to_debug_name().to_owned());
+				schematic.palette.len() as u16 - 1
+			});
+			match schematic.blocks.last_mut() {
+				Some((len, last)) if *last == palette_index => *len += 1,
+				_ => schematic.blocks.push((1, palette_index)),
+			}
+			if block != Block::from_id(block.id) {
+				schematic.data.push((index as u32, block.raw_data()));
+			}
+			count += 1;
+		}
+		if count != schematic.volume()? {
+			return Err("the number of blocks does not match the size".into());
+		}
+		Ok(schematic)
+	}
+
+	pub fn size(&self) -> IVec3 {
+		IVec3::from_array(self.size.map(|len| len as i32))
+	}
+
+	pub fn offset(&self) -> IVec3 {
+		IVec3::from_array(self.offset)
+	}
+
+	/// the number of blocks in the schematic.<br>
+	/// fails if it is larger than [`MAX_VOLUME`]
+	fn volume(&self) -> Result<usize, Box<dyn Error>> {
+		self.size
+			.iter()
+			.try_fold(1usize, |volume, &len| volume.checked_mul(len as usize))
+			.filter(|&volume| volume <= MAX_VOLUME)
+			.ok_or_else(|| format!("the schematic is larger than {MAX_VOLUME} blocks").into())
+	}
+
+	/// all blocks in the order of [`BlockRegion::iter`].<br>
+	/// fails if the schematic contains unknown blocks or invalid data.
+	pub fn to_blocks(&self) -> Result<Vec<Block>, Box<dyn Error>> {
+		if self.version != VERSION {
+			return Err(format!("unsupported schematic version {}", self.version).into());
+		}
+		let palette = self
+			.palette
+			.iter()
+			.map(|name| {
+				BlockId::from_debug_name(name).ok_or_else(|| format!("unknown block name: {name}"))
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let volume = self.volume()?;
+		let mut blocks = Vec::with_capacity(volume);
+		for &(len, palette_index) in &self.blocks {
+			let id = *palette
+				.get(palette_index as usize)
+				.ok_or("a block is not in the palette")?;
+			if blocks.len() + len as usize > volume {
+				return Err("there are more blocks than the size allows".into());
+			}
+			blocks.extend(std::iter::repeat_n(Block::from_id(id), len as usize));
+		}
+		if blocks.len() != volume {
+			return Err("the number of blocks does not match the size".into());
+		}
+		for &(index, data) in &self.data {
+			let block = blocks
+				.get_mut(index as usize)
+				.ok_or("block data is outside of the schematic")?;
+			*block = Block::from_raw_data(block.id, data)
+				.ok_or_else(|| format!("invalid data {data} for {}", block.id.to_debug_name()))?;
+		}
+		Ok(blocks)
+	}
+
+	pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+		Ok(bincode::serialize(self)?)
+	}
+
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+		let schematic = bincode::deserialize::<Self>(bytes)?;
+		// makes sure that errors are found when loading, not when placing
+		schematic.to_blocks()?;
+		Ok(schematic)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		block::{
+			fluid::{FluidKind, FluidState},
+			prelude::*,
+		},
+		game_world::{
+			chunk::{BlockArray, Chunk},
+			GameWorld,
+		},
+		pos::{BlockPos, BlockRegion, ChunkPos},
+	};
+
+	fn water(distance: u8) -> Block {
+		FluidKind::Water.block(FluidState::flowing(distance))
+	}
+
+	fn test_blocks() -> Vec<Block> {
+		let mut blocks = vec![Stone::BLOCK; 2 * 3 * 4];
+		blocks[3] = Air::BLOCK;
+		blocks[4] = Log::BLOCK;
+		blocks[10] = water(3);
+		blocks[11] = water(0);
+		blocks
+	}
+
+	fn test_world() -> GameWorld {
+		let mut game_world = GameWorld::default();
+		game_world.chunks.insert(
+			ChunkPos::new(0, 0, 0),
+			Chunk {
+				blocks: Box::new(BlockArray::ALL_AIR),
+				loaded: Default::default(),
+				generation_state: Default::default(),
+			},
+		);
+		game_world
+	}
+
+	#[test]
+	fn bytes_round_trip() {
+		let schematic =
+			Schematic::from_blocks(IVec3::new(2, 3, 4), IVec3::new(-1, 0, 2), test_blocks())
+				.unwrap();
+		let loaded = Schematic::from_bytes(&schematic.to_bytes().unwrap()).unwrap();
+		assert_eq!(loaded, schematic);
+		assert_eq!(loaded.to_blocks().unwrap(), test_blocks());
+		assert_eq!(loaded.size(), IVec3::new(2, 3, 4));
+		assert_eq!(loaded.offset(), IVec3::new(-1, 0, 2));
+	}
+
+	#[test]
+	fn is_compact() {
+		let schematic = Schematic::from_blocks(
+			IVec3::splat(16),
+			IVec3::ZERO,
+			vec![Stone::BLOCK; 16 * 16 * 16],
+		)
+		.unwrap();
+		assert_eq!(schematic.palette, ["Stone"]);
+		assert_eq!(schematic.blocks, [(16 * 16 * 16, 0)]);
+		assert!(schematic.data.is_empty());
+		assert!(schematic.to_bytes().unwrap().len() < 100);
+	}
+
+	#[test]
+	fn world_round_trip() {
+		let mut game_world = test_world();
+		let region = BlockRegion::from_corners(BlockPos::new(1, 1, 1), BlockPos::new(2, 3, 4));
+		game_world.set_blocks(region.iter().zip(test_blocks()));
+		let copied = region
+			.iter()
+			.map(|pos| *game_world.get_block_at(pos).unwrap());
+		let bytes = Schematic::from_blocks(region.size().as_ivec3(), IVec3::ZERO, copied)
+			.unwrap()
+			.to_bytes()
+			.unwrap();
+
+		let mut other_world = test_world();
+		let schematic = Schematic::from_bytes(&bytes).unwrap();
+		let origin = BlockPos::new(10, 5, 10);
+		let placed = BlockRegion::from_corners(origin, origin + (schematic.size() - 1));
+		other_world.set_blocks(placed.iter().zip(schematic.to_blocks().unwrap()));
+		for (pos, block) in placed.iter().zip(test_blocks()) {
+			assert_eq!(other_world.get_block_at(pos), Some(&block));
+		}
+	}
+
+	#[test]
+	fn wrong_number_of_blocks_fails() {
+		assert!(Schematic::from_blocks(IVec3::new(2, 2, 2), IVec3::ZERO, test_blocks()).is_err());
+	}
+
+	#[test]
+	fn invalid_schematics_fail() {
+		let valid =
+			Schematic::from_blocks(IVec3::new(2, 3, 4), IVec3::ZERO, test_blocks()).unwrap();
+		let mut unknown_block = valid.clone();
+		unknown_block.palette[0] = "NotABlock".to_owned();
+		let mut too_few_blocks = valid.clone();
+		too_few_blocks.size = [3, 3, 4];
+		let mut invalid_data = valid.clone();
+		invalid_data.data.push((0, 1));
+		let mut huge = valid.clone();
+		huge.size = [u32::MAX; 3];
+		huge.blocks = vec![(u32::MAX, 0)];
+		let mut too_large = valid.clone();
+		too_large.size = [257, 256, 256];
+		too_large.blocks = vec![(257 * 256 * 256, 0)];
+		let mut newer_version = valid;
+		newer_version.version += 1;
+
+		for schematic in [
+			unknown_block,
+			too_few_blocks,
+			invalid_data,
+			huge,
+			too_large,
+			newer_version,
+		] {
+			assert!(Schematic::from_bytes(&schematic.to_bytes().unwrap()).is_err());
+		}
+		assert!(Schematic::from_bytes(&[1, 2, 3]).is_err());
+	}
+}
@@ -0,0 +1,311 @@
+//! imports `.schem` files in the sponge schematic format, which minecraft tools like WorldEdit use.<br>
+//! minecraft blocks are turned into blocks of this game with a table from
+//! `assets/schematics/minecraft_blocks.ron`.
+
+use super::{
+	nbt::{self, Tag},
+	schematic::Schematic,
+};
+use crate::block::{Block, BlockId};
+use bevy::{asset::io::file::FileAssetReader, log::warn, math::IVec3};
+use serde::Deserialize;
+use std::{collections::HashMap, error::Error, fs};
+
+/// how minecraft blocks are turned into blocks of this game
+#[derive(Debug, Deserialize)]
+pub struct BlockTable {
+	/// the block that is used for all minecraft blocks that are not in the table
+	fallback: String,
+	/// a map from minecraft block names without their states to block names of this game
+	blocks: HashMap<String, String>,
+}
+
+impl BlockTable {
+	/// reads the table from the assets folder, wherever bevy would look for it
+	pub fn load() -> Result<Self, Box<dyn Error>> {
+		let path = FileAssetReader::get_base_path().join("assets/schematics/minecraft_blocks.ron");
+		let source = fs::read_to_string(path)?;
+		Self::from_ron(&source)
+	}
+
+	fn from_ron(source: &str) -> Result<Self, Box<dyn Error>> {
+		let table = ron::from_str::<Self>(source)?;
+		for name in table.blocks.values().chain([&table.fallback]) {
+			if BlockId::from_debug_name(name).is_none() {
+				return Err(format!("unknown block name: {name}").into());
+			}
+		}
+		Ok(table)
+	}
+
+	/// the block for a minecraft block like `minecraft:oak_log[axis=y]`,
+	/// or `None` if it is not in the table
+	fn get(&self, minecraft_name: &str) -> Option<Block> {
+		let name = minecraft_name.split('[').next().unwrap_or_default();
+		let name = self.blocks.get(name)?;
+		BlockId::from_debug_name(name).map(Block::from_id)
+	}
+
+	fn fallback(&self) -> Block {
+		Block::from_id(BlockId::from_debug_name(&self.fallback).expect("is checked when loading"))
+	}
+}
+
+/// reads a sponge schematic of version 2 or 3, which may be compressed with gzip
+pub fn from_sponge(bytes: &[u8], table: &BlockTable) -> Result<Schematic, Box<dyn Error>> {
+	let root = nbt::read(bytes)?;
+	// version 3 puts everything into another compound
+	let schematic = root.get("Schematic").unwrap_or(&root);
+	let int = |name: &str| {
+		schematic
+			.get(name)
+			.and_then(Tag::as_int)
+			.ok_or_else(|| format!("the schematic has no {name}"))
+	};
+	let version = int("Version")?;
+	// the sizes are unsigned shorts, but NBT only has signed ones
+	let size = IVec3::new(
+		int("Width")? as u16 as i32,
+		int("Height")? as u16 as i32,
+		int("Length")? as u16 as i32,
+	);
+
+	let (palette, block_data) = match version {
+		2 => (schematic.get("Palette"), schematic.get("BlockData")),
+		3 => {
+			let blocks = schematic
+				.get("Blocks")
+				.ok_or("the schematic has no Blocks")?;
+			(blocks.get("Palette"), blocks.get("Data"))
+		}
+		_ => return Err(format!("unsupported sponge schematic version {version}").into()),
+	};
+	let Some(Tag::Compound(palette)) = palette else {
+		return Err("the schematic has no Palette".into());
+	};
+	let Some(Tag::ByteArray(block_data)) = block_data else {
+		return Err("the schematic has no block data".into());
+	};
+
+	let mut unknown_names = Vec::new();
+	let mut palette_blocks = HashMap::new();
+	for (name, index) in palette {
+		let index = index.as_int().ok_or("a palette index is not a number")?;
+		let block = table.get(name).unwrap_or_else(|| {
+			unknown_names.push(name.as_str());
+			table.fallback()
+		});
+		palette_blocks.insert(index, block);
+	}
+	if !unknown_names.is_empty() {
+		unknown_names.sort();
+		warn!(
+			"replaced unknown blocks with {}: {}",
+			table.fallback,
+			unknown_names.join(", ")
+		);
+	}
+
+	let sponge_blocks = read_varints(block_data)?
+		.into_iter()
+		.map(|index| {
+			palette_blocks
+				.get(&index)
+				.copied()
+				.ok_or_else(|| format!("the palette index {index} does not exist"))
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	let volume = size.x as usize * size.y as usize * size.z as usize;
+	if sponge_blocks.len() != volume {
+		return Err("the number of blocks does not match the size".into());
+	}
+
+	// sponge orders blocks by y, then z, then x, but schematics use the order of `BlockRegion`
+	let (width, length) = (size.x as usize, size.z as usize);
+	let blocks = (0..size.x as usize).flat_map(|x| {
+		let sponge_blocks = &sponge_blocks;
+		(0..size.y as usize).flat_map(move |y| {
+			(0..length).map(move |z| sponge_blocks[x + z * width + y * width * length])
+		})
+	});
+	Schematic::from_blocks(size, worldedit_offset(schematic), blocks)
+}
+
+/// where WorldEdit copied the blocks from, relative to the player
+fn worldedit_offset(schematic: &Tag) -> IVec3 {
+	let metadata = schematic.get("Metadata");
+	let offset = |name: &str| {
+		metadata
+			.and_then(|metadata| metadata.get(name))
+			.and_then(Tag::as_int)
+			.unwrap_or(0) as i32
+	};
+	IVec3::new(
+		offset("WEOffsetX"),
+		offset("WEOffsetY"),
+		offset("WEOffsetZ"),
+	)
+}
+
+/// block data is saved as numbers with a variable length,
+/// where the highest bit of every byte says whether another byte follows
+fn read_varints(bytes: &[u8]) -> Result<Vec<i64>, Box<dyn Error>> {
+	let mut values = Vec::new();
+	let mut value = 0;
+	let mut shift = 0;
+	for &byte in bytes {
+		value |= ((byte & 0x7f) as i64) << shift;
+		if byte & 0x80 == 0 {
+			values.push(value);
+			value = 0;
+			shift = 0;
+			continue;
+		}
+		shift += 7;
+		if shift > 28 {
+			return Err("a number in the block data is too long".into());
+		}
+	}
+	if shift != 0 {
+		return Err("the block data ended in the middle of a number".into());
+	}
+	Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::block::prelude::*;
+	use flate2::{write::GzEncoder, Compression};
+	use std::io::Write;
+
+	fn compound(tags: impl IntoIterator<Item = (&'static str, Tag)>) -> Tag {
+		Tag::Compound(
+			tags.into_iter()
+				.map(|(name, tag)| (name.to_owned(), tag))
+				.collect(),
+		)
+	}
+
+	/// a 2x2x3 schematic, where the bottom layer is stone,
+	/// and the top layer has planks at x = 1 and something unknown at z = 2
+	fn test_schematic(version: i32) -> Tag {
+		let palette = compound([
+			("minecraft:stone", Tag::Int(0)),
+			("minecraft:air", Tag::Int(1)),
+			("minecraft:oak_planks", Tag::Int(2)),
+			("minecraft:oak_stairs[facing=north]", Tag::Int(200)),
+		]);
+		// y, then z, then x. the unknown block needs two bytes
+		let mut data = vec![0; 6];
+		data.extend([1, 2, 1, 2, 0xc8, 0x01, 0xc8, 0x01]);
+		let size = [
+			("Width", Tag::Short(2)),
+			("Height", Tag::Short(2)),
+			("Length", Tag::Short(3)),
+		];
+		let metadata = compound([("WEOffsetX", Tag::Int(-1)), ("WEOffsetY", Tag::Int(2))]);
+		match version {
+			2 => compound(
+				[
+					("Version", Tag::Int(2)),
+					("Palette", palette),
+					("BlockData", Tag::ByteArray(data)),
+					("Metadata", metadata),
+				]
+				.into_iter()
+				.chain(size),
+			),
+			_ => compound([(
+				"Schematic",
+				compound(
+					[
+						("Version", Tag::Int(3)),
+						(
+							"Blocks",
+							compound([("Palette", palette), ("Data", Tag::ByteArray(data))]),
+						),
+						("Metadata", metadata),
+					]
+					.into_iter()
+					.chain(size),
+				),
+			)]),
+		}
+	}
+
+	fn table() -> BlockTable {
+		BlockTable::load().unwrap()
+	}
+
+	fn expected_blocks() -> Vec<Block> {
+		let mut blocks = Vec::new();
+		for x in 0..2 {
+			for y in 0..2 {
+				for z in 0..3 {
+					blocks.push(match (x, y, z) {
+						(_, 0, _) => Stone::BLOCK,
+						(_, 1, 2) => Stone::BLOCK,
+						(0, 1, _) => Air::BLOCK,
+						_ => Planks::BLOCK,
+					});
+				}
+			}
+		}
+		blocks
+	}
+
+	#[test]
+	fn reads_version_2() {
+		let bytes = nbt::write(&test_schematic(2));
+		let schematic = from_sponge(&bytes, &table()).unwrap();
+		assert_eq!(schematic.size(), IVec3::new(2, 2, 3));
+		assert_eq!(schematic.offset(), IVec3::new(-1, 2, 0));
+		assert_eq!(schematic.to_blocks().unwrap(), expected_blocks());
+	}
+
+	#[test]
+	fn reads_compressed_version_3() {
+		let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+		encoder.write_all(&nbt::write(&test_schematic(3))).unwrap();
+		let bytes = encoder.finish().unwrap();
+		let schematic = from_sponge(&bytes, &table()).unwrap();
+		assert_eq!(schematic.to_blocks().unwrap(), expected_blocks());
+	}
+
+	#[test]
+	fn round_trips_through_own_format() {
+		let schematic = from_sponge(&nbt::write(&test_schematic(2)), &table()).unwrap();
+		let loaded = Schematic::from_bytes(&schematic.to_bytes().unwrap()).unwrap();
+		assert_eq!(loaded, schematic);
+	}
+
+	#[test]
+	fn rejects_broken_files() {
+		let mut wrong_size = test_schematic(2);
+		let Tag::Compound(tags) = &mut wrong_size else {
+			unreachable!();
+		};
+		tags.insert("Length".to_owned(), Tag::Short(4));
+		assert!(from_sponge(&nbt::write(&wrong_size), &table()).is_err());
+
+		let bytes = nbt::write(&test_schematic(2));
+		assert!(from_sponge(&bytes[..bytes.len() - 5], &table()).is_err());
+		assert!(from_sponge(&[], &table()).is_err());
+	}
+
+	#[test]
+	fn table_only_contains_known_blocks() {
+		let table = table();
+		assert_eq!(table.get("minecraft:oak_log[axis=x]"), Some(Log::BLOCK));
+		assert_eq!(table.get("minecraft:cave_air"), Some(Air::BLOCK));
+		assert_eq!(table.get("minecraft:beacon"), None);
+		assert!(BlockTable::from_ron(r#"(fallback: "Nope", blocks: {})"#).is_err());
+	}
+
+	#[test]
+	fn varints() {
+		assert_eq!(read_varints(&[0, 0x7f, 0x80, 0x01]).unwrap(), [0, 127, 128]);
+		assert!(read_varints(&[0x80]).is_err());
+	}
+}
@@ -0,0 +1,182 @@
+//! the sky around the player, which changes color with the time of day,
+//! and the sun and moon moving across it
+
+use crate::{
+	entity::movement::MovementSet,
+	game_world::{GameWorld, WorldTime},
+	GlobalState,
+};
+use bevy::{prelude::*, render::mesh::SphereKind};
+
+pub struct SkyPlugin;
+
+impl Plugin for SkyPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(OnEnter(GlobalState::InWorld), spawn)
+			.add_systems(OnExit(GlobalState::InWorld), despawn)
+			.add_systems(
+				Update,
+				(update_sky_colors, move_sky.after(MovementSet::Camera))
+					.run_if(in_state(GlobalState::InWorld)),
+			);
+	}
+}
+
+/// the distance from the camera to the sky. this has to be less than the far plane of the camera.
+const SKY_DISTANCE: f32 = 500.0;
+/// the distance from the camera to the sun and the moon, so that they are drawn in front of the sky
+const SUN_DISTANCE: f32 = 400.0;
+const SUN_SIZE: f32 = 60.0;
+const MOON_SIZE: f32 = 40.0;
+
+const DAY_ZENITH: Srgba = Srgba::rgb(0.25, 0.5, 0.95);
+const DAY_HORIZON: Srgba = Srgba::rgb(0.7, 0.82, 0.97);
+const NIGHT_ZENITH: Srgba = Srgba::rgb(0.0, 0.0, 0.03);
+const NIGHT_HORIZON: Srgba = Srgba::rgb(0.04, 0.05, 0.1);
+const SUNSET_HORIZON: Srgba = Srgba::rgb(0.95, 0.5, 0.25);
+
+/// anything that is part of the sky and follows the camera
+#[derive(Component, Default)]
+struct SkyPart;
+
+/// a sphere around the camera with a color gradient from the horizon to the top
+#[derive(Component)]
+#[require(SkyPart)]
+struct SkyDome;
+
+/// a billboard in the sky, which is always on the opposite side of the other one
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+#[require(SkyPart)]
+enum CelestialBody {
+	Sun,
+	Moon,
+}
+
+fn spawn(
+	mut commands: Commands,
+	mut meshes: ResMut<Assets<Mesh>>,
+	mut materials: ResMut<Assets<StandardMaterial>>,
+	asset_server: Res<AssetServer>,
+) {
+	let dome_mesh = Sphere::new(SKY_DISTANCE).mesh().kind(SphereKind::Uv {
+		sectors: 24,
+		stacks: 12,
+	});
+	commands.spawn((
+		SkyDome,
+		Name::new("SkyDome"),
+		Mesh3d(meshes.add(dome_mesh)),
+		MeshMaterial3d(materials.add(StandardMaterial {
+			unlit: true,
+			fog_enabled: false,
+			// the camera is inside of the sphere
+			cull_mode: None,
+			..default()
+		})),
+		Transform::default(),
+	));
+
+	let mut spawn_billboard = |texture: &str, size: f32| {
+		(
+			Mesh3d(meshes.add(Rectangle::from_length(size))),
+			MeshMaterial3d(materials.add(StandardMaterial {
+				base_color_texture: Some(asset_server.load(texture)),
+				alpha_mode: AlphaMode::Blend,
+				unlit: true,
+				fog_enabled: false,
+				..default()
+			})),
+			Transform::default(),
+		)
+	};
+	let sun = spawn_billboard("sprite/Sun.png", SUN_SIZE);
+	let moon = spawn_billboard("sprite/Moon.png", MOON_SIZE);
+	commands.spawn((CelestialBody::Sun, Name::new("Sun"), sun));
+	commands.spawn((CelestialBody::Moon, Name::new("Moon"), moon));
+}
+
+fn despawn(
+	mut commands: Commands,
+	sky: Query<Entity, With<SkyPart>>,
+	mut clear_color: ResMut<ClearColor>,
+) {
+	for entity in &sky {
+		commands.entity(entity).despawn_recursive();
+	}
+	*clear_color = ClearColor::default();
+}
+
+/// the color of the sky straight up and at the horizon
+fn sky_colors(time: WorldTime) -> (Srgba, Srgba) {
+	let daylight = time.daylight();
+	let zenith = NIGHT_ZENITH.mix(&DAY_ZENITH, daylight);
+	let horizon = NIGHT_HORIZON.mix(&DAY_HORIZON, daylight);
+	// the horizon turns orange while the sun is close to it
+	let sunset = 1.0 - (time.sun_direction().y.abs() / 0.3).min(1.0);
+	let horizon = horizon.mix(&SUNSET_HORIZON, sunset * 0.6);
+	(zenith, horizon)
+}
+
+fn update_sky_colors(
+	game_world: Res<GameWorld>,
+	dome: Query<&Mesh3d, With<SkyDome>>,
+	mut meshes: ResMut<Assets<Mesh>>,
+	mut clear_color: ResMut<ClearColor>,
+	mut last_time: Local<Option<WorldTime>>,
+) {
+	let time = game_world.time;
+	if *last_time == Some(time) {
+		return;
+	}
+	let Ok(dome) = dome.get_single() else {
+		return;
+	};
+	let Some(mesh) = meshes.get_mut(&dome.0) else {
+		return;
+	};
+	*last_time = Some(time);
+
+	let (zenith, horizon) = sky_colors(time);
+	let Some(positions) = mesh
+		.attribute(Mesh::ATTRIBUTE_POSITION)
+		.and_then(|positions| positions.as_float3())
+	else {
+		return;
+	};
+	let colors = positions
+		.iter()
+		.map(|pos| {
+			let height = (pos[1] / SKY_DISTANCE).max(0.0);
+			horizon.mix(&zenith, height.sqrt()).to_f32_array()
+		})
+		.collect::<Vec<_>>();
+	mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+	// the clear color can be seen when the sky is not rendered yet
+	*clear_color = ClearColor(horizon.into());
+}
+
+/// keeps the sky around the camera, and moves the sun and moon across it
+fn move_sky(
+	game_world: Res<GameWorld>,
+	cam: Query<&Transform, (With<Camera3d>, Without<SkyPart>)>,
+	mut dome: Query<&mut Transform, (With<SkyDome>, Without<CelestialBody>)>,
+	mut bodies: Query<(&mut Transform, &CelestialBody), Without<SkyDome>>,
+) {
+	let Ok(cam) = cam.get_single() else {
+		return;
+	};
+	let cam_pos = cam.translation;
+	let sun_dir = game_world.time.sun_direction();
+
+	for mut trans in &mut dome {
+		trans.translation = cam_pos;
+	}
+	for (mut trans, body) in &mut bodies {
+		let dir = match body {
+			CelestialBody::Sun => sun_dir,
+			CelestialBody::Moon => -sun_dir,
+		};
+		// the front of a billboard has to face the camera
+		*trans = Transform::from_translation(cam_pos + dir * SUN_DISTANCE).looking_to(dir, Vec3::Z);
+	}
+}
@@ -1,4 +1,8 @@
-use crate::GlobalState;
+use crate::{
+	entity::{health::Health, player::Player},
+	item::{Inventory, HOTBAR_SIZE},
+	GlobalState,
+};
 use bevy::prelude::*;
 
 pub struct UiPlugin;
@@ -6,13 +10,34 @@ pub struct UiPlugin;
 impl Plugin for UiPlugin {
 	fn build(&self, app: &mut App) {
 		app.add_systems(OnEnter(GlobalState::InWorld), spawn)
-			.add_systems(OnExit(GlobalState::InWorld), despawn);
+			.add_systems(OnExit(GlobalState::InWorld), despawn)
+			.add_systems(
+				Update,
+				(update_hotbar, update_health_bar).run_if(in_state(GlobalState::InWorld)),
+			);
 	}
 }
 
 #[derive(Component)]
 struct UiRoot;
 
+/// a slot of the hotbar, with the index of the slot
+#[derive(Component)]
+struct HotbarSlot(usize);
+
+/// the text inside of a [`HotbarSlot`], with the index of the slot
+#[derive(Component)]
+struct HotbarSlotText(usize);
+
+/// the part of the health bar that shrinks when the player loses health
+#[derive(Component)]
+struct HealthBarFill;
+
+const SLOT_COLOR: Color = Color::srgba(0.15, 0.15, 0.15, 0.8);
+const SELECTED_SLOT_BORDER: Color = Color::WHITE;
+const SLOT_BORDER: Color = Color::srgb(0.3, 0.3, 0.3);
+const HEALTH_COLOR: Color = Color::srgb(0.8, 0.1, 0.1);
+
 fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
 	let crosshair = asset_server.load("sprite/Crosshair.png");
 
@@ -36,9 +61,100 @@ fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
 				},
 				ImageNode::new(crosshair),
 			));
+			parent
+				.spawn((
+					Node {
+						position_type: PositionType::Absolute,
+						bottom: Val::VMin(11.5),
+						width: Val::VMin(30.0),
+						height: Val::VMin(1.5),
+						..default()
+					},
+					BackgroundColor::from(SLOT_COLOR),
+				))
+				.with_children(|parent| {
+					parent.spawn((
+						HealthBarFill,
+						Node {
+							width: Val::Percent(100.0),
+							height: Val::Percent(100.0),
+							..default()
+						},
+						BackgroundColor::from(HEALTH_COLOR),
+					));
+				});
+			parent
+				.spawn(Node {
+					position_type: PositionType::Absolute,
+					bottom: Val::VMin(2.0),
+					column_gap: Val::VMin(0.5),
+					..default()
+				})
+				.with_children(|parent| {
+					for i in 0..HOTBAR_SIZE {
+						parent
+							.spawn((
+								HotbarSlot(i),
+								Node {
+									width: Val::VMin(8.0),
+									height: Val::VMin(8.0),
+									border: UiRect::all(Val::VMin(0.4)),
+									justify_content: JustifyContent::Center,
+									align_items: AlignItems::Center,
+									..default()
+								},
+								BackgroundColor::from(SLOT_COLOR),
+								BorderColor::from(SLOT_BORDER),
+							))
+							.with_children(|parent| {
+								parent.spawn((
+									HotbarSlotText(i),
+									Text::new(""),
+									TextColor::from(Color::WHITE),
+									TextFont::from_font_size(12.),
+									TextLayout::new_with_justify(JustifyText::Center),
+								));
+							});
+					}
+				});
 		});
 }
 
+fn update_hotbar(
+	inventory: Query<&Inventory, (With<Player>, Changed<Inventory>)>,
+	mut slots: Query<(&HotbarSlot, &mut BorderColor)>,
+	mut texts: Query<(&HotbarSlotText, &mut Text)>,
+) {
+	let Ok(inventory) = inventory.get_single() else {
+		return;
+	};
+	for (slot, mut border) in &mut slots {
+		*border = if slot.0 == inventory.selected_slot() {
+			SELECTED_SLOT_BORDER.into()
+		} else {
+			SLOT_BORDER.into()
+		};
+	}
+	for (slot, mut text) in &mut texts {
+		text.0 = match inventory.hotbar()[slot.0] {
+			Some(stack) => format!("{}\n{}", stack.item.name(), stack.count),
+			None => String::new(),
+		};
+	}
+}
+
+fn update_health_bar(
+	health: Query<&Health, (With<Player>, Changed<Health>)>,
+	mut fill: Query<&mut Node, With<HealthBarFill>>,
+) {
+	let Ok(health) = health.get_single() else {
+		return;
+	};
+	for mut node in &mut fill {
+		node.width = Val::Percent(health.current / health.max * 100.0);
+	}
+}
+
 fn despawn(mut commands: Commands, root: Query<Entity, With<UiRoot>>) {
 	let root = root.single();
 	commands.entity(root).despawn_recursive();